@@ -5,7 +5,7 @@ use crate::config::Config;
 use crate::developer::{DeveloperState, DeveloperView};
 use crate::devices::{DevicesState, DevicesView};
 use crate::menu::MenuState;
-use crate::model::ModelView;
+use crate::model::{ModelState, ModelView};
 use crate::settings::SettingsState;
 use crate::topology::{TopologyState, TopologyView};
 use color_eyre::eyre::Result;
@@ -29,6 +29,7 @@ pub struct AppState {
     pub settings: SettingsState,
     pub devices: DevicesState,
     pub topology: TopologyState,
+    pub model: ModelState,
     pub developer: DeveloperState,
     pub chat: ChatState,
 }
@@ -74,6 +75,8 @@ pub struct App {
     /// Last time an arrow key was pressed (for ESC debouncing).
     /// See [`App::handle_crossterm_events`] for details.
     pub last_arrow_key_time: Instant,
+    /// Whether the terminal is focused, as reported by the terminal (if it supports it).
+    pub is_focused: bool,
 }
 
 impl App {
@@ -84,6 +87,9 @@ impl App {
 
     pub fn new_at_view(view: AppView) -> Result<Self> {
         let config = Config::load()?;
+        let mut state = AppState::default();
+        state.chat.session_mut().system_prompt = config.system_prompt.clone();
+
         Ok(Self {
             is_running: false,
 
@@ -91,7 +97,7 @@ impl App {
             event_stream: EventStream::new(),
             config,
             view,
-            state: AppState::default(),
+            state,
             model_selector_state: ModelSelectorState::new(),
             topology: None,
             is_api_online: false,
@@ -100,6 +106,7 @@ impl App {
             status_message: String::new(),
             animation_start: Instant::now(),
             last_arrow_key_time: Instant::now(),
+            is_focused: true,
         })
     }
 
@@ -137,6 +144,9 @@ impl App {
                 _ => {}
             }
 
+            // chat responses keep streaming in the background, regardless of the view
+            self.tick_chat_streams();
+
             // handle events with timeout to allow animation updates
             tokio::select! {
                 _ = interval.tick() => {
@@ -221,7 +231,14 @@ impl App {
                         AppView::Chat(view) => self.handle_chat_input(key, view),
                     }
                 }
-                Event::Mouse(_) => {} // no mouse events
+                Event::Mouse(mouse) => {
+                    // only the chat view makes use of the mouse (wheel scrolling)
+                    if let AppView::Chat(view) = &self.view.clone() {
+                        self.handle_chat_mouse(mouse, view);
+                    }
+                }
+                Event::FocusGained => self.is_focused = true,
+                Event::FocusLost => self.is_focused = false,
                 Event::Resize(_, _) => {}
                 _ => {}
             },
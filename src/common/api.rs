@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::common::{DeviceProperties, ModelInfo, TopologyInfo};
 
 use serde::{Deserialize, Serialize};
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
     base_url: String,
@@ -15,6 +15,14 @@ impl Default for ApiClient {
     }
 }
 
+/// Active topologies of the API, see [`ApiClient::get_topologies`].
+#[derive(Debug, Clone, Default)]
+pub struct Topologies {
+    pub topologies: Vec<TopologyInfo>,
+    /// Whether the API reported a list of topologies, i.e. supports several loaded models.
+    pub is_multi_model: bool,
+}
+
 impl ApiClient {
     pub fn new(host: &str, port: u16) -> Self {
         ApiClient {
@@ -50,18 +58,43 @@ impl ApiClient {
         Ok(models.data)
     }
 
+    /// Get the first active topology, see [`Self::get_topologies`].
     pub async fn get_topology(&self) -> color_eyre::Result<Option<TopologyInfo>> {
+        Ok(self.get_topologies().await?.topologies.into_iter().next())
+    }
+
+    /// Get the active topologies, one per loaded model.
+    ///
+    /// The API may report a single topology or a list of them, where a list tells that it
+    /// supports several loaded models. No topologies means that none has been prepared yet.
+    pub async fn get_topologies(&self) -> color_eyre::Result<Topologies> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum TopologyResponse {
+            Many(Vec<TopologyInfo>),
+            One(TopologyInfo),
+        }
+
         let url = format!("{}/v1/topology", self.base_url);
         let response = self.client.get(&url).send().await?;
 
         if response.status().is_success() {
-            let topology = response
-                .json::<TopologyInfo>()
+            let topologies = response
+                .json::<TopologyResponse>()
                 .await
                 .map_err(|e| color_eyre::eyre::eyre!("Failed to parse topology response: {}", e))?;
-            Ok(Some(topology))
+            Ok(match topologies {
+                TopologyResponse::Many(topologies) => Topologies {
+                    topologies,
+                    is_multi_model: true,
+                },
+                TopologyResponse::One(topology) => Topologies {
+                    topologies: vec![topology],
+                    is_multi_model: false,
+                },
+            })
         } else if response.status() == reqwest::StatusCode::BAD_REQUEST {
-            Ok(None)
+            Ok(Topologies::default())
         } else {
             color_eyre::eyre::bail!(
                 "Failed to get topology: ({}) {}",
@@ -86,6 +119,18 @@ impl ApiClient {
         Ok(devices_response.devices)
     }
 
+    /// Register a device with the manager by its address, for devices that
+    /// are not discovered automatically, e.g. those on another subnet.
+    pub async fn register_device(&self, device: &DeviceProperties) -> color_eyre::Result<()> {
+        let url = format!("{}/v1/devices/register", self.base_url);
+
+        let response = self.client.post(&url).json(device).send().await?;
+        if !response.status().is_success() {
+            color_eyre::eyre::bail!("Failed to register device: {}", response.text().await?);
+        }
+        Ok(())
+    }
+
     pub async fn load_model(&self, model: &str) -> color_eyre::Result<LoadModelResponse> {
         let url = format!("{}/v1/load_model", self.base_url);
         let body = serde_json::json!({"model": model});
@@ -99,10 +144,18 @@ impl ApiClient {
         Ok(load_response)
     }
 
-    pub async fn unload_model(&self) -> color_eyre::Result<()> {
+    /// Unload the given model, or all models if `None`.
+    ///
+    /// The model is only sent when given, for managers that serve a single model.
+    pub async fn unload_model(&self, model: Option<&str>) -> color_eyre::Result<()> {
         let url = format!("{}/v1/unload_model", self.base_url);
 
-        let response = self.client.post(&url).send().await?;
+        let request = self.client.post(&url);
+        let request = match model {
+            Some(model) => request.json(&serde_json::json!({ "model": model })),
+            None => request,
+        };
+        let response = request.send().await?;
         if response.status().is_success() {
             Ok(())
         } else {
@@ -110,6 +163,8 @@ impl ApiClient {
         }
     }
 
+    /// Prepare a topology for the model with the solver, which skips the
+    /// excluded devices of the config.
     pub async fn prepare_topology(
         &self,
         config: &crate::Config,
@@ -121,6 +176,7 @@ impl ApiClient {
             "kv_bits": config.kv_bits,
             "seq_len": config.seq_len,
             "max_batch_exp": config.max_batch_exp,
+            "excluded_devices": config.excluded_devices,
         });
 
         let response = self.client.post(&url).json(&body).send().await?;
@@ -162,6 +218,26 @@ impl ApiClient {
         let topology: TopologyInfo = response.json().await?;
         Ok(topology)
     }
+
+    /// Send an arbitrary request to the API, e.g. from the developer console,
+    /// returning the status and the body of the response whatever the status is.
+    pub async fn send_raw(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> color_eyre::Result<(reqwest::StatusCode, String)> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let request = self.client.request(method, &url);
+        let request = match body {
+            Some(body) => request.json(body),
+            None => request,
+        };
+        let response = request.send().await?;
+        let status = response.status();
+        Ok((status, response.text().await?))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<ApiMessage>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub stream: bool,
+}
+
+/// Request to the raw completions endpoint, where the prompt is not templated.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletionRequest {
+    pub model: String,
+    pub prompt: String,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub stream: bool,
+}
+
+/// A streaming request, either to the chat or the raw completions endpoint.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StreamRequest {
+    Chat(ChatRequest),
+    Completion(CompletionRequest),
+}
+
+impl StreamRequest {
+    /// Path of the endpoint for this request.
+    pub fn path(&self) -> &'static str {
+        match self {
+            StreamRequest::Chat(_) => "/v1/chat/completions",
+            StreamRequest::Completion(_) => "/v1/completions",
+        }
+    }
+
+    /// Whether the generation can be resumed after the content `generated` so far.
+    ///
+    /// Raw completions continue the prompt, but a partial chat response can not be
+    /// sent back without starting a new assistant turn, so chats are only retried
+    /// if nothing was generated yet.
+    pub fn can_resume(&self, generated: &str) -> bool {
+        match self {
+            StreamRequest::Chat(_) => generated.is_empty(),
+            StreamRequest::Completion(_) => true,
+        }
+    }
+
+    /// The request that resumes the generation from the content `generated` so far,
+    /// with the max tokens reduced by the `num_tokens` generated, see [`Self::can_resume`].
+    pub fn resumed(&self, generated: &str, num_tokens: u32) -> Self {
+        let mut request = self.clone();
+        if let StreamRequest::Completion(completion) = &mut request {
+            completion.prompt.push_str(generated);
+            completion.max_tokens = completion
+                .max_tokens
+                .map(|t| t.saturating_sub(num_tokens).max(1));
+        }
+        request
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiMessage {
+    pub role: String,
+    pub content: String,
+    /// Tool calls of an assistant message, sent back so that the turn is not empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ApiToolCall>,
+}
+
+/// A tool call in the shape that the API expects within messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: ApiFunction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiFunction {
+    pub name: String,
+    pub arguments: String,
+}
+
+impl ApiMessage {
+    pub fn system(content: &str) -> Self {
+        ApiMessage {
+            role: "system".to_string(),
+            content: content.to_string(),
+            tool_calls: Vec::new(),
+        }
+    }
+
+    pub fn user(content: &str) -> Self {
+        ApiMessage {
+            role: "user".to_string(),
+            content: content.to_string(),
+            tool_calls: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct StreamChunk {
+    id: String,
+    object: String,
+    created: u64,
+    model: String,
+    pub choices: Vec<StreamChoice>,
+    /// Token usage, only present in the final chunk.
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// Token usage of a completion, as reported in the final stream chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+impl Usage {
+    /// A short summary for the message header, e.g. `12 prompt + 34 completion tokens`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} prompt + {} completion tokens",
+            self.prompt_tokens, self.completion_tokens
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct StreamChoice {
+    index: usize,
+    /// Content of chat completions.
+    #[serde(default)]
+    pub delta: StreamDelta,
+    /// Content of raw completions, which have no delta.
+    #[serde(default)]
+    pub text: Option<String>,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[allow(dead_code)]
+pub struct StreamDelta {
+    pub role: Option<String>,
+    pub content: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// A piece of a tool call within a stream delta, the name and arguments
+/// arrive in fragments that are concatenated by `index`.
+#[derive(Debug, Deserialize)]
+pub struct ToolCallDelta {
+    /// Index of the call, omitted by some servers that only stream a single call.
+    #[serde(default)]
+    pub index: usize,
+    pub id: Option<String>,
+    pub function: Option<FunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FunctionDelta {
+    pub name: Option<String>,
+    pub arguments: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_chunk_usage() {
+        let json = r#"{"id":"1","object":"chat.completion.chunk","created":0,"model":"m","choices":[],"usage":{"prompt_tokens":12,"completion_tokens":34,"total_tokens":46}}"#;
+        let chunk: StreamChunk = serde_json::from_str(json).unwrap();
+        let usage = chunk.usage.unwrap();
+        assert_eq!(usage.total_tokens, 46);
+        assert_eq!(usage.summary(), "12 prompt + 34 completion tokens");
+
+        let json =
+            r#"{"id":"1","object":"chat.completion.chunk","created":0,"model":"m","choices":[]}"#;
+        let chunk: StreamChunk = serde_json::from_str(json).unwrap();
+        assert_eq!(chunk.usage, None);
+    }
+
+    #[test]
+    fn test_stream_request_resumed() {
+        let chat = StreamRequest::Chat(ChatRequest {
+            model: "m".to_string(),
+            messages: vec![ApiMessage::system("be brief")],
+            max_tokens: Some(10),
+            temperature: None,
+            stream: true,
+        });
+        assert!(chat.can_resume(""));
+        assert!(!chat.can_resume("partial"));
+
+        let completion = StreamRequest::Completion(CompletionRequest {
+            model: "m".to_string(),
+            prompt: "Once upon".to_string(),
+            max_tokens: Some(10),
+            temperature: None,
+            stream: true,
+        });
+        assert!(completion.can_resume(" a time"));
+        let StreamRequest::Completion(resumed) = completion.resumed(" a time", 3) else {
+            panic!("expected a completion request");
+        };
+        assert_eq!(resumed.prompt, "Once upon a time");
+        assert_eq!(resumed.max_tokens, Some(7));
+    }
+}
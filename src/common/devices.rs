@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
 
 /// A device info as retrieved from the API, which reads from the discovery module.
 ///
@@ -24,6 +25,39 @@ pub struct DeviceProperties {
     pub thunderbolt: Option<ThunderboltData>,
 }
 
+impl DeviceProperties {
+    /// Whether one of the device's own thunderbolt instances has the given domain UUID.
+    pub fn owns_thunderbolt(&self, uuid: &str) -> bool {
+        self.thunderbolt
+            .as_ref()
+            .is_some_and(|tb| tb.instances.iter().any(|(own, _)| own.uuid == uuid))
+    }
+}
+
+/// Thunderbolt links between the devices, as pairs of device indices.
+///
+/// A device is linked to another if one of its thunderbolt instances is connected
+/// to an instance of the other, matched by the domain UUID. Each link is listed once.
+pub fn thunderbolt_links<D: Borrow<DeviceProperties>>(devices: &[D]) -> Vec<(usize, usize)> {
+    let mut links = Vec::new();
+    for (i, device) in devices.iter().enumerate() {
+        let Some(thunderbolt) = &device.borrow().thunderbolt else {
+            continue;
+        };
+        for connected in thunderbolt.instances.iter().flat_map(|(_, c)| c) {
+            let peer = (0..devices.len())
+                .find(|&j| j != i && devices[j].borrow().owns_thunderbolt(&connected.uuid));
+            if let Some(j) = peer {
+                let link = (i.min(j), i.max(j));
+                if !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+        }
+    }
+    links
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThunderboltData {
     /// The IP address of the Thunderbolt device.
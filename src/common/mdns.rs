@@ -0,0 +1,143 @@
+//! Local mDNS discovery, used when the manager API is offline.
+use super::DeviceProperties;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Service type that the dnet nodes advertise.
+pub const DNET_SERVICE_TYPE: &str = "_dnet._tcp.local.";
+
+/// How long a discovery browses the network for.
+const BROWSE_DURATION: Duration = Duration::from_secs(3);
+
+/// Devices discovered on the local network, keyed by service name.
+pub type DiscoveredDevices = Result<HashMap<String, DeviceProperties>, String>;
+
+/// A discovery of the devices on the local network, running in the background.
+#[derive(Debug, Default)]
+pub struct MdnsDiscovery {
+    /// Receiver of the devices of the running discovery, if any.
+    rx: Option<mpsc::UnboundedReceiver<DiscoveredDevices>>,
+}
+
+impl MdnsDiscovery {
+    /// Start browsing for the devices, unless a discovery is already running.
+    pub fn start(&mut self) {
+        if self.rx.is_some() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::task::spawn_blocking(move || {
+            tx.send(browse(BROWSE_DURATION)).ok();
+        });
+        self.rx = Some(rx);
+    }
+
+    /// Whether a discovery is running.
+    pub fn is_pending(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Collect the discovered devices, if the discovery has finished.
+    pub fn poll(&mut self) -> Option<DiscoveredDevices> {
+        let devices = self.rx.as_mut()?.try_recv().ok()?;
+        self.rx = None;
+        Some(devices)
+    }
+}
+
+/// Browse for the dnet services for the given duration, blocking.
+fn browse(duration: Duration) -> DiscoveredDevices {
+    let daemon = ServiceDaemon::new().map_err(|e| format!("Failed to start mDNS: {}", e))?;
+    let receiver = daemon
+        .browse(DNET_SERVICE_TYPE)
+        .map_err(|e| format!("Failed to browse mDNS: {}", e))?;
+
+    let deadline = Instant::now() + duration;
+    let mut devices = HashMap::new();
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match receiver.recv_timeout(remaining) {
+            Ok(ServiceEvent::ServiceResolved(info)) => {
+                if let Some(device) = device_from_service(&info) {
+                    devices.insert(info.get_fullname().to_string(), device);
+                }
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    daemon.shutdown().ok();
+    Ok(devices)
+}
+
+/// Device properties from the TXT records of a resolved service, which carry the
+/// fields of [`DeviceProperties`]. The instance defaults to the service's name, the
+/// IP to its first IPv4 address and the HTTP port to the service's port.
+///
+/// Returns `None` if the service has no address.
+pub fn device_from_service(info: &ServiceInfo) -> Option<DeviceProperties> {
+    let property = |key: &str| info.get_property_val_str(key);
+    let flag = |key: &str| property(key).is_some_and(|v| matches!(v, "true" | "True" | "1"));
+    let port = |key: &str| property(key).and_then(|v| v.parse().ok());
+
+    let local_ip = match property("local_ip") {
+        Some(ip) => ip.to_string(),
+        None => info
+            .get_addresses_v4()
+            .into_iter()
+            .min()
+            .map(|ip| ip.to_string())?,
+    };
+    let instance = match property("instance") {
+        Some(instance) => instance.to_string(),
+        None => info
+            .get_fullname()
+            .trim_end_matches(DNET_SERVICE_TYPE)
+            .trim_end_matches('.')
+            .to_string(),
+    };
+
+    Some(DeviceProperties {
+        is_manager: flag("is_manager"),
+        is_busy: flag("is_busy"),
+        instance,
+        server_port: port("server_port").unwrap_or(info.get_port()),
+        shard_port: port("shard_port").unwrap_or_default(),
+        local_ip,
+        thunderbolt: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_from_service() {
+        let properties = HashMap::from([
+            ("is_manager".to_string(), "false".to_string()),
+            ("is_busy".to_string(), "true".to_string()),
+            ("shard_port".to_string(), "58081".to_string()),
+        ]);
+        let info = ServiceInfo::new(
+            DNET_SERVICE_TYPE,
+            "shard-01",
+            "mac-mini.local.",
+            "192.168.1.20",
+            8081,
+            properties,
+        )
+        .unwrap();
+
+        let device = device_from_service(&info).unwrap();
+        assert_eq!(device.instance, "shard-01");
+        assert_eq!(device.local_ip, "192.168.1.20");
+        assert_eq!(device.server_port, 8081);
+        assert_eq!(device.shard_port, 58081);
+        assert!(device.is_busy);
+        assert!(!device.is_manager);
+    }
+}
@@ -9,3 +9,17 @@ pub use shard::*;
 
 mod api;
 pub use api::*;
+
+mod sse;
+pub use sse::*;
+
+mod completions;
+pub use completions::*;
+
+mod stream;
+pub use stream::*;
+
+#[cfg(feature = "mdns")]
+mod mdns;
+#[cfg(feature = "mdns")]
+pub use mdns::*;
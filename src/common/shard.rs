@@ -22,4 +22,67 @@ pub struct ShardHealth {
     pub http_port: u16,
     /// Shard name
     pub instance: String,
+    /// Resource usage, if the shard reports it.
+    #[serde(flatten)]
+    pub resources: ShardResources,
+    /// Progress of the download of the weights, if the shard is fetching them from the hub.
+    #[serde(default)]
+    pub download: Option<DownloadProgress>,
+}
+
+/// Progress of the download of the weights of a shard.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    /// Bytes downloaded so far.
+    pub downloaded_bytes: u64,
+    /// Total bytes to download, if known.
+    #[serde(default)]
+    pub total_bytes: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Downloaded fraction in `[0, 1]`, if the total is known.
+    pub fn ratio(&self) -> Option<f64> {
+        self.total_bytes
+            .filter(|&total| total > 0)
+            .map(|total| (self.downloaded_bytes as f64 / total as f64).min(1.0))
+    }
+
+    /// Whether all bytes are downloaded.
+    pub fn is_complete(&self) -> bool {
+        self.total_bytes
+            .is_some_and(|total| self.downloaded_bytes >= total)
+    }
+}
+
+/// Resource usage of a shard, reported by newer shards in `/health` or `/stats`.
+///
+/// All fields are optional, as older shards do not report them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShardResources {
+    /// Memory used by the shard, in bytes.
+    #[serde(default)]
+    pub memory_used: Option<u64>,
+    /// Total memory of the device, in bytes.
+    #[serde(default)]
+    pub memory_total: Option<u64>,
+    /// Bytes of model weights that are loaded.
+    #[serde(default)]
+    pub weights_bytes: Option<u64>,
+    /// Size of the KV cache, in bytes.
+    #[serde(default)]
+    pub kv_cache_bytes: Option<u64>,
+    /// Disk space used on the device, in bytes.
+    #[serde(default)]
+    pub disk_used: Option<u64>,
+    /// Total disk space of the device, in bytes.
+    #[serde(default)]
+    pub disk_total: Option<u64>,
+}
+
+impl ShardResources {
+    /// Whether nothing is reported.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
@@ -0,0 +1,133 @@
+/// A single server-sent event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SseEvent {
+    /// Event type, `None` means the default `message` type.
+    pub event: Option<String>,
+    /// Event data, multiple `data:` fields are joined with newlines.
+    pub data: String,
+    /// Event id, if given.
+    pub id: Option<String>,
+}
+
+/// An incremental parser for [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+///
+/// Bytes are pushed as they arrive, and complete events are returned as soon as
+/// they are terminated by a blank line. Handles `\n`, `\r\n` and `\r` line endings,
+/// multi-line `data:` fields, comments and `event:` types, as well as lines (and
+/// UTF-8 characters) that are split across chunks.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes of the incomplete line so far.
+    buffer: Vec<u8>,
+    /// Whether the last line ended with `\r`, in which case a leading `\n` is skipped.
+    after_cr: bool,
+    /// Event that is being built.
+    event: SseEvent,
+    /// Whether the event being built has any data.
+    has_data: bool,
+}
+
+impl SseParser {
+    /// Push a chunk of bytes, returning the events that are completed by it.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            match byte {
+                b'\n' if self.after_cr => self.after_cr = false,
+                b'\n' | b'\r' => {
+                    self.after_cr = byte == b'\r';
+                    let line = String::from_utf8_lossy(&self.buffer).to_string();
+                    self.buffer.clear();
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => {
+                    self.after_cr = false;
+                    self.buffer.push(byte);
+                }
+            }
+        }
+        events
+    }
+
+    /// Flush at the end of the stream, returning the last event if it was not
+    /// terminated by a blank line (which some servers omit).
+    pub fn finish(&mut self) -> Vec<SseEvent> {
+        let mut events = self.push(b"\n");
+        events.extend(self.process_line(""));
+        events
+    }
+
+    /// Process a complete line, returning an event if the line dispatches one.
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        // a blank line dispatches the event, if it has any data
+        if line.is_empty() {
+            let event = std::mem::take(&mut self.event);
+            return std::mem::take(&mut self.has_data).then_some(event);
+        }
+
+        // comments, e.g. keep-alive pings
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                if self.has_data {
+                    self.event.data.push('\n');
+                }
+                self.event.data.push_str(value);
+                self.has_data = true;
+            }
+            "event" => self.event.event = Some(value.to_string()),
+            "id" => self.event.id = Some(value.to_string()),
+            // `retry` and unknown fields are ignored
+            _ => {}
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser() {
+        let mut parser = SseParser::default();
+
+        // line endings, comments and split chunks
+        assert!(parser.push(b": ping\r\ndata: hel").is_empty());
+        let events = parser.push(b"lo\r\n\r\ndata: world\n\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "hello");
+        assert_eq!(events[1].data, "world");
+
+        // multi-line data with an event type, and `\r` split from its `\n`
+        assert!(parser.push(b"event: error\rdata: a\r").is_empty());
+        let events = parser.push(b"\ndata:b\r\n\r\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: Some("error".to_string()),
+                data: "a\nb".to_string(),
+                id: None,
+            }]
+        );
+
+        // multi-byte character split across chunks
+        let bytes = "data: çay\n\n".as_bytes();
+        assert!(parser.push(&bytes[..8]).is_empty());
+        assert_eq!(parser.push(&bytes[8..])[0].data, "çay");
+
+        // unterminated event at the end of the stream
+        assert!(parser.push(b"data: [DONE]").is_empty());
+        assert_eq!(parser.finish()[0].data, "[DONE]");
+    }
+}
@@ -0,0 +1,310 @@
+use crate::common::{ApiMessage, ChatRequest, SseParser, StreamChunk, StreamRequest};
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+/// Timing statistics of a single streamed response.
+///
+/// Each received chunk is counted as a token, which matches the
+/// one-token-per-delta behavior of the dnet API.
+#[derive(Debug, Clone)]
+pub struct GenerationStats {
+    /// When the request was submitted.
+    pub started_at: Instant,
+    /// When the first token arrived.
+    pub first_token_at: Option<Instant>,
+    /// When the stream finished (or was aborted).
+    pub finished_at: Option<Instant>,
+    /// Number of tokens received so far.
+    pub num_tokens: usize,
+}
+
+impl GenerationStats {
+    pub fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            first_token_at: None,
+            finished_at: None,
+            num_tokens: 0,
+        }
+    }
+
+    /// Record a newly received token.
+    pub fn record_token(&mut self) {
+        if self.first_token_at.is_none() {
+            self.first_token_at = Some(Instant::now());
+        }
+        self.num_tokens += 1;
+    }
+
+    /// Mark the generation as finished, no-op if already finished.
+    pub fn finish(&mut self) {
+        if self.finished_at.is_none() {
+            self.finished_at = Some(Instant::now());
+        }
+    }
+
+    /// Time to first token.
+    pub fn ttft(&self) -> Option<Duration> {
+        self.first_token_at
+            .map(|first| first.duration_since(self.started_at))
+    }
+
+    /// Tokens per second, measured from the first token until the end
+    /// (or until now, if still generating).
+    pub fn tokens_per_sec(&self) -> Option<f64> {
+        let first = self.first_token_at?;
+        let end = self.finished_at.unwrap_or_else(Instant::now);
+        let secs = end.duration_since(first).as_secs_f64();
+        (secs > 0.0).then(|| self.num_tokens as f64 / secs)
+    }
+
+    /// A one-line summary, e.g. `TTFT: 0.42s | 12.3 tok/s | 128 tokens`.
+    pub fn summary(&self) -> String {
+        let ttft = self
+            .ttft()
+            .map(|d| format!("{:.2}s", d.as_secs_f64()))
+            .unwrap_or_else(|| "-".to_string());
+        let tps = self
+            .tokens_per_sec()
+            .map(|t| format!("{:.1}", t))
+            .unwrap_or_else(|| "-".to_string());
+        format!(
+            "TTFT: {} | {} tok/s | {} tokens",
+            ttft, tps, self.num_tokens
+        )
+    }
+}
+
+/// A single run of a benchmark, returning its timing statistics.
+pub async fn bench_run(
+    client: &reqwest::Client,
+    url: &str,
+    request: &StreamRequest,
+) -> Result<GenerationStats, String> {
+    let mut stats = GenerationStats::new(Instant::now());
+    stream_tokens(client, url, request, |_| stats.record_token()).await?;
+    stats.finish();
+    Ok(stats)
+}
+
+/// Result of a batch of concurrent requests, see [`bench_batch`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchStats {
+    pub num_ok: usize,
+    pub num_failed: usize,
+    /// Tokens generated over all requests of the batch.
+    pub num_tokens: usize,
+    /// Average time to first token of the successful requests.
+    pub ttft: Option<Duration>,
+    /// Wall time of the whole batch.
+    pub elapsed: Duration,
+    /// Error of the first failed request, if any.
+    pub error: Option<String>,
+}
+
+impl BatchStats {
+    /// Aggregate of the requests of a batch that took `elapsed` in total.
+    pub fn new(results: &[Result<GenerationStats, String>], elapsed: Duration) -> Self {
+        let ttfts = results
+            .iter()
+            .filter_map(|r| r.as_ref().ok()?.ttft())
+            .collect::<Vec<_>>();
+        Self {
+            num_ok: results.iter().filter(|r| r.is_ok()).count(),
+            num_failed: results.iter().filter(|r| r.is_err()).count(),
+            num_tokens: results
+                .iter()
+                .filter_map(|r| r.as_ref().ok())
+                .map(|s| s.num_tokens)
+                .sum(),
+            ttft: (!ttfts.is_empty()).then(|| ttfts.iter().sum::<Duration>() / ttfts.len() as u32),
+            elapsed,
+            error: results.iter().find_map(|r| r.as_ref().err().cloned()),
+        }
+    }
+
+    /// Tokens per second of the whole batch, over its wall time.
+    pub fn tokens_per_sec(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (secs > 0.0 && self.num_ok > 0).then(|| self.num_tokens as f64 / secs)
+    }
+}
+
+/// Send `batch_size` chat completions of the prompt at once, generating
+/// at most `max_tokens` each, e.g. for the cluster benchmark.
+pub async fn bench_batch(
+    api_url: &str,
+    model: &str,
+    prompt: &str,
+    max_tokens: u32,
+    temperature: f32,
+    batch_size: usize,
+) -> BatchStats {
+    let request = StreamRequest::Chat(ChatRequest {
+        model: model.to_string(),
+        messages: vec![ApiMessage::user(prompt)],
+        max_tokens: Some(max_tokens),
+        temperature: Some(temperature),
+        stream: true,
+    });
+    let url = format!("{}{}", api_url, request.path());
+
+    let client = reqwest::Client::new();
+    let started_at = Instant::now();
+    let results =
+        futures::future::join_all((0..batch_size).map(|_| bench_run(&client, &url, &request)))
+            .await;
+    BatchStats::new(&results, started_at.elapsed())
+}
+
+/// Prompt of the warm-up request, see [`warm_up`].
+const WARM_UP_PROMPT: &str = "Hi!";
+
+/// Max tokens of the warm-up request, enough to tell that the output makes sense.
+const WARM_UP_MAX_TOKENS: u32 = 16;
+
+/// Result of a warm-up request, the first tokens of the model and their timing.
+#[derive(Debug, Clone)]
+pub struct WarmUp {
+    pub stats: GenerationStats,
+    pub text: String,
+}
+
+impl WarmUp {
+    /// The generated text on a single line, truncated to `max_chars`.
+    pub fn preview(&self, max_chars: usize) -> String {
+        let text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.chars().count() > max_chars {
+            format!("{}...", text.chars().take(max_chars).collect::<String>())
+        } else {
+            text
+        }
+    }
+}
+
+/// Send a tiny chat completion to the model, as a smoke test of the whole ring.
+pub async fn warm_up(api_url: String, model: String) -> Result<WarmUp, String> {
+    let request = StreamRequest::Chat(ChatRequest {
+        model,
+        messages: vec![ApiMessage::user(WARM_UP_PROMPT)],
+        max_tokens: Some(WARM_UP_MAX_TOKENS),
+        temperature: Some(0.0),
+        stream: true,
+    });
+    let url = format!("{}{}", api_url, request.path());
+
+    let mut stats = GenerationStats::new(Instant::now());
+    let mut text = String::new();
+    stream_tokens(&reqwest::Client::new(), &url, &request, |token| {
+        stats.record_token();
+        text.push_str(token);
+    })
+    .await?;
+    stats.finish();
+
+    if stats.num_tokens == 0 {
+        return Err("No tokens were generated".to_string());
+    }
+    Ok(WarmUp { stats, text })
+}
+
+/// Stream the response of the request, calling `on_token` with each token until it finishes.
+pub async fn stream_tokens(
+    client: &reqwest::Client,
+    url: &str,
+    request: &StreamRequest,
+    mut on_token: impl FnMut(&str),
+) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut parser = SseParser::default();
+    'stream: while let Some(chunk) = stream.next().await {
+        for event in parser.push(&chunk.map_err(|e| e.to_string())?) {
+            if event.data.trim() == "[DONE]" {
+                break 'stream;
+            }
+
+            let Ok(chunk) = serde_json::from_str::<StreamChunk>(&event.data) else {
+                continue;
+            };
+            if let Some(choice) = chunk.choices.first() {
+                if let Some(token) = choice.delta.content.as_ref().or(choice.text.as_ref()) {
+                    on_token(token);
+                }
+                if choice.finish_reason.is_some() {
+                    break 'stream;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_stats() {
+        let start = Instant::now();
+        let mut stats = GenerationStats::new(start);
+        assert_eq!(stats.ttft(), None);
+        assert_eq!(stats.tokens_per_sec(), None);
+        assert_eq!(stats.summary(), "TTFT: - | - tok/s | 0 tokens");
+
+        stats.first_token_at = Some(start + Duration::from_millis(500));
+        stats.finished_at = Some(start + Duration::from_millis(2500));
+        stats.num_tokens = 20;
+        assert_eq!(stats.ttft(), Some(Duration::from_millis(500)));
+        assert_eq!(stats.tokens_per_sec(), Some(10.0));
+        assert_eq!(stats.summary(), "TTFT: 0.50s | 10.0 tok/s | 20 tokens");
+
+        // finishing again should not move the end time
+        stats.finish();
+        assert_eq!(stats.tokens_per_sec(), Some(10.0));
+    }
+
+    #[test]
+    fn test_warm_up_preview() {
+        let warm_up = WarmUp {
+            stats: GenerationStats::new(Instant::now()),
+            text: "Hello!\n\nHow can I help  you today?".to_string(),
+        };
+        assert_eq!(warm_up.preview(40), "Hello! How can I help you today?");
+        assert_eq!(warm_up.preview(10), "Hello! How...");
+    }
+
+    #[test]
+    fn test_batch_stats() {
+        let start = Instant::now();
+        let run = |ttft_ms: u64, num_tokens: usize| {
+            let mut stats = GenerationStats::new(start);
+            stats.first_token_at = Some(start + Duration::from_millis(ttft_ms));
+            stats.num_tokens = num_tokens;
+            Ok(stats)
+        };
+
+        let results = [run(200, 10), run(400, 30), Err("HTTP 500".to_string())];
+        let stats = BatchStats::new(&results, Duration::from_secs(2));
+        assert_eq!(stats.num_ok, 2);
+        assert_eq!(stats.num_failed, 1);
+        assert_eq!(stats.num_tokens, 40);
+        assert_eq!(stats.ttft, Some(Duration::from_millis(300)));
+        assert_eq!(stats.tokens_per_sec(), Some(20.0));
+        assert_eq!(stats.error.as_deref(), Some("HTTP 500"));
+
+        let stats = BatchStats::new(&[Err("HTTP 500".to_string())], Duration::from_secs(1));
+        assert_eq!(stats.ttft, None);
+        assert_eq!(stats.tokens_per_sec(), None);
+    }
+}
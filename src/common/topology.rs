@@ -19,8 +19,11 @@ pub struct TopologyInfo {
     /// Each [`Assignment`] describes which layers are assigned to which instancee,
     /// and the `instance` field corresponds to the `instance` field in [`DeviceProperties`].
     pub assignments: Vec<AssignmentInfo>,
-    // can be anything
-    // pub solution: Option<serde_json::Value>,
+    /// Output of the solver, which can be anything.
+    ///
+    /// Only topologies prepared by the solver have one, manual topologies do not.
+    #[serde(default)]
+    pub solution: Option<serde_json::Value>,
     pub kv_bits: KVBits,
 }
 
@@ -44,3 +47,23 @@ pub struct AssignmentInfo {
     pub window_size: u32,
     pub residency_size: u32,
 }
+
+/// Whether the instance of an assignment (i.e. of the service) refers to the device
+/// with the given instance, which it contains rather than being equal to.
+pub fn is_instance_of(service: &str, device: &str) -> bool {
+    service.contains(device)
+}
+
+impl AssignmentInfo {
+    /// Whether this is the assignment of the device with the given instance.
+    pub fn is_for(&self, device: &str) -> bool {
+        is_instance_of(&self.instance, device)
+    }
+}
+
+impl TopologyInfo {
+    /// Assignment of the device with the given instance, if it has one.
+    pub fn assignment_of(&self, device: &str) -> Option<&AssignmentInfo> {
+        self.assignments.iter().find(|a| a.is_for(device))
+    }
+}
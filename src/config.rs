@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -26,6 +27,17 @@ impl std::fmt::Display for KVBits {
     }
 }
 
+impl KVBits {
+    /// Number of bits per cached element.
+    pub fn bits(&self) -> u64 {
+        match self {
+            KVBits::Bits4 => 4,
+            KVBits::Bits8 => 8,
+            KVBits::FP16 => 16,
+        }
+    }
+}
+
 impl FromStr for KVBits {
     type Err = color_eyre::eyre::Error;
 
@@ -39,6 +51,238 @@ impl FromStr for KVBits {
     }
 }
 
+/// File format used when exporting a chat conversation.
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExportFormat {
+    #[default]
+    #[serde(rename = "md")]
+    Markdown,
+    #[serde(rename = "json")]
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for this format, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(color_eyre::eyre::eyre!("Invalid export format: {}", s)),
+        }
+    }
+}
+
+/// How chat message timestamps are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// Time of day, e.g. `14:05`.
+    #[default]
+    #[serde(rename = "time")]
+    Time,
+    /// Date and time, e.g. `2025-01-31 14:05`.
+    #[serde(rename = "datetime")]
+    DateTime,
+    /// RFC 3339, e.g. `2025-01-31T14:05:09+03:00`.
+    #[serde(rename = "iso")]
+    Iso,
+    /// Relative to now, e.g. `5m ago`.
+    #[serde(rename = "relative")]
+    Relative,
+}
+
+impl TimestampFormat {
+    /// Format the given timestamp.
+    pub fn format(&self, timestamp: &chrono::DateTime<chrono::Local>) -> String {
+        match self {
+            TimestampFormat::Time => timestamp.format("%H:%M").to_string(),
+            TimestampFormat::DateTime => timestamp.format("%Y-%m-%d %H:%M").to_string(),
+            TimestampFormat::Iso => timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            TimestampFormat::Relative => {
+                let elapsed = chrono::Local::now().signed_duration_since(timestamp);
+                if elapsed.num_minutes() < 1 {
+                    "just now".to_string()
+                } else if elapsed.num_hours() < 1 {
+                    format!("{}m ago", elapsed.num_minutes())
+                } else if elapsed.num_days() < 1 {
+                    format!("{}h ago", elapsed.num_hours())
+                } else {
+                    format!("{}d ago", elapsed.num_days())
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TimestampFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimestampFormat::Time => write!(f, "time"),
+            TimestampFormat::DateTime => write!(f, "datetime"),
+            TimestampFormat::Iso => write!(f, "iso"),
+            TimestampFormat::Relative => write!(f, "relative"),
+        }
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "time" => Ok(TimestampFormat::Time),
+            "datetime" => Ok(TimestampFormat::DateTime),
+            "iso" => Ok(TimestampFormat::Iso),
+            "relative" => Ok(TimestampFormat::Relative),
+            _ => Err(color_eyre::eyre::eyre!("Invalid timestamp format: {}", s)),
+        }
+    }
+}
+
+/// How to notify when a chat response finishes while the TUI is not being looked at,
+/// i.e. the terminal is unfocused or another view is open.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ChatNotification {
+    #[default]
+    #[serde(rename = "off")]
+    Off,
+    /// Ring the terminal bell.
+    #[serde(rename = "bell")]
+    Bell,
+    /// Desktop notification with the OSC 9 escape sequence, supported by
+    /// iTerm2, WezTerm, Windows Terminal and others.
+    #[serde(rename = "osc")]
+    Osc,
+}
+
+impl ChatNotification {
+    /// Send the notification with the given message to the terminal.
+    pub fn notify(&self, message: &str) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let sequence = match self {
+            ChatNotification::Off => return Ok(()),
+            ChatNotification::Bell => "\x07".to_string(),
+            // control characters would end the sequence early
+            ChatNotification::Osc => format!(
+                "\x1b]9;{}\x07",
+                message.replace(|c: char| c.is_control(), " ")
+            ),
+        };
+
+        let mut stdout = std::io::stdout();
+        stdout.write_all(sequence.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+impl std::fmt::Display for ChatNotification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChatNotification::Off => write!(f, "off"),
+            ChatNotification::Bell => write!(f, "bell"),
+            ChatNotification::Osc => write!(f, "osc"),
+        }
+    }
+}
+
+impl FromStr for ChatNotification {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" => Ok(ChatNotification::Off),
+            "bell" => Ok(ChatNotification::Bell),
+            "osc" => Ok(ChatNotification::Osc),
+            _ => Err(color_eyre::eyre::eyre!("Invalid notification: {}", s)),
+        }
+    }
+}
+
+/// A rule to strip special tokens of a model family from the streamed chat content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanupRule {
+    /// Applies to the models whose id contains this (case-insensitive), or to all models if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Token to remove, or a regular expression if `regex` is set.
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub regex: bool,
+}
+
+impl CleanupRule {
+    fn literal(pattern: &str) -> Self {
+        Self {
+            model: None,
+            pattern: pattern.to_string(),
+            regex: false,
+        }
+    }
+
+    /// Whether this rule applies to the given model.
+    pub fn applies_to(&self, model: &str) -> bool {
+        self.model
+            .as_ref()
+            .is_none_or(|m| model.to_lowercase().contains(&m.to_lowercase()))
+    }
+}
+
+/// Colors of the chat roles, as color names (e.g. `green`) or hex codes (e.g. `#00ff00`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatTheme {
+    pub user: String,
+    pub assistant: String,
+    /// Color of the thinking content and system messages.
+    pub thinking: String,
+}
+
+impl Default for ChatTheme {
+    fn default() -> Self {
+        Self {
+            user: "green".to_string(),
+            assistant: "blue".to_string(),
+            thinking: "#fff6e5".to_string(),
+        }
+    }
+}
+
+/// Parse a color, making sure that it is valid.
+fn parse_color(value: &str) -> color_eyre::Result<String> {
+    let value = value.trim();
+    ratatui::style::Color::from_str(value)
+        .map_err(|_| color_eyre::eyre::eyre!("Invalid color: {}", value))?;
+    Ok(value.to_string())
+}
+
+/// Parse an on/off toggle, also accepting `true` & `false`.
+fn parse_toggle(value: &str) -> color_eyre::Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "on" | "true" => Ok(true),
+        "off" | "false" => Ok(false),
+        other => Err(color_eyre::eyre::eyre!(
+            "Expected on or off, got: {}",
+            other
+        )),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub api_host: String,
@@ -49,12 +293,84 @@ pub struct Config {
     pub temperature: f32,
     #[serde(default = "default_devices_refresh_interval")]
     pub devices_refresh_interval: u64,
+    /// Topology ring refresh interval in seconds, 0 disables the auto-refresh.
+    #[serde(default = "default_topology_refresh_interval")]
+    pub topology_refresh_interval: u64,
+    /// Shard health refresh interval in seconds, 0 disables the auto-refresh.
+    #[serde(default = "default_shard_refresh_interval")]
+    pub shard_refresh_interval: u64,
     #[serde(default)]
     pub kv_bits: KVBits,
     #[serde(default = "default_max_batch_exp")]
     pub max_batch_exp: u8,
     #[serde(default = "default_seq_len")]
     pub seq_len: u32,
+    /// Send a short chat completion after a successful load, to check that the ring works.
+    #[serde(default)]
+    pub warm_up: bool,
+    #[serde(default)]
+    pub export_format: ExportFormat,
+    /// Default system prompt for new chat sessions, empty means none.
+    #[serde(default)]
+    pub system_prompt: String,
+    #[serde(default)]
+    pub timestamp_format: TimestampFormat,
+    /// Snapshot the chat every this many messages, 0 disables autosave.
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval: u32,
+    /// Token budget of the chat history, the oldest turns beyond it are not sent.
+    /// 0 fits the history within `seq_len`, leaving room for the max tokens.
+    #[serde(default)]
+    pub context_budget: u32,
+    #[serde(default)]
+    pub notification: ChatNotification,
+    /// Rules to strip special tokens from the chat, edited in the config file.
+    #[serde(default = "default_token_cleanup")]
+    pub token_cleanup: Vec<CleanupRule>,
+    #[serde(default)]
+    pub theme: ChatTheme,
+    /// Friendly names of the devices by instance, edited in the config file.
+    #[serde(default)]
+    pub device_aliases: HashMap<String, String>,
+    /// Instances of the devices that are left out of the topologies, toggled in the devices view.
+    #[serde(default)]
+    pub excluded_devices: BTreeSet<String>,
+    /// Starred models, listed first in the model selector.
+    #[serde(default)]
+    pub favorite_models: BTreeSet<String>,
+    /// Recently loaded models, the latest first, listed after the favorites.
+    #[serde(default)]
+    pub recent_models: Vec<String>,
+    /// Durations of the latest loads in seconds, keyed by the model and the shards
+    /// it was loaded on (see [`Config::load_key`]), to estimate the next load.
+    #[serde(default)]
+    pub load_durations: HashMap<String, Vec<u64>>,
+    /// Saved combinations of a model and its load settings, to load in one step.
+    #[serde(default)]
+    pub load_profiles: Vec<LoadProfile>,
+}
+
+/// A named model along with the settings to load it with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadProfile {
+    pub name: String,
+    pub model: String,
+    pub kv_bits: KVBits,
+    pub seq_len: u32,
+    pub max_batch_exp: u8,
+}
+
+impl LoadProfile {
+    /// Profile of the model with the current settings of the config.
+    pub fn new(name: &str, model: &str, config: &Config) -> Self {
+        Self {
+            name: name.to_string(),
+            model: model.to_string(),
+            kv_bits: config.kv_bits,
+            seq_len: config.seq_len,
+            max_batch_exp: config.max_batch_exp,
+        }
+    }
 }
 
 impl Config {
@@ -65,9 +381,21 @@ impl Config {
             SettingsField::MaxTokens => self.max_tokens.to_string(),
             SettingsField::Temperature => format!("{:.2}", self.temperature),
             SettingsField::DevicesRefreshInterval => self.devices_refresh_interval.to_string(),
+            SettingsField::TopologyRefreshInterval => self.topology_refresh_interval.to_string(),
+            SettingsField::ShardRefreshInterval => self.shard_refresh_interval.to_string(),
             SettingsField::KVBits => self.kv_bits.to_string(),
             SettingsField::MaxBatchExp => self.max_batch_exp.to_string(),
             SettingsField::SeqLen => self.seq_len.to_string(),
+            SettingsField::WarmUp => if self.warm_up { "on" } else { "off" }.to_string(),
+            SettingsField::ExportFormat => self.export_format.to_string(),
+            SettingsField::SystemPrompt => self.system_prompt.clone(),
+            SettingsField::TimestampFormat => self.timestamp_format.to_string(),
+            SettingsField::AutosaveInterval => self.autosave_interval.to_string(),
+            SettingsField::ContextBudget => self.context_budget.to_string(),
+            SettingsField::Notification => self.notification.to_string(),
+            SettingsField::UserColor => self.theme.user.clone(),
+            SettingsField::AssistantColor => self.theme.assistant.clone(),
+            SettingsField::ThinkingColor => self.theme.thinking.clone(),
         }
     }
 
@@ -88,6 +416,12 @@ impl Config {
             SettingsField::DevicesRefreshInterval => {
                 self.devices_refresh_interval = value.parse().map(|t: u64| t.clamp(1, 3600))?;
             }
+            SettingsField::TopologyRefreshInterval => {
+                self.topology_refresh_interval = value.parse().map(|t: u64| t.clamp(0, 3600))?;
+            }
+            SettingsField::ShardRefreshInterval => {
+                self.shard_refresh_interval = value.parse().map(|t: u64| t.clamp(0, 3600))?;
+            }
             SettingsField::KVBits => self.kv_bits = value.parse()?,
             SettingsField::MaxBatchExp => {
                 self.max_batch_exp = value.parse().map(|t: u8| t.clamp(1, 8))?
@@ -95,12 +429,32 @@ impl Config {
             SettingsField::SeqLen => {
                 self.seq_len = value.parse().map(|t: u32| t.clamp(0, 999_999))?
             }
+            SettingsField::WarmUp => self.warm_up = parse_toggle(value)?,
+            SettingsField::ExportFormat => self.export_format = value.parse()?,
+            SettingsField::SystemPrompt => self.system_prompt = value.trim().to_string(),
+            SettingsField::TimestampFormat => self.timestamp_format = value.parse()?,
+            SettingsField::AutosaveInterval => {
+                self.autosave_interval = value.parse().map(|t: u32| t.clamp(0, 100))?
+            }
+            SettingsField::ContextBudget => {
+                self.context_budget = value.parse().map(|t: u32| t.clamp(0, 999_999))?
+            }
+            SettingsField::Notification => self.notification = value.parse()?,
+            SettingsField::UserColor => self.theme.user = parse_color(value)?,
+            SettingsField::AssistantColor => self.theme.assistant = parse_color(value)?,
+            SettingsField::ThinkingColor => self.theme.thinking = parse_color(value)?,
         }
 
         Ok(())
     }
 }
 
+/// Number of the recently loaded models that are remembered.
+pub const MAX_RECENT_MODELS: usize = 5;
+
+/// Number of load durations that are kept per model & shards.
+pub const MAX_LOAD_DURATIONS: usize = 5;
+
 #[inline(always)]
 #[rustfmt::skip]
 fn default_max_tokens() -> u32  { 2000 }
@@ -112,10 +466,35 @@ fn default_temperature() -> f32 { 0.7 }
 fn default_devices_refresh_interval() -> u64 { 1 }
 #[inline(always)]
 #[rustfmt::skip]
+fn default_topology_refresh_interval() -> u64 { 5 }
+#[inline(always)]
+#[rustfmt::skip]
+fn default_shard_refresh_interval() -> u64 { 2 }
+#[inline(always)]
+#[rustfmt::skip]
 fn default_max_batch_exp() -> u8 { 2 }
 #[inline(always)]
 #[rustfmt::skip]
 fn default_seq_len() -> u32 { 4096 }
+#[inline(always)]
+#[rustfmt::skip]
+fn default_autosave_interval() -> u32 { 2 }
+
+fn default_token_cleanup() -> Vec<CleanupRule> {
+    [
+        "<|im_start|>",  // Qwen models (shouldn't appear but just in case)
+        "<|im_end|>",    // Qwen models
+        "<|endoftext|>", // GPT models
+        "</s>",          // Llama models
+        "<s>",           // Llama models
+        "[INST]",        // Instruction models
+        "[/INST]",       // Instruction models
+        "�",             // Unicode replacement character (malformed UTF-8)
+    ]
+    .into_iter()
+    .map(CleanupRule::literal)
+    .collect()
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -125,15 +504,107 @@ impl Default for Config {
             max_tokens: default_max_tokens(),
             temperature: default_temperature(),
             devices_refresh_interval: default_devices_refresh_interval(),
+            topology_refresh_interval: default_topology_refresh_interval(),
+            shard_refresh_interval: default_shard_refresh_interval(),
             kv_bits: KVBits::default(),
             max_batch_exp: default_max_batch_exp(),
             seq_len: default_seq_len(),
+            warm_up: false,
+            export_format: ExportFormat::default(),
+            system_prompt: String::new(),
+            timestamp_format: TimestampFormat::default(),
+            autosave_interval: default_autosave_interval(),
+            context_budget: 0,
+            notification: ChatNotification::default(),
+            token_cleanup: default_token_cleanup(),
+            theme: ChatTheme::default(),
+            device_aliases: HashMap::new(),
+            excluded_devices: BTreeSet::new(),
+            favorite_models: BTreeSet::new(),
+            recent_models: Vec::new(),
+            load_durations: HashMap::new(),
+            load_profiles: Vec::new(),
         }
     }
 }
 
 impl Config {
     pub const FILE_NAME: &'static str = "dnet.json";
+
+    /// Name to show for a device, i.e. its alias if it has one, otherwise its instance.
+    pub fn device_name<'a>(&'a self, instance: &'a str) -> &'a str {
+        self.device_aliases
+            .get(instance)
+            .map_or(instance, String::as_str)
+    }
+
+    /// Whether the device is left out of the topologies, see [`Config::excluded_devices`].
+    pub fn is_excluded(&self, instance: &str) -> bool {
+        self.excluded_devices.contains(instance)
+    }
+
+    /// Remember the model as the most recently loaded one, forgetting the oldest beyond the limit.
+    pub fn record_recent_model(&mut self, model: &str) {
+        self.recent_models.retain(|m| m != model);
+        self.recent_models.insert(0, model.to_string());
+        self.recent_models.truncate(MAX_RECENT_MODELS);
+    }
+
+    /// Rank of the model in the model selector, i.e. favorites first,
+    /// then the recent models from the latest, then the rest.
+    pub fn model_rank(&self, model: &str) -> (u8, usize) {
+        if self.favorite_models.contains(model) {
+            return (0, 0);
+        }
+        match self.recent_models.iter().position(|m| m == model) {
+            Some(i) => (1, i),
+            None => (2, 0),
+        }
+    }
+
+    /// Key of the load durations of a model on the given shards, regardless of their order.
+    pub fn load_key<'a>(model: &str, instances: impl IntoIterator<Item = &'a str>) -> String {
+        let mut instances = instances.into_iter().collect::<Vec<_>>();
+        instances.sort_unstable();
+        format!("{}@{}", model, instances.join(","))
+    }
+
+    /// Remember the duration of a load, forgetting the oldest beyond the limit.
+    pub fn record_load_duration(&mut self, key: String, duration: std::time::Duration) {
+        let durations = self.load_durations.entry(key).or_default();
+        durations.push(duration.as_secs());
+        if durations.len() > MAX_LOAD_DURATIONS {
+            durations.remove(0);
+        }
+    }
+
+    /// Estimated duration of a load, i.e. the average of the latest ones, if any.
+    pub fn estimated_load_duration(&self, key: &str) -> Option<std::time::Duration> {
+        let durations = self.load_durations.get(key).filter(|d| !d.is_empty())?;
+        let average = durations.iter().sum::<u64>() / durations.len() as u64;
+        Some(std::time::Duration::from_secs(average))
+    }
+
+    /// Save the profile, replacing the one with the same name if any.
+    pub fn save_profile(&mut self, profile: LoadProfile) {
+        match self
+            .load_profiles
+            .iter_mut()
+            .find(|p| p.name == profile.name)
+        {
+            Some(existing) => *existing = profile,
+            None => self.load_profiles.push(profile),
+        }
+    }
+
+    /// Apply the settings of the profile, returning its model to load.
+    pub fn apply_profile(&mut self, profile: &LoadProfile) -> String {
+        self.kv_bits = profile.kv_bits;
+        self.seq_len = profile.seq_len;
+        self.max_batch_exp = profile.max_batch_exp;
+        profile.model.clone()
+    }
+
     /// Load config from either current directory or `~/.dria/dnet/` directory
     pub fn load() -> color_eyre::Result<Self> {
         // try current directory first
@@ -173,14 +644,19 @@ impl Config {
     }
 
     /// Get the path to `$HOME/.dria/dnet/dnet.json`
+    fn dria_config_path() -> PathBuf {
+        Self::dria_dir().join(Self::FILE_NAME)
+    }
+
+    /// Get the path to `$HOME/.dria/dnet/`, where the config and other app data lives.
     ///
     /// FIXME: this is not cross-platform
-    fn dria_config_path() -> PathBuf {
+    pub fn dria_dir() -> PathBuf {
         let mut path = match std::env::var("HOME") {
             Ok(home) => PathBuf::from(home),
             Err(_) => PathBuf::from("."),
         };
-        path.extend([".dria", "dnet", Self::FILE_NAME]);
+        path.extend([".dria", "dnet"]);
         path
     }
 
@@ -204,3 +680,57 @@ impl Config {
         format!("http://{}:{}", self.api_host, self.api_port)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_rank() {
+        let mut config = Config::default();
+        for model in ["a", "b", "c", "d", "e", "f", "b"] {
+            config.record_recent_model(model);
+        }
+        assert_eq!(config.recent_models, vec!["b", "f", "e", "d", "c"]);
+
+        config.favorite_models.insert("e".to_string());
+        let mut models = vec!["a", "b", "c", "e", "z"];
+        models.sort_by_key(|model| config.model_rank(model));
+        assert_eq!(models, vec!["e", "b", "c", "a", "z"]);
+    }
+
+    #[test]
+    fn test_load_profiles() {
+        let mut config = Config::default();
+        config.save_profile(LoadProfile::new("fast", "model-a", &config));
+        config.seq_len = 2048;
+        config.kv_bits = KVBits::Bits4;
+        config.save_profile(LoadProfile::new("long", "model-b", &config));
+        config.save_profile(LoadProfile::new("fast", "model-c", &config));
+        assert_eq!(config.load_profiles.len(), 2);
+        assert_eq!(config.load_profiles[0].model, "model-c");
+
+        let default = Config::default();
+        let profile = LoadProfile::new("default", "model-a", &default);
+        assert_eq!(config.apply_profile(&profile), "model-a");
+        assert_eq!(config.seq_len, default.seq_len);
+        assert_eq!(config.kv_bits, default.kv_bits);
+    }
+
+    #[test]
+    fn test_load_durations() {
+        let mut config = Config::default();
+        let key = Config::load_key("model", ["shard-b", "shard-a"]);
+        assert_eq!(key, Config::load_key("model", ["shard-a", "shard-b"]));
+        assert_eq!(config.estimated_load_duration(&key), None);
+
+        for secs in [100, 10, 20, 30, 40, 50] {
+            config.record_load_duration(key.clone(), std::time::Duration::from_secs(secs));
+        }
+        assert_eq!(config.load_durations[&key], vec![10, 20, 30, 40, 50]);
+        assert_eq!(
+            config.estimated_load_duration(&key),
+            Some(std::time::Duration::from_secs(30))
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crossterm::event::{
+    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+};
 use dnet_tui::App;
 
 #[tokio::main]
@@ -5,8 +8,10 @@ async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
     let terminal = ratatui::init();
+    crossterm::execute!(std::io::stdout(), EnableMouseCapture, EnableFocusChange)?;
     let app = App::new()?;
     let result = app.run(terminal).await;
+    crossterm::execute!(std::io::stdout(), DisableMouseCapture, DisableFocusChange)?;
     ratatui::restore();
     result
 }
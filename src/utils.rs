@@ -1,3 +1,5 @@
+use crate::config::KVBits;
+use ratatui::layout::{Constraint, Layout, Rect};
 use std::time::Duration;
 
 /// Offset in milliseconds for sliding effect, the higher the slower.
@@ -21,6 +23,41 @@ pub fn get_sliding_text(duration: Duration, full_text: &str, window_size: usize)
     }
 }
 
+/// Format a number of bytes in a human-readable form with binary units, e.g. `1.5 GiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Helper function to create a centered rect for popup
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(r);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
+
 /// A wrapper around model `config.json` on HuggingFace.
 ///
 /// It is not a strict type because the config may change from model to model.
@@ -43,6 +80,98 @@ impl ModelConfig {
         }
         None
     }
+
+    /// Returns the field as a number, if it exists.
+    fn get_u64(&self, key: &str) -> Option<u64> {
+        self.0.get(key).and_then(|value| value.as_u64())
+    }
+
+    /// Returns the number of key-value heads, which is the number of attention
+    /// heads for models without grouped-query attention.
+    pub fn num_kv_heads(&self) -> Option<u64> {
+        self.get_u64("num_key_value_heads")
+            .or_else(|| self.get_u64("num_attention_heads"))
+    }
+
+    /// Returns the dimension of the attention heads, derived from the hidden size if not given.
+    pub fn head_dim(&self) -> Option<u64> {
+        self.get_u64("head_dim").or_else(|| {
+            let num_heads = self.get_u64("num_attention_heads").filter(|&n| n > 0)?;
+            Some(self.get_u64("hidden_size")? / num_heads)
+        })
+    }
+
+    /// Returns the size of the KV cache of a single layer in bytes, for the given
+    /// sequence length and KV quantization, i.e. keys and values of each head.
+    pub fn kv_cache_bytes_per_layer(&self, seq_len: u32, kv_bits: KVBits) -> Option<u64> {
+        let elements = 2 * self.num_kv_heads()? * self.head_dim()? * seq_len as u64;
+        Some(elements * kv_bits.bits() / 8)
+    }
+
+    /// Returns the bytes per weight, from the quantization bits (e.g. of MLX models)
+    /// or the dtype, assuming 16-bit weights otherwise.
+    pub fn bytes_per_weight(&self) -> f64 {
+        let bits = self.0.get("quantization").and_then(|q| q.get("bits"));
+        if let Some(bits) = bits.and_then(|bits| bits.as_u64()) {
+            return bits as f64 / 8.0;
+        }
+        match self.0.get("torch_dtype").and_then(|dtype| dtype.as_str()) {
+            Some("float32") => 4.0,
+            _ => 2.0,
+        }
+    }
+
+    /// Returns the quantization of the weights, e.g. `4-bit` for MLX models, or their dtype.
+    pub fn quantization(&self) -> Option<String> {
+        let bits = self.0.get("quantization").and_then(|q| q.get("bits"));
+        if let Some(bits) = bits.and_then(|bits| bits.as_u64()) {
+            return Some(format!("{}-bit", bits));
+        }
+        let dtype = self.0.get("torch_dtype").and_then(|dtype| dtype.as_str());
+        dtype.map(str::to_string)
+    }
+
+    /// Returns the number of parameters, estimated from the layers and the
+    /// embeddings (which are counted twice unless tied to the output).
+    pub fn num_params(&self) -> Option<u64> {
+        let embeddings = self.get_u64("vocab_size")? * self.get_u64("hidden_size")?;
+        let is_tied = self
+            .0
+            .get("tie_word_embeddings")
+            .and_then(|tied| tied.as_bool())
+            .unwrap_or(false);
+        let embeddings = if is_tied { embeddings } else { 2 * embeddings };
+        Some(self.num_layers()? * self.layer_params()? + embeddings)
+    }
+
+    /// Returns the number of weights of a single layer, estimated from the
+    /// attention projections and the gated MLP (of all experts for MoE models).
+    fn layer_params(&self) -> Option<u64> {
+        let hidden_size = self.get_u64("hidden_size")?;
+        let num_heads = self.get_u64("num_attention_heads")?;
+        // query & output projections per head, key & value projections per KV head
+        let attention = hidden_size * self.head_dim()? * 2 * (num_heads + self.num_kv_heads()?);
+
+        let num_experts = self
+            .get_u64("num_local_experts")
+            .or_else(|| self.get_u64("num_experts"))
+            .unwrap_or(1);
+        let intermediate_size = match num_experts {
+            1 => self.get_u64("intermediate_size")?,
+            _ => self
+                .get_u64("moe_intermediate_size")
+                .or_else(|| self.get_u64("intermediate_size"))?,
+        };
+        let mlp = 3 * hidden_size * intermediate_size * num_experts;
+
+        Some(attention + mlp)
+    }
+
+    /// Returns the size of the weights of a single layer in bytes, see [`Self::layer_params`].
+    pub fn layer_weight_bytes(&self) -> Option<u64> {
+        Some((self.layer_params()? as f64 * self.bytes_per_weight()) as u64)
+    }
+
     /// Fetches the model config from HuggingFace (via `raw/main/config.json`).
     pub async fn get_model_config(repo_id: &str) -> color_eyre::Result<Self> {
         let url = format!("https://huggingface.co/{repo_id}/raw/main/config.json");
@@ -81,6 +210,86 @@ mod tests {
         assert_eq!(get_sliding_text(x * 3, text, 5), "abc");
     }
 
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_kv_cache_bytes_per_layer() {
+        // grouped-query attention, e.g. Llama 3 8B
+        let config = ModelConfig(serde_json::json!({
+            "hidden_size": 4096,
+            "num_attention_heads": 32,
+            "num_key_value_heads": 8
+        }));
+        assert_eq!(config.head_dim(), Some(128));
+        assert_eq!(
+            config.kv_cache_bytes_per_layer(4096, KVBits::FP16),
+            Some(16 * 1024 * 1024)
+        );
+        assert_eq!(
+            config.kv_cache_bytes_per_layer(4096, KVBits::Bits4),
+            Some(4 * 1024 * 1024)
+        );
+
+        assert_eq!(ModelConfig(serde_json::json!({})).head_dim(), None);
+    }
+
+    #[test]
+    fn test_layer_weight_bytes() {
+        // 1024 * 128 * 2 * 16 attention + 3 * 1024 * 4096 MLP = 16Mi weights
+        let config = serde_json::json!({
+            "hidden_size": 1024,
+            "num_attention_heads": 8,
+            "num_key_value_heads": 8,
+            "intermediate_size": 4096,
+            "torch_dtype": "bfloat16"
+        });
+        assert_eq!(
+            ModelConfig(config.clone()).layer_weight_bytes(),
+            Some(32 * 1024 * 1024)
+        );
+
+        let mut quantized = config;
+        quantized["quantization"] = serde_json::json!({"bits": 4, "group_size": 64});
+        assert_eq!(
+            ModelConfig(quantized).layer_weight_bytes(),
+            Some(8 * 1024 * 1024)
+        );
+    }
+
+    #[test]
+    fn test_num_params() {
+        let mut config = serde_json::json!({
+            "num_hidden_layers": 2,
+            "hidden_size": 1024,
+            "num_attention_heads": 8,
+            "num_key_value_heads": 8,
+            "intermediate_size": 4096,
+            "vocab_size": 1000,
+            "torch_dtype": "bfloat16"
+        });
+        // 16Mi weights per layer, and the input & output embeddings
+        let layers = 2 * 16 * 1024 * 1024;
+        assert_eq!(
+            ModelConfig(config.clone()).num_params(),
+            Some(layers + 2 * 1000 * 1024)
+        );
+        assert_eq!(
+            ModelConfig(config.clone()).quantization().as_deref(),
+            Some("bfloat16")
+        );
+
+        config["tie_word_embeddings"] = serde_json::json!(true);
+        config["quantization"] = serde_json::json!({"bits": 4, "group_size": 64});
+        let config = ModelConfig(config);
+        assert_eq!(config.num_params(), Some(layers + 1000 * 1024));
+        assert_eq!(config.quantization().as_deref(), Some("4-bit"));
+    }
+
     #[tokio::test]
     async fn test_model_config() {
         let config = ModelConfig::get_model_config("Qwen/Qwen3-32B-MLX-bf16")
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+/// Largest file that can be attached to a message, larger files would
+/// hardly fit in the context window anyways.
+pub const MAX_ATTACHMENT_SIZE: u64 = 64 * 1024;
+
+/// A local file attached to the next outgoing message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    /// File name, without the directory.
+    pub name: String,
+    /// Contents of the file.
+    pub content: String,
+}
+
+impl Attachment {
+    /// Read a (UTF-8) file to be attached, rejecting directories and files
+    /// larger than [`MAX_ATTACHMENT_SIZE`].
+    ///
+    /// A leading `~/` in the path is expanded to the home directory.
+    pub fn read(path: &str) -> Result<Self, String> {
+        let path = expand_home(path.trim());
+
+        let metadata = std::fs::metadata(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        if !metadata.is_file() {
+            return Err(format!("{} is not a file", path.display()));
+        }
+        if metadata.len() > MAX_ATTACHMENT_SIZE {
+            return Err(format!(
+                "{} is too large ({} KB, max {} KB)",
+                path.display(),
+                metadata.len() / 1024,
+                MAX_ATTACHMENT_SIZE / 1024
+            ));
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+
+        Ok(Self { name, content })
+    }
+
+    /// Size of the attachment in a human-readable form, e.g. `1.5 KB`.
+    pub fn size(&self) -> String {
+        let len = self.content.len();
+        if len < 1024 {
+            format!("{} B", len)
+        } else {
+            format!("{:.1} KB", len as f64 / 1024.0)
+        }
+    }
+
+    /// Append the attachment to the message as a fenced block, labeled with the file name.
+    pub fn append_to(&self, message: &str) -> String {
+        let block = format!("`{}`:\n```\n{}\n```", self.name, self.content.trim_end());
+        if message.is_empty() {
+            block
+        } else {
+            format!("{}\n\n{}", message, block)
+        }
+    }
+}
+
+/// Expand a leading `~/` to the home directory, if it is known.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_attachment() {
+        let attachment = Attachment {
+            name: "config.toml".to_string(),
+            content: "port = 8080\n".to_string(),
+        };
+        assert_eq!(attachment.size(), "12 B");
+        assert_eq!(
+            attachment.append_to("What is the port?"),
+            "What is the port?\n\n`config.toml`:\n```\nport = 8080\n```"
+        );
+        assert_eq!(
+            attachment.append_to(""),
+            "`config.toml`:\n```\nport = 8080\n```"
+        );
+    }
+}
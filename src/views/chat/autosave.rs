@@ -0,0 +1,82 @@
+use super::{ChatMessage, ChatSession};
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+/// A snapshot of a chat session, written periodically so that the
+/// conversation can be resumed after a restart (or a crash).
+///
+/// Each session is saved to its own file, keyed by the session id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSnapshot {
+    /// Id of the snapshotted session, see [`ChatSession::id`].
+    pub id: String,
+    pub name: String,
+    pub system_prompt: String,
+    pub messages: VecDeque<ChatMessage>,
+    pub saved_at: chrono::DateTime<chrono::Local>,
+}
+
+impl ChatSnapshot {
+    pub const DIR_NAME: &'static str = "chat-autosave";
+
+    pub fn new(session: &ChatSession) -> Self {
+        Self {
+            id: session.id.clone(),
+            name: session.name.clone(),
+            system_prompt: session.system_prompt.clone(),
+            messages: session.messages.clone(),
+            saved_at: chrono::Local::now(),
+        }
+    }
+
+    /// Get the path to `$HOME/.dria/dnet/chat-autosave`
+    pub fn dir() -> PathBuf {
+        Config::dria_dir().join(Self::DIR_NAME)
+    }
+
+    /// Get the path to `$HOME/.dria/dnet/chat-autosave/<id>.json`
+    pub fn path(id: &str) -> PathBuf {
+        Self::dir().join(format!("{}.json", id))
+    }
+
+    /// Write the snapshot, overwriting the previous one of the same session.
+    pub fn save(&self) -> color_eyre::Result<()> {
+        fs::create_dir_all(Self::dir())?;
+        fs::write(Self::path(&self.id), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Load the most recently saved snapshot, if there is a readable one.
+    pub fn load_latest() -> Option<Self> {
+        fs::read_dir(Self::dir())
+            .ok()?
+            .filter_map(|entry| fs::read_to_string(entry.ok()?.path()).ok())
+            .filter_map(|content| serde_json::from_str::<Self>(&content).ok())
+            .max_by_key(|snapshot| snapshot.saved_at)
+    }
+
+    /// Delete the file of the snapshot, so that it is not offered again.
+    pub fn discard(&self) {
+        fs::remove_file(Self::path(&self.id)).ok();
+    }
+
+    /// Number of messages that were actually exchanged, i.e. excluding system notices.
+    pub fn num_messages(&self) -> usize {
+        self.messages.iter().filter(|m| m.role != "system").count()
+    }
+
+    /// Restore the snapshot into the given session.
+    pub fn restore(self, session: &mut ChatSession) {
+        // keep saving to the same file
+        session.id = self.id;
+        session.name = self.name;
+        session.system_prompt = self.system_prompt;
+        session.messages = self.messages;
+        session.autosaved_len = session.messages.len();
+        session.scroll_cur = 0;
+        session.scroll_locked = true;
+    }
+}
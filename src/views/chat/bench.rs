@@ -0,0 +1,246 @@
+use super::ChatMessage;
+use crate::common::{
+    ApiMessage, ChatRequest, CompletionRequest, GenerationStats, StreamRequest, bench_run,
+};
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// Largest number of runs of a single benchmark.
+pub const MAX_BENCH_RUNS: usize = 100;
+
+/// A running prompt benchmark, started with the `bench` chat command.
+#[derive(Debug)]
+pub struct ChatBench {
+    /// Name of the session that started the benchmark, where the results are posted.
+    pub session_name: String,
+    /// Total number of runs.
+    pub num_runs: usize,
+    /// Progress & result receiver.
+    pub rx: mpsc::UnboundedReceiver<BenchEvent>,
+    /// Handle to the benchmark task, used to cancel it.
+    pub task: AbortHandle,
+}
+
+#[derive(Debug)]
+pub enum BenchEvent {
+    /// A run has finished, with the number of finished runs so far.
+    Progress(usize),
+    /// All runs have finished, with the summary table.
+    Done(String),
+}
+
+impl crate::App {
+    /// Start benchmarking the prompt in the input (or the last user message) of the
+    /// active session, with its system prompt and sampling parameters.
+    pub fn start_benchmark(&mut self, num_runs: usize, concurrency: usize) {
+        if self.state.chat.bench.is_some() {
+            self.state
+                .chat
+                .show_toast("A benchmark is already running.", true);
+            return;
+        }
+        let Some(model) = self.chat_model(self.state.chat.active_session) else {
+            self.state
+                .chat
+                .show_toast("No model configured in topology.", true);
+            return;
+        };
+
+        let session = self.state.chat.session();
+        let prompt = match self.state.chat.input.value().trim() {
+            "" => session
+                .messages
+                .iter()
+                .rev()
+                .find(|m| m.role == "user")
+                .map(|m| m.content.clone()),
+            input => Some(input.to_string()),
+        };
+        let Some(prompt) = prompt else {
+            self.state
+                .chat
+                .show_toast("Type a prompt to benchmark first.", true);
+            return;
+        };
+
+        let (max_tokens, temperature) = self.chat_sampling(self.state.chat.active_session);
+        let request = if session.raw_mode {
+            StreamRequest::Completion(CompletionRequest {
+                model,
+                prompt,
+                max_tokens: Some(max_tokens),
+                temperature: Some(temperature),
+                stream: true,
+            })
+        } else {
+            let mut messages = Vec::new();
+            if !session.system_prompt.is_empty() {
+                messages.push(ApiMessage::system(&session.system_prompt));
+            }
+            messages.push((&ChatMessage::new_user(&prompt)).into());
+            StreamRequest::Chat(ChatRequest {
+                model,
+                messages,
+                max_tokens: Some(max_tokens),
+                temperature: Some(temperature),
+                stream: true,
+            })
+        };
+
+        let url = format!("{}{}", self.config.api_url(), request.path());
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run_benchmark(url, request, num_runs, concurrency, tx));
+        self.state.chat.bench = Some(ChatBench {
+            session_name: session.name.clone(),
+            num_runs,
+            rx,
+            task: task.abort_handle(),
+        });
+        self.state
+            .chat
+            .show_toast(format!("Benchmark: 0/{} runs", num_runs), false);
+    }
+
+    /// Process the progress of the running benchmark, if any, posting the
+    /// results to the session that started it once all runs are finished.
+    pub(crate) fn tick_chat_bench(&mut self) {
+        let chat = &mut self.state.chat;
+        let Some(bench) = &mut chat.bench else {
+            return;
+        };
+
+        let mut progress = None;
+        let mut table = None;
+        while let Ok(event) = bench.rx.try_recv() {
+            match event {
+                BenchEvent::Progress(finished) => progress = Some(finished),
+                BenchEvent::Done(summary) => table = Some(summary),
+            }
+        }
+
+        if let Some(table) = table {
+            let Some(bench) = chat.bench.take() else {
+                return;
+            };
+            // post to the active session if the original one was closed meanwhile
+            let idx = chat
+                .sessions
+                .iter()
+                .position(|s| s.name == bench.session_name)
+                .unwrap_or(chat.active_session);
+            chat.sessions[idx]
+                .messages
+                .push_back(ChatMessage::new_system(&table));
+            chat.show_toast(
+                format!("Benchmark finished in {}.", bench.session_name),
+                false,
+            );
+        } else if let Some(finished) = progress {
+            let num_runs = bench.num_runs;
+            chat.show_toast(format!("Benchmark: {}/{} runs", finished, num_runs), false);
+        }
+    }
+}
+
+/// Run the request `num_runs` times with at most `concurrency` runs at a time,
+/// sending the progress and finally the summary table over `tx`.
+pub async fn run_benchmark(
+    url: String,
+    request: StreamRequest,
+    num_runs: usize,
+    concurrency: usize,
+    tx: mpsc::UnboundedSender<BenchEvent>,
+) {
+    let client = reqwest::Client::new();
+    let mut finished = 0;
+    let mut runs = futures::stream::iter(0..num_runs)
+        .map(|_| bench_run(&client, &url, &request))
+        .buffered(concurrency.max(1));
+
+    let mut results = Vec::with_capacity(num_runs);
+    while let Some(result) = runs.next().await {
+        finished += 1;
+        tx.send(BenchEvent::Progress(finished)).ok();
+        results.push(result);
+    }
+
+    tx.send(BenchEvent::Done(summary_table(&results, concurrency)))
+        .ok();
+}
+
+/// Render the results of the runs as a table, with the averages of the successful runs.
+pub fn summary_table(results: &[Result<GenerationStats, String>], concurrency: usize) -> String {
+    let mut table = format!(
+        "Benchmark: {} runs (concurrency {})\n\n{:>4} | {:>8} | {:>6} | {:>7}\n",
+        results.len(),
+        concurrency,
+        "Run",
+        "TTFT (s)",
+        "Tokens",
+        "Tok/s"
+    );
+
+    let (mut sum_ttft, mut sum_tokens, mut sum_tps, mut num_ok) = (0.0, 0, 0.0, 0);
+    for (i, result) in results.iter().enumerate() {
+        match result {
+            Ok(stats) => {
+                let ttft = stats.ttft().map(|d| d.as_secs_f64()).unwrap_or_default();
+                let tps = stats.tokens_per_sec().unwrap_or_default();
+                table.push_str(&format!(
+                    "{:>4} | {:>8.2} | {:>6} | {:>7.1}\n",
+                    i + 1,
+                    ttft,
+                    stats.num_tokens,
+                    tps
+                ));
+                sum_ttft += ttft;
+                sum_tokens += stats.num_tokens;
+                sum_tps += tps;
+                num_ok += 1;
+            }
+            Err(err) => table.push_str(&format!("{:>4} | failed: {}\n", i + 1, err)),
+        }
+    }
+
+    if num_ok > 0 {
+        let n = num_ok as f64;
+        table.push_str(&format!(
+            "{:>4} | {:>8.2} | {:>6} | {:>7.1}",
+            "Avg",
+            sum_ttft / n,
+            sum_tokens / num_ok,
+            sum_tps / n
+        ));
+    } else {
+        table.push_str("All runs failed.");
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_summary_table() {
+        let start = Instant::now();
+        let mut stats = GenerationStats::new(start);
+        stats.first_token_at = Some(start + Duration::from_millis(500));
+        stats.finished_at = Some(start + Duration::from_millis(2500));
+        stats.num_tokens = 20;
+
+        let table = summary_table(&[Ok(stats), Err("HTTP 500".to_string())], 1);
+        let expected = [
+            "Benchmark: 2 runs (concurrency 1)",
+            "",
+            " Run | TTFT (s) | Tokens |   Tok/s",
+            "   1 |     0.50 |     20 |    10.0",
+            "   2 | failed: HTTP 500",
+            " Avg |     0.50 |     20 |    10.0",
+        ]
+        .join("\n");
+        assert_eq!(table, expected);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::config::CleanupRule;
+use regex::Regex;
+
+/// A compiled cleanup rule, see [`CleanupRule`].
+#[derive(Debug, Clone)]
+enum Cleanup {
+    Literal(String),
+    Regex(Regex),
+}
+
+/// Removes model-specific special tokens from the streamed content,
+/// using the cleanup rules that apply to the model being chatted with.
+///
+/// Rules are applied to each streamed delta separately, so a pattern
+/// can not match across two deltas.
+#[derive(Debug, Clone, Default)]
+pub struct TokenCleaner {
+    cleanups: Vec<Cleanup>,
+}
+
+impl TokenCleaner {
+    /// Compile the rules that apply to the given model, failing on an invalid regex.
+    pub fn new(rules: &[CleanupRule], model: &str) -> Result<Self, String> {
+        let cleanups = rules
+            .iter()
+            .filter(|rule| rule.applies_to(model))
+            .map(|rule| {
+                if rule.regex {
+                    Regex::new(&rule.pattern)
+                        .map(Cleanup::Regex)
+                        .map_err(|e| format!("Invalid cleanup pattern '{}': {}", rule.pattern, e))
+                } else {
+                    Ok(Cleanup::Literal(rule.pattern.clone()))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { cleanups })
+    }
+
+    /// Remove the matching tokens from the content.
+    pub fn clean(&self, content: &str) -> String {
+        let mut cleaned = content.to_string();
+        for cleanup in &self.cleanups {
+            cleaned = match cleanup {
+                Cleanup::Literal(token) => cleaned.replace(token, ""),
+                Cleanup::Regex(regex) => regex.replace_all(&cleaned, "").to_string(),
+            };
+        }
+        cleaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_cleaner() {
+        let rules = vec![
+            CleanupRule {
+                model: None,
+                pattern: "</s>".to_string(),
+                regex: false,
+            },
+            CleanupRule {
+                model: Some("qwen".to_string()),
+                pattern: r"<\|[a-z_]+\|>".to_string(),
+                regex: true,
+            },
+        ];
+
+        let cleaner = TokenCleaner::new(&rules, "Qwen/Qwen3-4B-MLX-4bit").unwrap();
+        assert_eq!(cleaner.clean("Hi<|im_end|></s>"), "Hi");
+
+        // the regex rule does not apply to other models
+        let cleaner = TokenCleaner::new(&rules, "meta-llama/Llama-3.2-1B").unwrap();
+        assert_eq!(cleaner.clean("Hi<|im_end|></s>"), "Hi<|im_end|>");
+
+        let invalid = vec![CleanupRule {
+            model: None,
+            pattern: "[".to_string(),
+            regex: true,
+        }];
+        assert!(TokenCleaner::new(&invalid, "any").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use super::ChatMessage;
+use super::stats::estimate_tokens;
+use std::collections::VecDeque;
+
+/// Per-message overhead of the chat template (role markers etc.), in tokens.
+const MESSAGE_OVERHEAD: usize = 4;
+
+/// Estimated number of tokens that the message takes within a request.
+pub fn message_tokens(msg: &ChatMessage) -> usize {
+    estimate_tokens(&msg.content) + MESSAGE_OVERHEAD
+}
+
+/// Index of the first message to send so that the history fits within `budget`
+/// tokens, dropping the oldest turns otherwise. Returns 0 if nothing is dropped.
+///
+/// Whole turns are dropped, i.e. a kept history starts with a user message,
+/// and the last message is always kept even if it does not fit by itself.
+/// System notices are not sent, so they are not counted.
+pub fn history_start(messages: &VecDeque<ChatMessage>, budget: usize) -> usize {
+    let mut tokens = 0;
+    let mut start = None;
+    let mut is_truncated = false;
+    for (i, msg) in messages.iter().enumerate().rev() {
+        if msg.role == "system" {
+            continue;
+        }
+
+        tokens += message_tokens(msg);
+        if tokens > budget && start.is_some() {
+            is_truncated = true;
+            break;
+        }
+        start = Some(i);
+    }
+
+    let Some(mut start) = start.filter(|_| is_truncated) else {
+        return 0;
+    };
+    // do not start in the middle of a turn
+    while start + 1 < messages.len() && messages[start].role != "user" {
+        start += 1;
+    }
+    start
+}
+
+/// Number of messages that are left out by [`history_start`], excluding system notices.
+pub fn num_truncated(messages: &VecDeque<ChatMessage>, start: usize) -> usize {
+    messages
+        .iter()
+        .take(start)
+        .filter(|m| m.role != "system")
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_start() {
+        // each message is 1 + 4 tokens
+        let messages = VecDeque::from([
+            ChatMessage::new_system("Welcome!"),
+            ChatMessage::new_user("abc"),
+            ChatMessage::new_assistant("abc"),
+            ChatMessage::new_user("abc"),
+            ChatMessage::new_assistant("abc"),
+            ChatMessage::new_user("abc"),
+        ]);
+
+        // everything fits
+        assert_eq!(history_start(&messages, 25), 0);
+
+        // the last 4 messages fit, but that would start with an assistant message
+        let start = history_start(&messages, 20);
+        assert_eq!(start, 3);
+        assert_eq!(num_truncated(&messages, start), 2);
+
+        // the last message is kept regardless
+        assert_eq!(history_start(&messages, 1), 5);
+    }
+}
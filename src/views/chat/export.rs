@@ -0,0 +1,77 @@
+use super::ChatMessage;
+use crate::config::ExportFormat;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Render the conversation as a Markdown document.
+pub fn to_markdown(title: &str, messages: &VecDeque<ChatMessage>) -> String {
+    let mut out = format!("# {}\n", title);
+    for msg in messages {
+        out.push_str(&format!(
+            "\n### {} ({})\n\n{}\n",
+            msg.role.to_uppercase(),
+            msg.timestamp.format("%Y-%m-%d %H:%M"),
+            msg.content.trim()
+        ));
+        for call in &msg.tool_calls {
+            out.push_str(&format!(
+                "\nTool call `{}`:\n\n```json\n{}\n```\n",
+                call.name, call.arguments
+            ));
+        }
+    }
+    out
+}
+
+/// Write the conversation to a timestamped file in the current directory,
+/// returning the path of the written file.
+pub fn export_conversation(
+    title: &str,
+    messages: &VecDeque<ChatMessage>,
+    format: ExportFormat,
+) -> color_eyre::Result<PathBuf> {
+    let content = match format {
+        ExportFormat::Markdown => to_markdown(title, messages),
+        ExportFormat::Json => serde_json::to_string_pretty(messages)?,
+    };
+
+    let path = PathBuf::from(format!(
+        "dnet-chat-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ));
+    std::fs::write(&path, content)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn test_to_markdown() {
+        let messages = VecDeque::from([
+            ChatMessage {
+                role: "user".to_string(),
+                content: "Hello?".to_string(),
+                timestamp: Local.with_ymd_and_hms(2025, 1, 31, 10, 0, 0).unwrap(),
+                usage: None,
+                tool_calls: Vec::new(),
+            },
+            ChatMessage {
+                role: "assistant".to_string(),
+                content: "Hi there!\n".to_string(),
+                timestamp: Local.with_ymd_and_hms(2025, 1, 31, 10, 1, 0).unwrap(),
+                usage: None,
+                tool_calls: Vec::new(),
+            },
+        ]);
+
+        assert_eq!(
+            to_markdown("Chat 1", &messages),
+            "# Chat 1\n\n### USER (2025-01-31 10:00)\n\nHello?\n\n### ASSISTANT (2025-01-31 10:01)\n\nHi there!\n"
+        );
+    }
+}
@@ -5,46 +5,106 @@ use utils::*;
 mod styles;
 use styles::*;
 
+mod session;
+pub use session::ChatSession;
+
+mod export;
+
+mod context;
+
+mod cleanup;
+use cleanup::TokenCleaner;
+
+mod attach;
+pub use attach::Attachment;
+
+mod autosave;
+pub use autosave::ChatSnapshot;
+
+mod bench;
+pub use bench::ChatBench;
+
+mod popup;
+
+mod search;
+pub use search::ChatSearch;
+
+mod stats;
+use stats::estimate_tokens;
+
 use crate::AppView;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::common::{
+    ApiMessage, ChatRequest, CompletionRequest, SseParser, StreamChunk, StreamRequest,
+};
+use crate::config::CleanupRule;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    widgets::{Block, Borders, LineGauge, Paragraph, Scrollbar, ScrollbarOrientation, Wrap},
 };
-use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
 use tui_input::backend::crossterm::EventHandler;
 
 #[derive(Debug)]
 pub struct ChatState {
-    pub messages: VecDeque<ChatMessage>,
-    /// Whether a response is currently being generated (streamed).
-    pub is_generating: bool,
-    pub current_response: String,
-    pub scroll_cur: u16,
-    /// Maximum scroll position, be careful about this as it may crash the app
-    /// if set incorrectly.
-    pub scroll_max: u16,
-    /// Whether the scroll is locked, i.e. auto-scrolls to the bottom
-    /// as new tokens are arriving. If the user scrolls manually while
-    /// generating, this is set to false.
-    pub scroll_locked: bool,
-    // pub model: String,
-    /// Chat message receiver for streaming responses
-    pub stream_rx: Option<mpsc::UnboundedReceiver<String>>,
+    /// Open chat sessions, there is always at least one.
+    pub sessions: Vec<ChatSession>,
+    /// Index of the active session within `sessions`.
+    pub active_session: usize,
     /// Chat input area.
     pub input: tui_input::Input,
-    /// Scrollbar for chat messages.
-    pub scroll_bar: ScrollbarState,
-    /// Pending chat message to send
-    pub pending_chat_message: Option<String>,
     /// Whether to show thinking content (default: true)
     pub show_thinking: bool,
+    /// Short-lived status message shown in the footer.
+    pub toast: Option<ChatToast>,
+    /// Open popup, if any. Popups take over the keyboard input.
+    pub popup: Option<ChatPopup>,
+    /// Input area used by the popups.
+    pub popup_input: tui_input::Input,
+    /// In-chat search, if open.
+    pub search: Option<ChatSearch>,
+    /// File attached to the next outgoing message.
+    pub attachment: Option<Attachment>,
+    /// Autosaved chat offered to be resumed, see [`ChatPopup::Resume`].
+    pub resumable: Option<ChatSnapshot>,
+    /// Whether resuming the autosaved chat was offered already, it is offered once per run.
+    pub resume_offered: bool,
+    /// Running prompt benchmark, if any.
+    pub bench: Option<ChatBench>,
+}
+
+/// Popups that can be opened on top of the chat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatPopup {
+    /// Editing the system prompt of the active session.
+    SystemPrompt,
+    /// Overriding the max tokens of the active session.
+    MaxTokens,
+    /// Overriding the temperature of the active session.
+    Temperature,
+    /// Picking a file to attach to the next message.
+    AttachFile,
+    /// Offering to resume the autosaved chat of a previous run.
+    Resume,
+}
+
+/// A short-lived status message, shown in place of the chat footer.
+#[derive(Debug, Clone)]
+pub struct ChatToast {
+    pub message: String,
+    pub is_error: bool,
+    pub shown_at: Instant,
 }
 
+/// How long a [`ChatToast`] stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ChatView {
     Active,
@@ -53,26 +113,87 @@ pub enum ChatView {
 
 impl Default for ChatState {
     fn default() -> Self {
-        let mut state = ChatState {
-            messages: VecDeque::new(),
-            is_generating: false,
-            current_response: String::new(),
-            scroll_cur: 0,
-            scroll_max: 0,
-            scroll_locked: false,
-            scroll_bar: ScrollbarState::default(),
-            stream_rx: None,
+        ChatState {
+            sessions: vec![ChatSession::new("Chat 1", "")],
+            active_session: 0,
             input: tui_input::Input::default(),
-            pending_chat_message: None,
             show_thinking: true, // Show thinking by default
-        };
+            toast: None,
+            popup: None,
+            popup_input: tui_input::Input::default(),
+            search: None,
+            attachment: None,
+            resumable: None,
+            resume_offered: false,
+            bench: None,
+        }
+    }
+}
+
+impl ChatState {
+    /// The active chat session.
+    pub fn session(&self) -> &ChatSession {
+        &self.sessions[self.active_session]
+    }
 
-        // add welcome message
-        state.messages.push_back(ChatMessage::new_system(
-            "Welcome to dnet chat! Type your message and press Enter to send.",
+    /// The active chat session, mutable.
+    pub fn session_mut(&mut self) -> &mut ChatSession {
+        &mut self.sessions[self.active_session]
+    }
+
+    /// Open a new session with the given system prompt and switch to it.
+    pub fn new_session(&mut self, system_prompt: &str) {
+        // name after the largest existing number so that names stay unique after closing
+        let next_num = self
+            .sessions
+            .iter()
+            .filter_map(|s| s.name.strip_prefix("Chat ")?.parse::<usize>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.sessions.push(ChatSession::new(
+            format!("Chat {}", next_num),
+            system_prompt,
         ));
+        self.active_session = self.sessions.len() - 1;
+    }
 
-        state
+    /// Close the active session, unless it is the only one.
+    pub fn close_session(&mut self) {
+        if self.sessions.len() > 1 {
+            self.sessions.remove(self.active_session);
+            self.active_session = self.active_session.min(self.sessions.len() - 1);
+        }
+    }
+
+    /// Switch to the next session, wrapping around.
+    pub fn next_session(&mut self) {
+        self.active_session = (self.active_session + 1) % self.sessions.len();
+    }
+
+    /// Switch to the previous session, wrapping around.
+    pub fn prev_session(&mut self) {
+        self.active_session = if self.active_session == 0 {
+            self.sessions.len() - 1
+        } else {
+            self.active_session - 1
+        };
+    }
+
+    /// Show a status message in the footer for a few seconds.
+    pub fn show_toast(&mut self, message: impl Into<String>, is_error: bool) {
+        self.toast = Some(ChatToast {
+            message: message.into(),
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// The toast, if it was shown recently enough to be still on screen.
+    pub fn recent_toast(&self) -> Option<&ChatToast> {
+        self.toast
+            .as_ref()
+            .filter(|toast| toast.shown_at.elapsed() < TOAST_DURATION)
     }
 }
 
@@ -84,27 +205,43 @@ impl crate::App {
             Constraint::Length(3), // Title
             Constraint::Min(10),   // Messages
             Constraint::Length(4), // Input
-            Constraint::Length(2), // Footer
+            Constraint::Length(3), // Footer
         ]);
         let [title_area, messages_area, input_area, footer_area] = vertical.areas(area);
 
         // Title with max tokens info
         let title = match view {
-            ChatView::Active => Line::from(format!(
-                "Chatting with {} (max tokens: {})",
-                self.topology
-                    .as_ref()
-                    .and_then(|t| t.model.clone())
-                    .unwrap_or_default(),
-                self.config.max_tokens
-            ))
-            .bold()
-            .cyan()
-            .centered(),
+            ChatView::Active => {
+                let (max_tokens, temperature) = self.chat_sampling(self.state.chat.active_session);
+                Line::from(format!(
+                    "Chatting with {} (max tokens: {}, temperature: {:.2})",
+                    self.chat_model(self.state.chat.active_session)
+                        .unwrap_or_default(),
+                    max_tokens,
+                    temperature
+                ))
+                .bold()
+                .cyan()
+                .centered()
+            }
             _ => Line::from("Chatting with Model").bold().cyan().centered(),
         };
+
+        // Session tabs, the active one is highlighted and generating ones are marked
+        let tabs = Line::from_iter(self.state.chat.sessions.iter().enumerate().map(
+            |(i, session)| {
+                let marker = if session.is_generating { "*" } else { "" };
+                let text = format!(" {}{} ", session.name, marker);
+                if i == self.state.chat.active_session {
+                    Span::styled(text, ACTIVE_TAB_STYLE)
+                } else {
+                    Span::styled(text, INACTIVE_TAB_STYLE)
+                }
+            },
+        ))
+        .centered();
         frame.render_widget(
-            Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
+            Paragraph::new(vec![title, tabs]).block(Block::default().borders(Borders::BOTTOM)),
             title_area,
         );
 
@@ -114,7 +251,11 @@ impl crate::App {
                 self.draw_chat_messages(frame, messages_area);
 
                 // Draw input area
-                self.draw_input_area(frame, input_area, self.state.chat.is_generating);
+                let [input_area, context_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Length(1)])
+                        .areas(input_area);
+                self.draw_input_area(frame, input_area, self.state.chat.session().is_generating);
+                self.draw_context_gauge(frame, context_area);
 
                 // Footer
                 let toggle_thinking_hint = if self.state.chat.show_thinking {
@@ -122,18 +263,47 @@ impl crate::App {
                 } else {
                     "Thinking: ON" // meaning it will be turned on
                 };
-                let footer_text = if self.state.chat.is_generating {
+                let footer_text = if self.state.chat.session().selected.is_some() {
+                    "Selecting messages | ↑↓: Select | d/Del: Delete with its pair | Esc: Done"
+                        .to_string()
+                } else if self.state.chat.session().is_generating {
                     format!(
-                        "Generating... | Ctrl+Q: Abort | Ctrl+T: {} | Esc: Exit",
+                        "Generating... | Ctrl+Q: Abort | Ctrl+T: {} | Tab: Next chat | Esc: Exit",
                         toggle_thinking_hint
                     )
                 } else {
                     format!(
-                        "Enter: Send | ↑↓: Scroll | Ctrl+L: Clear | Ctrl+T: {} | Esc: Exit",
+                        "Enter: Send | ↑↓: Scroll | Shift+↑: Select messages | /: Search or command | Ctrl+L: Clear | Ctrl+T: {} | Ctrl+R: Edit last | Ctrl+N/W: New/Close | Tab: Next chat | Ctrl+P: System prompt | Ctrl+S: Sampling | Ctrl+O: Attach file | Ctrl+K: Raw mode | Ctrl+E: Export | Ctrl+Y: Copy | Esc: Exit",
                         toggle_thinking_hint
                     )
                 };
-                frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+
+                // show the toast instead of the hints, if there is a recent one
+                let footer = match self.state.chat.recent_toast() {
+                    Some(toast) => {
+                        let color = if toast.is_error {
+                            Color::Red
+                        } else {
+                            Color::Green
+                        };
+                        Paragraph::new(toast.message.clone()).fg(color)
+                    }
+                    _ => Paragraph::new(footer_text).gray(),
+                };
+                // generation stats of the last (or current) response on top of the hints
+                let [stats_area, hints_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                        .areas(footer_area);
+                if let Some(stats) = &self.state.chat.session().stats {
+                    frame.render_widget(
+                        Paragraph::new(stats.summary()).centered().fg(STATS_COLOR),
+                        stats_area,
+                    );
+                }
+                frame.render_widget(footer.centered().wrap(Wrap { trim: true }), hints_area);
+
+                // popups are drawn last, on top of everything else
+                self.draw_chat_popup(frame, area);
             }
             ChatView::Error(err) => {
                 frame.render_widget(
@@ -155,27 +325,73 @@ impl crate::App {
     }
 
     fn draw_chat_messages(&mut self, frame: &mut Frame, area: Rect) {
+        let history_budget = self.chat_history_budget(self.state.chat.active_session);
+        let timestamp_format = self.config.timestamp_format;
+        let styles = RoleStyles::from(&self.config.theme);
+        let chat = &mut self.state.chat;
+        let show_thinking = chat.show_thinking;
+        let session = &mut chat.sessions[chat.active_session];
+        // older messages that no longer fit are not sent, which is marked in the conversation
+        let history_start = match session.raw_mode {
+            true => 0,
+            false => context::history_start(&session.messages, history_budget),
+        };
+
         let mut lines: Vec<Line> = Vec::new();
-        for msg in &self.state.chat.messages {
+        // index of the header line of the selected message, if selecting
+        let mut selected_line = None;
+        for (i, msg) in session.messages.iter().enumerate() {
+            if i == history_start && history_start > 0 {
+                lines.push(
+                    Line::from(format!(
+                        "── {} older messages truncated from the context ──",
+                        context::num_truncated(&session.messages, history_start)
+                    ))
+                    .style(TRUNCATED_STYLE)
+                    .centered(),
+                );
+                lines.push(Line::from(""));
+            }
+
             // role & timestamp header
             let role_text = msg.role.to_uppercase();
             let role_style = match msg.role.as_str() {
-                "user" => USER_STYLE,
-                "assistant" => ASSISTANT_STYLE,
-                _ => THINK_STYLE,
+                "user" => styles.user,
+                "assistant" => styles.assistant,
+                _ => styles.think,
             };
 
-            lines.push(Line::from(vec![
-                Span::styled(format!("[{}] ", msg.timestamp), TIMESTAMP_STYLE),
+            let mut header = Vec::new();
+            if session.selected == Some(i) {
+                selected_line = Some(lines.len());
+                header.push(Span::styled("▶ ", SELECTED_STYLE));
+            }
+            header.extend([
+                Span::styled(
+                    format!("[{}] ", timestamp_format.format(&msg.timestamp)),
+                    TIMESTAMP_STYLE,
+                ),
                 Span::styled(role_text, role_style),
-            ]));
+            ]);
+            if let Some(usage) = &msg.usage {
+                header.push(Span::styled(
+                    format!(" ({})", usage.summary()),
+                    TIMESTAMP_STYLE,
+                ));
+            }
+            lines.push(Line::from(header));
 
             // Add message content with word wrapping and think tag parsing
             if msg.role == "assistant" {
                 // for assistant messages, parse think tags for the entire content
                 let think_lines =
-                    parse_think_tags_to_lines(&msg.content, false, self.state.chat.show_thinking);
+                    parse_think_tags_to_lines(&msg.content, false, show_thinking, styles.think);
                 lines.extend_from_slice(&think_lines);
+
+                // tool calls are shown as distinct blocks after the content
+                for call in &msg.tool_calls {
+                    lines.extend(call.to_lines());
+                }
             } else {
                 lines.push(Line::from(msg.content.clone()));
             }
@@ -185,62 +401,137 @@ impl crate::App {
         }
 
         // add current response if generating (or has content)
-        if self.state.chat.is_generating || !self.state.chat.current_response.is_empty() {
+        if session.is_generating || !session.current_response.is_empty() {
             lines.push(Line::from(vec![
-                Span::styled(format!("[{}] ", ChatMessage::now()), TIMESTAMP_STYLE),
-                Span::styled("ASSISTANT", ASSISTANT_STYLE),
+                Span::styled(
+                    format!("[{}] ", timestamp_format.format(&ChatMessage::now())),
+                    TIMESTAMP_STYLE,
+                ),
+                Span::styled("ASSISTANT", styles.assistant),
             ]));
 
             // parse current response for think tags
             let think_lines = parse_think_tags_to_lines(
-                &self.state.chat.current_response,
+                &session.current_response,
                 true,
-                self.state.chat.show_thinking,
+                show_thinking,
+                styles.think,
             );
             lines.extend_from_slice(&think_lines);
         }
 
+        // highlight search matches, if searching
+        let (width, height) = (area.width, area.height as usize);
+        if let Some(search) = &mut chat.search {
+            lines = search.apply(lines, width - 2);
+        }
+
+        // scroll offset of the selected message, i.e. the height of the lines before it
+        let selected_offset = selected_line.map(|line| {
+            Paragraph::new(lines[..line].to_vec())
+                .wrap(Wrap { trim: false })
+                .line_count(width - 2) as u16
+        });
+
         // create paragraph
         let mut par = Paragraph::new(lines)
             .block(Block::default().borders(Borders::ALL).title("Conversation"))
             .wrap(Wrap { trim: false });
 
         // update max scroll
-        let (width, height) = (area.width, area.height as usize);
         let num_lines = par.line_count(width - 2); // account for borders
         let max_scroll = num_lines.saturating_sub(height); // prevent underflow
 
-        self.state.chat.scroll_max = max_scroll as u16;
+        session.scroll_max = max_scroll as u16;
+
+        // jump to the current search match, if requested
+        if let Some(search) = chat.search.as_mut().filter(|s| s.jump) {
+            if let Some(offset) = search.match_offsets.get(search.current) {
+                session.scroll_cur = *offset;
+                session.scroll_locked = false;
+            }
+            search.jump = false;
+        }
+
+        // keep the selected message in view, showing it from its header
+        if let Some(offset) = selected_offset {
+            let visible = height.saturating_sub(2) as u16;
+            if offset < session.scroll_cur || offset >= session.scroll_cur + visible {
+                session.scroll_cur = offset;
+                session.scroll_locked = false;
+            }
+        }
 
         // sanity check, not needed for our case though
-        self.state.chat.scroll_cur = self.state.chat.scroll_cur.min(self.state.chat.scroll_max);
-        par = par.scroll((self.state.chat.scroll_cur, 0));
+        session.scroll_cur = session.scroll_cur.min(session.scroll_max);
+        par = par.scroll((session.scroll_cur, 0));
         frame.render_widget(par, area);
 
         // update scrollbar
-        self.state.chat.scroll_bar = self
-            .state
-            .chat
+        session.scroll_bar = session
             .scroll_bar
-            .content_length(self.state.chat.scroll_max as usize)
-            .position(self.state.chat.scroll_cur as usize);
+            .content_length(session.scroll_max as usize)
+            .position(session.scroll_cur as usize);
         frame.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓")),
             area,
-            &mut self.state.chat.scroll_bar,
+            &mut session.scroll_bar,
         );
     }
 
     fn draw_input_area(&mut self, frame: &mut Frame, area: Rect, is_generating: bool) {
+        // the search bar replaces the input area while searching
+        if let Some(search) = &self.state.chat.search {
+            let width = area.width.max(3) - 3;
+            if search.is_editing {
+                let scroll = search.input.visual_scroll(width as usize);
+                frame.render_widget(
+                    Paragraph::new(search.input.value())
+                        .scroll((0, scroll as u16))
+                        .block(
+                            Block::bordered().title(
+                                "Search, or a command like :bench 10 or :model qwen (Enter: Run | Esc: Cancel)",
+                            ),
+                        ),
+                    area,
+                );
+                let x = search.input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((area.x + x as u16, area.y + 1));
+            } else {
+                frame.render_widget(
+                    Paragraph::new(search.status()).block(
+                        Block::bordered().title("Search (n/N: Next/Prev | /: Edit | Esc: Close)"),
+                    ),
+                    area,
+                );
+            }
+            return;
+        }
+
         // keep 2 for borders and 1 for cursor
         let width = area.width.max(3) - 3;
         let scroll = self.state.chat.input.visual_scroll(width as usize);
 
+        let mut title = if self.state.chat.session().editing_from.is_some() {
+            "Input (editing last message, Esc to cancel)".to_string()
+        } else {
+            "Input".to_string()
+        };
+        if self.state.chat.session().raw_mode {
+            title.push_str(" [raw completion]");
+        }
+        if let Some(attachment) = &self.state.chat.attachment {
+            title.push_str(&format!(
+                " [attached: {}, {}]",
+                attachment.name,
+                attachment.size()
+            ));
+        }
         let input = Paragraph::new(self.state.chat.input.value())
             .scroll((0, scroll as u16))
-            .block(Block::bordered().title("Input"));
+            .block(Block::bordered().title(title));
         frame.render_widget(input, area);
 
         if !is_generating {
@@ -251,52 +542,158 @@ impl crate::App {
         }
     }
 
+    /// Draw a gauge of the estimated context usage of the next request
+    /// (conversation, input and the reserved max tokens) against `seq_len`.
+    fn draw_context_gauge(&mut self, frame: &mut Frame, area: Rect) {
+        let seq_len = self.config.seq_len as usize;
+        let (max_tokens, _) = self.chat_sampling(self.state.chat.active_session);
+        let prompt_tokens = self.chat_context_tokens();
+        let total = prompt_tokens + max_tokens as usize;
+
+        let ratio = if seq_len == 0 {
+            1.0
+        } else {
+            (total as f64 / seq_len as f64).min(1.0)
+        };
+        let color = if total > seq_len {
+            Color::Red
+        } else if ratio > 0.75 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let mut label = format!(
+            "Context: ~{} + {} / {} tokens",
+            prompt_tokens, max_tokens, seq_len
+        );
+        if total > seq_len {
+            label.push_str(" (next request may exceed seq_len!)");
+        }
+
+        frame.render_widget(
+            LineGauge::default()
+                .ratio(ratio)
+                .label(label)
+                .filled_style(Style::default().fg(color))
+                .unfilled_style(Style::default().fg(Color::DarkGray))
+                .line_set(symbols::line::THICK),
+            area,
+        );
+    }
+
+    /// Estimated number of prompt tokens of the next request of the active session,
+    /// i.e. the system prompt, the conversation (unless in raw mode), and the current input & attachment.
+    fn chat_context_tokens(&self) -> usize {
+        let chat = &self.state.chat;
+        let session = chat.session();
+        let mut tokens = 0;
+        // raw prompts are sent without the system prompt and the history
+        if !session.raw_mode {
+            let history_budget = self.chat_history_budget(chat.active_session);
+            let history_start = context::history_start(&session.messages, history_budget);
+            tokens += estimate_tokens(&session.system_prompt);
+            for msg in session.messages.iter().skip(history_start) {
+                if msg.role != "system" {
+                    tokens += context::message_tokens(msg);
+                }
+            }
+        }
+        tokens += estimate_tokens(chat.input.value());
+        if let Some(attachment) = &chat.attachment {
+            tokens += estimate_tokens(&attachment.content);
+        }
+        tokens
+    }
+
+    /// Handle input in the message-selection mode, see [`ChatSession::selected`].
+    fn handle_chat_selection_input(&mut self, key: KeyEvent) {
+        let session = self.state.chat.session_mut();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => session.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => session.select_next(),
+            KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => {
+                let num_deleted = session.delete_selected();
+                if num_deleted > 0 {
+                    self.state
+                        .chat
+                        .show_toast(format!("Deleted {} message(s).", num_deleted), false);
+                }
+            }
+            KeyCode::Esc | KeyCode::Enter => session.selected = None,
+            _ => {}
+        }
+    }
+
+    /// Handle mouse events in the chat view, where the wheel scrolls the messages.
+    pub fn handle_chat_mouse(&mut self, mouse: MouseEvent, view: &ChatView) {
+        if !matches!(view, ChatView::Active) || self.state.chat.popup.is_some() {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.state.chat.session_mut().scroll_up(),
+            MouseEventKind::ScrollDown => self.state.chat.session_mut().scroll_down(),
+            _ => {}
+        }
+    }
+
     pub fn handle_chat_input(&mut self, key: KeyEvent, view: &ChatView) {
         if let ChatView::Active = view {
-            if self.state.chat.is_generating {
+            // an open popup takes over all inputs
+            if let Some(popup) = self.state.chat.popup {
+                self.handle_chat_popup_input(key, popup);
+                return;
+            }
+
+            // so does the search
+            if self.state.chat.search.is_some() {
+                self.handle_chat_search_input(key);
+                return;
+            }
+
+            // and the message-selection mode
+            if self.state.chat.session().selected.is_some() {
+                self.handle_chat_selection_input(key);
+                return;
+            }
+
+            // session navigation & search are available regardless of the generation state
+            match (key.modifiers, key.code) {
+                (KeyModifiers::NONE, KeyCode::Char('/'))
+                    if self.state.chat.input.value().is_empty() =>
+                {
+                    self.state.chat.search = Some(ChatSearch {
+                        is_editing: true,
+                        ..Default::default()
+                    });
+                    return;
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('n') | KeyCode::Char('N')) => {
+                    self.state.chat.new_session(&self.config.system_prompt);
+                    return;
+                }
+                (_, KeyCode::Tab) => {
+                    self.state.chat.next_session();
+                    return;
+                }
+                (_, KeyCode::BackTab) => {
+                    self.state.chat.prev_session();
+                    return;
+                }
+                _ => {}
+            }
+
+            if self.state.chat.session().is_generating {
                 match (key.modifiers, key.code) {
                     (_, KeyCode::Esc) => {
-                        // we allow to exit chat even when generating
-                        // the stream may continue in the background
+                        // we allow to exit chat even when generating,
+                        // the response keeps streaming in the background
                         self.view = AppView::Menu;
                     }
-                    // scroll up (offset shrinks)
-                    (_, KeyCode::Up) => {
-                        if self.state.chat.scroll_cur > 0 {
-                            self.state.chat.scroll_cur -= 1;
-                            self.state.chat.scroll_locked = false;
-                        }
-                    }
-                    // scroll down (offset grows)
-                    (_, KeyCode::Down) => {
-                        if self.state.chat.scroll_cur < self.state.chat.scroll_max {
-                            self.state.chat.scroll_cur += 1;
-                            self.state.chat.scroll_locked = false;
-
-                            // lock anyways if we are back at the bottom
-                            if self.state.chat.scroll_cur == self.state.chat.scroll_max {
-                                self.state.chat.scroll_locked = true;
-                            }
-                        }
-                    }
+                    (_, KeyCode::Up) => self.state.chat.session_mut().scroll_up(),
+                    (_, KeyCode::Down) => self.state.chat.session_mut().scroll_down(),
                     (KeyModifiers::CONTROL, KeyCode::Char('q') | KeyCode::Char('Q')) => {
-                        // abort generation - TODO: would need to implement cancellation
-                        if !self.state.chat.current_response.is_empty() {
-                            self.state
-                                .chat
-                                .messages
-                                .push_back(ChatMessage::new_assistant(
-                                    &self.state.chat.current_response,
-                                ));
-                        }
-                        self.state.chat.current_response.clear();
-
-                        self.state
-                            .chat
-                            .messages
-                            .push_back(ChatMessage::new_system("Generation aborted by user."));
-                        self.state.chat.is_generating = false;
-                        self.state.chat.stream_rx = None; // clear the stream
+                        self.state.chat.session_mut().abort();
                     }
                     (KeyModifiers::CONTROL, KeyCode::Char('t') | KeyCode::Char('T')) => {
                         self.state.chat.show_thinking = !self.state.chat.show_thinking
@@ -305,27 +702,58 @@ impl crate::App {
                 }
             } else {
                 match (key.modifiers, key.code) {
+                    (_, KeyCode::Esc) if self.state.chat.session().editing_from.is_some() => {
+                        // cancel editing the recalled message
+                        self.state.chat.session_mut().editing_from = None;
+                        self.state.chat.input.reset();
+                    }
                     (_, KeyCode::Esc) => {
                         self.view = AppView::Menu;
                     }
-                    // scroll up (offset shrinks)
-                    (_, KeyCode::Up) => {
-                        if self.state.chat.scroll_cur > 0 {
-                            self.state.chat.scroll_cur -= 1;
-                        }
+                    (KeyModifiers::SHIFT, KeyCode::Up) => {
+                        self.state.chat.session_mut().start_selection();
                     }
-                    // scroll down (offset grows)
-                    (_, KeyCode::Down) => {
-                        if self.state.chat.scroll_cur < self.state.chat.scroll_max {
-                            self.state.chat.scroll_cur += 1;
+                    (_, KeyCode::Up) => self.state.chat.session_mut().scroll_up(),
+                    (_, KeyCode::Down) => self.state.chat.session_mut().scroll_down(),
+                    (KeyModifiers::CONTROL, KeyCode::Char('l') | KeyCode::Char('L')) => {
+                        self.state.chat.session_mut().clear();
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('w') | KeyCode::Char('W')) => {
+                        self.state.chat.close_session();
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('e') | KeyCode::Char('E')) => {
+                        self.export_chat();
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('y') | KeyCode::Char('Y')) => {
+                        self.yank_chat();
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('r') | KeyCode::Char('R')) => {
+                        if let Some(content) =
+                            self.state.chat.session_mut().recall_last_user_message()
+                        {
+                            self.state.chat.input = tui_input::Input::new(content);
                         }
                     }
-                    (KeyModifiers::CONTROL, KeyCode::Char('l') | KeyCode::Char('L')) => {
-                        self.state.chat.messages.clear();
-                        self.state.chat.messages.push_back(ChatMessage::new_system(
-                            "Chat cleared. Start a new conversation!",
-                        ));
-                        self.state.chat.scroll_cur = 0;
+                    (KeyModifiers::CONTROL, KeyCode::Char('p') | KeyCode::Char('P')) => {
+                        let system_prompt = self.state.chat.session().system_prompt.clone();
+                        self.open_chat_popup(ChatPopup::SystemPrompt, system_prompt);
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('s') | KeyCode::Char('S')) => {
+                        let (max_tokens, _) = self.chat_sampling(self.state.chat.active_session);
+                        self.open_chat_popup(ChatPopup::MaxTokens, max_tokens.to_string());
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('k') | KeyCode::Char('K')) => {
+                        let session = self.state.chat.session_mut();
+                        session.raw_mode = !session.raw_mode;
+                        let message = if session.raw_mode {
+                            "Raw mode: prompts are sent to /v1/completions without a chat template."
+                        } else {
+                            "Chat mode: messages are sent to /v1/chat/completions."
+                        };
+                        self.state.chat.show_toast(message, false);
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('o') | KeyCode::Char('O')) => {
+                        self.open_chat_popup(ChatPopup::AttachFile, String::new());
                     }
                     (KeyModifiers::CONTROL, KeyCode::Char('t') | KeyCode::Char('T')) => {
                         self.state.chat.show_thinking = !self.state.chat.show_thinking
@@ -333,23 +761,13 @@ impl crate::App {
 
                     (_, KeyCode::Enter) => {
                         let input_buffer = self.state.chat.input.value().trim();
-                        if !input_buffer.is_empty() {
-                            let user_input = input_buffer.to_string();
+                        if !input_buffer.is_empty() || self.state.chat.attachment.is_some() {
+                            let mut user_input = input_buffer.to_string();
+                            if let Some(attachment) = self.state.chat.attachment.take() {
+                                user_input = attachment.append_to(&user_input);
+                            }
                             self.state.chat.input.reset();
-
-                            // add user message
-                            self.state
-                                .chat
-                                .messages
-                                .push_back(ChatMessage::new_user(&user_input));
-
-                            // set generating state
-                            self.state.chat.is_generating = true;
-                            self.state.chat.scroll_locked = true;
-                            self.state.chat.current_response.clear();
-
-                            // store the message for API call
-                            self.state.chat.pending_chat_message = Some(user_input);
+                            self.state.chat.session_mut().submit(user_input);
                         }
                     }
 
@@ -367,129 +785,341 @@ impl crate::App {
     }
 }
 
-/// Helper function to clean model-specific special tokens from streaming content
-fn clean_model_tokens(content: &str) -> String {
-    let mut cleaned = content.to_string();
-    let tokens_to_remove = [
-        "<|im_start|>",  // Qwen models (shouldn't appear but just in case)
-        "<|im_end|>",    // Qwen models
-        "<|endoftext|>", // GPT models
-        "</s>",          // Llama models
-        "<s>",           // Llama models
-        "[INST]",        // Instruction models
-        "[/INST]",       // Instruction models
-        "�",             // Unicode replacement character (malformed UTF-8)
-    ];
-    for token in &tokens_to_remove {
-        cleaned = cleaned.replace(token, "");
-    }
-
-    cleaned
+impl crate::App {
+    /// Max tokens and temperature of the given session,
+    /// i.e. its overrides if any, otherwise the configured values.
+    pub fn chat_sampling(&self, idx: usize) -> (u32, f32) {
+        let session = &self.state.chat.sessions[idx];
+        (
+            session.max_tokens.unwrap_or(self.config.max_tokens),
+            session.temperature.unwrap_or(self.config.temperature),
+        )
+    }
+
+    /// Model that the given session chats with, i.e. its own model if that is still
+    /// loaded, otherwise the model of the shown topology.
+    pub fn chat_model(&self, idx: usize) -> Option<String> {
+        let loaded = self.loaded_models();
+        match &self.state.chat.sessions[idx].model {
+            Some(model) if loaded.contains(model) => Some(model.clone()),
+            _ => loaded.into_iter().next(),
+        }
+    }
+
+    /// Token budget of the history of the given session, see [`crate::config::Config::context_budget`].
+    pub fn chat_history_budget(&self, idx: usize) -> usize {
+        if self.config.context_budget > 0 {
+            return self.config.context_budget as usize;
+        }
+
+        let (max_tokens, _) = self.chat_sampling(idx);
+        let system_tokens = estimate_tokens(&self.state.chat.sessions[idx].system_prompt);
+        (self.config.seq_len as usize).saturating_sub(max_tokens as usize + system_tokens)
+    }
+
+    /// Enter the chat view, offering to resume the autosaved chat of
+    /// a previous run if there is one and nothing has been said yet.
+    pub fn enter_chat(&mut self) {
+        self.view = AppView::Chat(ChatView::Active);
+
+        let chat = &mut self.state.chat;
+        if chat.resume_offered {
+            return;
+        }
+        chat.resume_offered = true;
+
+        let is_fresh = chat
+            .sessions
+            .iter()
+            .all(|s| s.messages.iter().all(|m| m.role == "system"));
+        if let Some(snapshot) =
+            ChatSnapshot::load_latest().filter(|s| is_fresh && s.num_messages() > 0)
+        {
+            chat.resumable = Some(snapshot);
+            chat.popup = Some(ChatPopup::Resume);
+        }
+    }
+
+    /// Snapshot the given session if enough messages were added since the last autosave.
+    fn autosave_chat_session(&mut self, idx: usize) {
+        let interval = self.config.autosave_interval as usize;
+        let session = &mut self.state.chat.sessions[idx];
+        if interval == 0 || session.messages.len() < session.autosaved_len + interval {
+            return;
+        }
+
+        session.autosaved_len = session.messages.len();
+        if let Err(e) = ChatSnapshot::new(session).save() {
+            self.state
+                .chat
+                .show_toast(format!("Could not autosave chat: {}", e), true);
+        }
+    }
+
+    /// Copy the transcript of the active session to the system clipboard, as Markdown.
+    ///
+    /// This uses the OSC 52 escape sequence, so that it works over SSH as well;
+    /// the terminal must support it (most do, tmux needs `set-clipboard on`).
+    fn yank_chat(&mut self) {
+        let session = self.state.chat.session();
+        let transcript = export::to_markdown(&session.name, &session.messages);
+        let num_messages = session.messages.len();
+        match crossterm::execute!(
+            std::io::stdout(),
+            crossterm::clipboard::CopyToClipboard::to_clipboard_from(transcript)
+        ) {
+            Ok(()) => self.state.chat.show_toast(
+                format!("Copied {} messages to the clipboard.", num_messages),
+                false,
+            ),
+            Err(e) => self
+                .state
+                .chat
+                .show_toast(format!("Could not copy chat: {}", e), true),
+        }
+    }
+
+    /// Export the active session to a file, see [`crate::config::ExportFormat`].
+    fn export_chat(&mut self) {
+        let session = self.state.chat.session();
+        match export::export_conversation(
+            &session.name,
+            &session.messages,
+            self.config.export_format,
+        ) {
+            Ok(path) => self
+                .state
+                .chat
+                .show_toast(format!("Exported to {}", path.display()), false),
+            Err(e) => self
+                .state
+                .chat
+                .show_toast(format!("Could not export chat: {}", e), true),
+        }
+    }
 }
 
 // API functions for chat
 impl ChatView {
+    /// Send the pending message of the session, streaming the response through the returned receiver.
+    ///
+    /// In raw mode, only the last message is sent as a prompt to the completions endpoint,
+    /// otherwise the oldest turns are dropped to fit the history within `history_budget` tokens.
     pub async fn send_message(
         api_url: &str,
-        messages: &VecDeque<ChatMessage>,
+        session: &ChatSession,
         model: &str,
         max_tokens: u32,
         temperature: f32,
-    ) -> Result<mpsc::UnboundedReceiver<String>, String> {
+        history_budget: usize,
+        cleanup_rules: &[CleanupRule],
+    ) -> Result<(mpsc::UnboundedReceiver<StreamEvent>, AbortHandle), String> {
+        let cleaner = TokenCleaner::new(cleanup_rules, model)?;
         let (tx, rx) = mpsc::unbounded_channel();
 
-        // Build message history for API
-        let mut api_messages = Vec::new();
+        let request = if session.raw_mode {
+            // the new message is the last one, sent as is without a chat template
+            let prompt = session
+                .messages
+                .back()
+                .map(|msg| msg.content.clone())
+                .unwrap_or_default();
+            StreamRequest::Completion(CompletionRequest {
+                model: model.to_string(),
+                prompt,
+                max_tokens: Some(max_tokens),
+                temperature: Some(temperature),
+                stream: true,
+            })
+        } else {
+            // Build message history for API
+            let mut api_messages = Vec::new();
 
-        // Add conversation
-        // Skip the system message and don't duplicate the new message
-        for msg in messages.iter() {
-            if msg.role != "system" {
-                api_messages.push(msg.into());
+            // System prompt goes first, if there is one
+            if !session.system_prompt.is_empty() {
+                api_messages.push(ApiMessage::system(&session.system_prompt));
+            }
+
+            // Add conversation, without the turns that do not fit
+            // Skip the system message and don't duplicate the new message
+            let history_start = context::history_start(&session.messages, history_budget);
+            for msg in session.messages.iter().skip(history_start) {
+                if msg.role != "system" {
+                    api_messages.push(msg.into());
+                }
             }
-        }
 
-        // The new message is already added to messages in handle_chat_input,
-        // so we don't add it again here
-        let request = ChatRequest {
-            model: model.to_string(),
-            messages: api_messages,
-            max_tokens: Some(max_tokens),
-            temperature: Some(temperature),
-            stream: true,
+            // The new message is already added to messages in handle_chat_input,
+            // so we don't add it again here
+            StreamRequest::Chat(ChatRequest {
+                model: model.to_string(),
+                messages: api_messages,
+                max_tokens: Some(max_tokens),
+                temperature: Some(temperature),
+                stream: true,
+            })
         };
 
         let api_url = api_url.to_string();
 
-        // Spawn async task to handle streaming
-        tokio::spawn(async move {
-            if let Err(e) = stream_chat_response(api_url, request, tx).await {
+        // Spawn async task to handle streaming, aborting it drops the request
+        let task = tokio::spawn(async move {
+            if let Err(e) = stream_chat_response(api_url, request, cleaner, tx).await {
                 eprintln!("Stream error: {}", e);
             }
         });
 
-        Ok(rx)
+        Ok((rx, task.abort_handle()))
     }
 }
 
+/// Number of times an interrupted stream is retried before giving up.
+const STREAM_MAX_RETRIES: u32 = 3;
+
+/// Backoff before the first stream retry, doubled on each retry.
+const STREAM_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Streams the chat response, retrying with backoff if the stream is interrupted
+/// (e.g. a connection reset while a shard hiccups).
+///
+/// On a retry, a raw completion resumes by appending the response generated so far
+/// to the prompt. A chat is only retried if nothing was generated yet, otherwise
+/// the partial response is kept and the interruption is reported.
 async fn stream_chat_response(
     api_url: String,
-    request: ChatRequest,
-    tx: mpsc::UnboundedSender<String>,
+    request: StreamRequest,
+    cleaner: TokenCleaner,
+    tx: mpsc::UnboundedSender<StreamEvent>,
 ) -> color_eyre::Result<()> {
-    use futures::StreamExt;
-
     let client = reqwest::Client::new();
-    let url = format!("{}/v1/chat/completions", api_url);
+    let url = format!("{}{}", api_url, request.path());
 
-    let response = client.post(&url).json(&request).send().await?;
+    let mut generated = String::new();
+    let mut num_tokens = 0;
+    let mut retries = 0;
+    loop {
+        // resume from whatever has been generated so far
+        let attempt = request.resumed(&generated, num_tokens);
+
+        let result = stream_chat_attempt(
+            &client,
+            &url,
+            &attempt,
+            &cleaner,
+            &tx,
+            &mut generated,
+            &mut num_tokens,
+        )
+        .await;
+        match result {
+            Ok(true) => return Ok(()),
+            Ok(false) | Err(_)
+                if retries < STREAM_MAX_RETRIES && request.can_resume(&generated) =>
+            {
+                retries += 1;
+                tx.send(StreamEvent::Retry(retries)).ok();
+                tokio::time::sleep(STREAM_RETRY_BACKOFF * 2u32.pow(retries - 1)).await;
+            }
+            Ok(false) => {
+                // out of retries (or unable to resume), keep what we have
+                tx.send(StreamEvent::Interrupted).ok();
+                return Ok(());
+            }
+            Err(e) => {
+                tx.send(StreamEvent::Error(e.to_string())).ok();
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// A single attempt of streaming the chat response, appending the
+/// received content to `generated`.
+///
+/// Returns `true` if the stream is over (finished, or failed with an API error),
+/// and `false` if it ended before the response was finished, i.e. without `[DONE]`
+/// or a finish reason.
+async fn stream_chat_attempt(
+    client: &reqwest::Client,
+    url: &str,
+    request: &StreamRequest,
+    cleaner: &TokenCleaner,
+    tx: &mpsc::UnboundedSender<StreamEvent>,
+    generated: &mut String,
+    num_tokens: &mut u32,
+) -> color_eyre::Result<bool> {
+    use futures::StreamExt;
+
+    let response = client.post(url).json(request).send().await?;
 
     if !response.status().is_success() {
         let error_text = response.text().await?;
-        tx.send(format!("ERROR: {}", error_text)).ok();
-        return Ok(());
+        tx.send(StreamEvent::Error(error_text)).ok();
+        return Ok(true);
     }
 
-    // Stream the response bytes
+    // Stream the response bytes, parsing them as server-sent events
     let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
+    let mut parser = SseParser::default();
+    let mut tool_calls = Vec::new();
+    let mut is_finished = false;
 
-    while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result?;
-        buffer.push_str(&String::from_utf8_lossy(&chunk));
+    let mut is_eof = false;
+    while !is_eof {
+        let events = match stream.next().await {
+            Some(chunk_result) => parser.push(&chunk_result?),
+            None => {
+                is_eof = true;
+                parser.finish()
+            }
+        };
 
-        // Process all complete lines (SSE lines end with \n)
-        while let Some(line_end) = buffer.find('\n') {
-            let line = buffer[..line_end].to_string();
-            buffer.drain(..=line_end); // Remove the line including the \n
+        for event in events {
+            match event.event.as_deref() {
+                None | Some("message") => {}
+                Some("error") => {
+                    tx.send(StreamEvent::Error(event.data)).ok();
+                    return Ok(true);
+                }
+                // other event types (e.g. pings from gateways) are not for us
+                Some(_) => continue,
+            }
 
-            // Skip empty lines
-            if line.trim().is_empty() {
-                continue;
+            if event.data.trim() == "[DONE]" {
+                send_done(tx, &tool_calls);
+                return Ok(true);
             }
 
-            // Check if this is a data line
-            if let Some(json_str) = line.strip_prefix("data: ") {
-                if json_str.trim() == "[DONE]" {
-                    tx.send("DONE".to_string()).ok();
-                    return Ok(());
+            // Try to parse as JSON
+            if let Ok(chunk) = serde_json::from_str::<StreamChunk>(&event.data) {
+                // usage comes with the final chunk, either along with the
+                // finish reason or in a separate chunk with no choices
+                if let Some(usage) = chunk.usage {
+                    tx.send(StreamEvent::Usage(usage)).ok();
                 }
+                if let Some(choice) = chunk.choices.first() {
+                    if let Some(content) = choice.delta.content.as_ref().or(choice.text.as_ref()) {
+                        *num_tokens += 1;
+                        generated.push_str(content);
 
-                // Try to parse as JSON
-                if let Ok(chunk) = serde_json::from_str::<StreamChunk>(json_str) {
-                    if let Some(choice) = chunk.choices.first() {
-                        if let Some(content) = &choice.delta.content {
-                            // Filter out model-specific special tokens
-                            let cleaned_content = clean_model_tokens(content);
+                        // Filter out model-specific special tokens
+                        let cleaned_content = cleaner.clean(content);
 
-                            // Only send if there's actual content after cleaning
-                            if !cleaned_content.is_empty() {
-                                tx.send(cleaned_content).ok();
-                            }
+                        // Only send if there's actual content after cleaning
+                        if !cleaned_content.is_empty() {
+                            tx.send(StreamEvent::Token(cleaned_content)).ok();
                         }
-                        if choice.finish_reason.is_some() {
-                            tx.send("DONE".to_string()).ok();
-                            return Ok(());
+                    }
+                    if let Some(deltas) = &choice.delta.tool_calls {
+                        ToolCall::merge_deltas(&mut tool_calls, deltas);
+                    }
+                    // keep reading after the finish reason if usage is yet to come,
+                    // the stream is usually terminated by `[DONE]`
+                    if choice.finish_reason.is_some() {
+                        is_finished = true;
+                        if chunk.usage.is_some() {
+                            send_done(tx, &tool_calls);
+                            return Ok(true);
                         }
                     }
                 }
@@ -497,18 +1127,35 @@ async fn stream_chat_response(
         }
     }
 
-    // Send DONE if not already sent
-    tx.send("DONE".to_string()).ok();
-    Ok(())
+    // some servers close the stream right after the finish reason, without `[DONE]`
+    if is_finished {
+        send_done(tx, &tool_calls);
+    }
+    Ok(is_finished)
+}
+
+/// Signal the end of the stream, sending the tool calls (if any) before.
+fn send_done(tx: &mpsc::UnboundedSender<StreamEvent>, tool_calls: &[ToolCall]) {
+    if !tool_calls.is_empty() {
+        tx.send(StreamEvent::ToolCalls(tool_calls.to_vec())).ok();
+    }
+    tx.send(StreamEvent::Done).ok();
 }
 
 impl crate::App {
-    /// Handle async operations for chat state (called during tick).
+    /// Handle async operations for chat state (called during tick), i.e. sending
+    /// the pending messages of all sessions, see [`Self::tick_chat_streams`] for the responses.
     pub(crate) async fn tick_chat(&mut self, view: &ChatView) {
+        for idx in 0..self.state.chat.sessions.len() {
+            self.tick_chat_session(idx, view).await;
+        }
+    }
+
+    async fn tick_chat_session(&mut self, idx: usize, view: &ChatView) {
         // Handle pending chat message
-        if let Some(_message) = self.state.chat.pending_chat_message.take() {
+        if let Some(_message) = self.state.chat.sessions[idx].pending_chat_message.take() {
             if let ChatView::Active = view {
-                let Some(model) = self.topology.as_ref().and_then(|t| t.model.clone()) else {
+                let Some(model) = self.chat_model(idx) else {
                     self.view = AppView::Chat(ChatView::Error(
                         // we dont expect to get there at all without a model,
                         // but it still shall be handled
@@ -517,77 +1164,147 @@ impl crate::App {
                     return;
                 };
 
+                let (max_tokens, temperature) = self.chat_sampling(idx);
                 match ChatView::send_message(
                     &self.config.api_url(),
-                    &self.state.chat.messages,
+                    &self.state.chat.sessions[idx],
                     &model,
-                    self.config.max_tokens,
-                    self.config.temperature,
+                    max_tokens,
+                    temperature,
+                    self.chat_history_budget(idx),
+                    &self.config.token_cleanup,
                 )
                 .await
                 {
-                    Ok(rx) => {
-                        self.state.chat.stream_rx = Some(rx);
+                    Ok((rx, task)) => {
+                        self.state.chat.sessions[idx].stream_rx = Some(rx);
+                        self.state.chat.sessions[idx].stream_task = Some(task);
                     }
                     Err(err) => {
+                        self.state.chat.sessions[idx].is_generating = false;
                         self.view = AppView::Chat(ChatView::Error(err));
                     }
                 }
             }
         }
+    }
+
+    /// Process the streamed responses of all sessions (called during every tick).
+    ///
+    /// This runs regardless of the current view, so that responses keep streaming
+    /// in the background after leaving the chat.
+    pub(crate) fn tick_chat_streams(&mut self) {
+        for idx in 0..self.state.chat.sessions.len() {
+            self.tick_chat_stream(idx);
+        }
+        self.tick_chat_bench();
+    }
 
-        // Process chat stream - but only if we're still in chat state
-        if let Some(mut rx) = self.state.chat.stream_rx.take() {
-            // Check if we're still in chat state
-            if !matches!(self.view, AppView::Chat(_)) {
-                // We've exited chat, don't process the stream
-                // FIXME: ??
-                self.state.chat.stream_rx = None;
-            } else {
-                let mut should_clear_rx = false;
-                let mut new_error_state = None;
-
-                // Try to receive messages without blocking
-                while let Ok(chunk) = rx.try_recv() {
-                    if let AppView::Chat(ChatView::Active) = &mut self.view {
-                        if chunk == "DONE" {
-                            // Finalize the response
-                            if !self.state.chat.current_response.is_empty() {
-                                self.state.chat.messages.push_back(ChatMessage {
-                                    role: "assistant".to_string(),
-                                    content: self.state.chat.current_response.clone(),
-                                    // TODO: store time itself here, convert to string later?
-                                    // or use std instead of chrono?
-                                    timestamp: chrono::Local::now().format("%H:%M").to_string(),
-                                });
-                                self.state.chat.current_response.clear();
-                            }
-                            self.state.chat.is_generating = false;
-                            should_clear_rx = true;
-                            break;
-                        } else if chunk.starts_with("ERROR:") {
-                            new_error_state = Some(chunk);
-                            should_clear_rx = true;
-                            break;
-                        } else {
-                            self.state.chat.current_response.push_str(&chunk);
+    fn tick_chat_stream(&mut self, idx: usize) {
+        let in_chat = matches!(self.view, AppView::Chat(_));
+        let session = &mut self.state.chat.sessions[idx];
+        let Some(mut rx) = session.stream_rx.take() else {
+            return;
+        };
+        let mut should_clear_rx = false;
+        let mut new_error_state = None;
+        let mut stream_toast = None;
+        let mut is_finished = false;
 
-                            // auto-scroll during generation to follow the new content
-                            if self.state.chat.scroll_locked {
-                                self.state.chat.scroll_cur = self.state.chat.scroll_max;
-                            }
-                        }
+        // Try to receive messages without blocking
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                end @ (StreamEvent::Done | StreamEvent::Interrupted) => {
+                    if end == StreamEvent::Interrupted {
+                        stream_toast =
+                            Some("Stream interrupted, the response is incomplete.".to_string());
+                    }
+
+                    // Finalize the response
+                    if !session.current_response.is_empty() || !session.tool_calls.is_empty() {
+                        session.messages.push_back(ChatMessage {
+                            role: "assistant".to_string(),
+                            content: session.current_response.clone(),
+                            timestamp: ChatMessage::now(),
+                            usage: session.usage.take(),
+                            tool_calls: std::mem::take(&mut session.tool_calls),
+                        });
+                        session.current_response.clear();
+                    }
+                    if let Some(stats) = &mut session.stats {
+                        stats.finish();
                     }
+                    session.is_generating = false;
+                    should_clear_rx = true;
+                    is_finished = true;
+                    break;
+                }
+                StreamEvent::Retry(retry) => {
+                    stream_toast = Some(format!(
+                        "Stream interrupted, retrying ({}/{})...",
+                        retry, STREAM_MAX_RETRIES
+                    ));
+                }
+                StreamEvent::ToolCalls(tool_calls) => session.tool_calls = tool_calls,
+                StreamEvent::Usage(usage) => session.usage = Some(usage),
+                StreamEvent::Error(error) => {
+                    new_error_state = Some(error);
+                    should_clear_rx = true;
+                    break;
                 }
+                StreamEvent::Token(token) => {
+                    session.current_response.push_str(&token);
+                    if let Some(stats) = &mut session.stats {
+                        stats.record_token();
+                    }
 
-                // Handle state changes after processing
-                if let Some(error) = new_error_state {
-                    self.view = AppView::Chat(ChatView::Error(error));
-                } else if !should_clear_rx {
-                    // put the receiver back if we're not done
-                    self.state.chat.stream_rx = Some(rx);
+                    // auto-scroll during generation to follow the new content
+                    if session.scroll_locked {
+                        session.scroll_cur = session.scroll_max;
+                    }
                 }
             }
         }
+
+        // Handle state changes after processing
+        if should_clear_rx {
+            session.stream_task = None;
+        }
+        if let Some(error) = &new_error_state {
+            session.is_generating = false;
+            if in_chat {
+                self.view = AppView::Chat(ChatView::Error(error.clone()));
+            } else {
+                // keep the error in the conversation, as the error view is not seen
+                session
+                    .messages
+                    .push_back(ChatMessage::new_system(&format!("Error: {}", error)));
+            }
+        } else if !should_clear_rx {
+            // put the receiver back if we're not done
+            session.stream_rx = Some(rx);
+        }
+
+        if let Some(message) = stream_toast {
+            self.state.chat.show_toast(message, true);
+        }
+        if is_finished {
+            self.autosave_chat_session(idx);
+        }
+
+        // notify about background generations, shown in the menu footer
+        // and optionally by the terminal, if it is not being looked at
+        if (!in_chat || !self.is_focused) && should_clear_rx {
+            let name = &self.state.chat.sessions[idx].name;
+            let (message, is_error) = match new_error_state {
+                Some(_) => (format!("Generation failed in {}.", name), true),
+                None => (format!("Response in {} is ready.", name), false),
+            };
+            // a failing notification is not worth interrupting for
+            self.config.notification.notify(&message).ok();
+            if !in_chat {
+                self.state.chat.show_toast(message, is_error);
+            }
+        }
     }
 }
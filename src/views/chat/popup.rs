@@ -0,0 +1,373 @@
+use super::ChatPopup;
+use super::attach::{Attachment, MAX_ATTACHMENT_SIZE};
+use crate::utils::centered_rect;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use tui_input::backend::crossterm::EventHandler;
+
+/// Step of the max tokens adjustment with arrow keys.
+const MAX_TOKENS_STEP: u32 = 256;
+/// Step of the temperature adjustment with arrow keys.
+const TEMPERATURE_STEP: f32 = 0.1;
+
+impl crate::App {
+    /// Draw the open chat popup (if any) on top of the chat.
+    pub(super) fn draw_chat_popup(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(popup) = self.state.chat.popup else {
+            return;
+        };
+
+        match popup {
+            ChatPopup::SystemPrompt => {
+                let help = vec![
+                    Line::from(vec![
+                        "System prompt for ".into(),
+                        self.state.chat.session().name.clone().bold().cyan(),
+                    ]),
+                    Line::from(""),
+                    Line::from("Sent before the conversation to steer the model.".dark_gray()),
+                    Line::from("Leave empty to send no system prompt.".dark_gray()),
+                ];
+                self.draw_input_popup(
+                    frame,
+                    area,
+                    " System Prompt ",
+                    help,
+                    "Enter: Save | Esc: Cancel",
+                );
+            }
+            ChatPopup::Resume => {
+                let Some(snapshot) = &self.state.chat.resumable else {
+                    return;
+                };
+                let lines = vec![
+                    Line::from("A chat from a previous run was autosaved:"),
+                    Line::from(""),
+                    Line::from(vec![
+                        snapshot.name.clone().bold().cyan(),
+                        format!(" ({} messages)", snapshot.num_messages()).into(),
+                    ]),
+                    Line::from(
+                        format!("Saved at {}", snapshot.saved_at.format("%Y-%m-%d %H:%M"))
+                            .dark_gray(),
+                    ),
+                    Line::from(""),
+                    Line::from("Would you like to resume it?"),
+                ];
+                draw_message_popup(
+                    frame,
+                    area,
+                    " Resume Previous Chat ",
+                    lines,
+                    "Enter: Resume | Esc: Start fresh",
+                );
+            }
+            ChatPopup::AttachFile => {
+                let cwd = std::env::current_dir()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default();
+                let mut help = vec![
+                    Line::from("Path of a text file to attach to the next message."),
+                    Line::from(""),
+                    Line::from(format!("Relative to: {}", cwd).dark_gray()),
+                    Line::from(
+                        format!("Files up to {} KB are allowed.", MAX_ATTACHMENT_SIZE / 1024)
+                            .dark_gray(),
+                    ),
+                ];
+                if let Some(attachment) = &self.state.chat.attachment {
+                    help.push(Line::from(
+                        format!(
+                            "Currently attached: {}, leave empty to remove it.",
+                            attachment.name
+                        )
+                        .yellow(),
+                    ));
+                }
+                self.draw_input_popup(
+                    frame,
+                    area,
+                    " Attach File ",
+                    help,
+                    "Enter: Attach | Esc: Cancel",
+                );
+            }
+            ChatPopup::MaxTokens | ChatPopup::Temperature => {
+                let (max_tokens, temperature) = self.chat_sampling(self.state.chat.active_session);
+                let field = |label: &str, value: String, selected: bool| {
+                    let line = Line::from(format!(
+                        "{}{}: {}",
+                        if selected { "> " } else { "  " },
+                        label,
+                        value
+                    ));
+                    if selected {
+                        line.bold().yellow()
+                    } else {
+                        line.gray()
+                    }
+                };
+                let help = vec![
+                    Line::from(vec![
+                        "Sampling for ".into(),
+                        self.state.chat.session().name.clone().bold().cyan(),
+                    ]),
+                    Line::from(""),
+                    field(
+                        "Max tokens",
+                        max_tokens.to_string(),
+                        popup == ChatPopup::MaxTokens,
+                    ),
+                    field(
+                        "Temperature",
+                        format!("{:.2}", temperature),
+                        popup == ChatPopup::Temperature,
+                    ),
+                    Line::from(""),
+                    Line::from(
+                        "Only applies to this session, leave empty to use the value from Settings."
+                            .dark_gray(),
+                    ),
+                ];
+                self.draw_input_popup(
+                    frame,
+                    area,
+                    " Sampling ",
+                    help,
+                    "Enter: Save | ↑↓: Adjust | Tab: Next field | Esc: Cancel",
+                );
+            }
+        }
+    }
+
+    /// Draw a popup with some help text and a single-line input at the bottom.
+    fn draw_input_popup(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        help: Vec<Line>,
+        hint: &str,
+    ) {
+        let popup_area = centered_rect(60, 40, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [help_area, input_area, hint_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        frame.render_widget(Paragraph::new(help).wrap(Wrap { trim: false }), help_area);
+
+        // keep 2 for borders and 1 for cursor
+        let width = input_area.width.max(3) - 3;
+        let input = &self.state.chat.popup_input;
+        let scroll = input.visual_scroll(width as usize);
+        frame.render_widget(
+            Paragraph::new(input.value())
+                .scroll((0, scroll as u16))
+                .block(Block::bordered()),
+            input_area,
+        );
+        let x = input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        frame.render_widget(Paragraph::new(hint).centered().gray(), hint_area);
+    }
+
+    /// Open a popup, with the popup input initialized to `value`.
+    pub(super) fn open_chat_popup(&mut self, popup: ChatPopup, value: String) {
+        self.state.chat.popup_input = tui_input::Input::new(value);
+        self.state.chat.popup = Some(popup);
+    }
+
+    /// Handle input while a popup is open.
+    pub(super) fn handle_chat_popup_input(&mut self, key: KeyEvent, popup: ChatPopup) {
+        match (popup, key.code) {
+            (_, KeyCode::Esc) => {
+                // declining to resume drops the snapshot, so that it is not offered again
+                if let Some(snapshot) = self.state.chat.resumable.take() {
+                    snapshot.discard();
+                }
+                self.state.chat.popup = None;
+            }
+            (ChatPopup::Resume, KeyCode::Enter) => {
+                if let Some(snapshot) = self.state.chat.resumable.take() {
+                    snapshot.restore(self.state.chat.session_mut());
+                    self.state.chat.show_toast("Previous chat resumed.", false);
+                }
+                self.state.chat.popup = None;
+            }
+            (ChatPopup::Resume, _) => {}
+            (ChatPopup::SystemPrompt, KeyCode::Enter) => {
+                let value = self.state.chat.popup_input.value().trim().to_string();
+                self.state.chat.session_mut().system_prompt = value;
+                self.state.chat.show_toast("System prompt updated.", false);
+                self.state.chat.popup = None;
+            }
+            (ChatPopup::AttachFile, KeyCode::Enter) => {
+                let path = self.state.chat.popup_input.value().trim().to_string();
+                if path.is_empty() {
+                    if self.state.chat.attachment.take().is_some() {
+                        self.state.chat.show_toast("Attachment removed.", false);
+                    }
+                    self.state.chat.popup = None;
+                    return;
+                }
+
+                match Attachment::read(&path) {
+                    Ok(attachment) => {
+                        self.state.chat.show_toast(
+                            format!("Attached {} ({}).", attachment.name, attachment.size()),
+                            false,
+                        );
+                        self.state.chat.attachment = Some(attachment);
+                        self.state.chat.popup = None;
+                    }
+                    // keep the popup open so that the path can be fixed
+                    Err(err) => self.state.chat.show_toast(err, true),
+                }
+            }
+            (ChatPopup::MaxTokens | ChatPopup::Temperature, KeyCode::Enter) => {
+                if self.save_sampling_field(popup) {
+                    self.state
+                        .chat
+                        .show_toast("Sampling updated for this session.", false);
+                    self.state.chat.popup = None;
+                }
+            }
+            (ChatPopup::MaxTokens | ChatPopup::Temperature, KeyCode::Tab | KeyCode::BackTab) => {
+                if self.save_sampling_field(popup) {
+                    let (max_tokens, temperature) =
+                        self.chat_sampling(self.state.chat.active_session);
+                    if popup == ChatPopup::MaxTokens {
+                        self.open_chat_popup(ChatPopup::Temperature, format!("{:.2}", temperature));
+                    } else {
+                        self.open_chat_popup(ChatPopup::MaxTokens, max_tokens.to_string());
+                    }
+                }
+            }
+            (ChatPopup::MaxTokens | ChatPopup::Temperature, KeyCode::Up | KeyCode::Down) => {
+                let increase = key.code == KeyCode::Up;
+                let value = self.state.chat.popup_input.value().trim().to_string();
+                let (max_tokens, temperature) = self.chat_sampling(self.state.chat.active_session);
+                let bumped = if popup == ChatPopup::MaxTokens {
+                    let current = value.parse().unwrap_or(max_tokens);
+                    let next = if increase {
+                        current.saturating_add(MAX_TOKENS_STEP)
+                    } else {
+                        current.saturating_sub(MAX_TOKENS_STEP)
+                    };
+                    next.clamp(1, 100000).to_string()
+                } else {
+                    let current = value.parse().unwrap_or(temperature);
+                    let next = if increase {
+                        current + TEMPERATURE_STEP
+                    } else {
+                        current - TEMPERATURE_STEP
+                    };
+                    format!("{:.2}", next.clamp(0.0, 2.0))
+                };
+                self.state.chat.popup_input = tui_input::Input::new(bumped);
+            }
+            _ => {
+                let event = crossterm::event::Event::Key(key);
+                self.state.chat.popup_input.handle_event(&event);
+            }
+        }
+    }
+
+    /// Parse the popup input into the session override of the given sampling field,
+    /// an empty input removes the override.
+    ///
+    /// Returns `false` (with an error toast) if the input is not a valid value.
+    fn save_sampling_field(&mut self, popup: ChatPopup) -> bool {
+        let value = self.state.chat.popup_input.value().trim().to_string();
+        let session = &mut self.state.chat.sessions[self.state.chat.active_session];
+        let is_valid = match popup {
+            ChatPopup::MaxTokens => parse_override(&value)
+                .map(|t: Option<u32>| session.max_tokens = t.map(|t| t.clamp(1, 100000)))
+                .is_ok(),
+            ChatPopup::Temperature => parse_float_override(&value)
+                .map(|t| session.temperature = t.map(|t| t.clamp(0.0, 2.0)))
+                .is_ok(),
+            ChatPopup::SystemPrompt | ChatPopup::AttachFile | ChatPopup::Resume => true,
+        };
+
+        if !is_valid {
+            self.state
+                .chat
+                .show_toast(format!("Invalid value '{}'.", value), true);
+        }
+        is_valid
+    }
+}
+
+/// Draw a popup with a message and a hint at the bottom, without any input.
+fn draw_message_popup(frame: &mut Frame, area: Rect, title: &str, lines: Vec<Line>, hint: &str) {
+    let popup_area = centered_rect(60, 40, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let [message_area, hint_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }),
+        message_area,
+    );
+    frame.render_widget(Paragraph::new(hint).centered().gray(), hint_area);
+}
+
+/// Parse an optional override, where an empty value means no override.
+fn parse_override<T: std::str::FromStr>(value: &str) -> Result<Option<T>, T::Err> {
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        value.parse().map(Some)
+    }
+}
+
+/// Parse an optional float override like [`parse_override`], rejecting values that
+/// are not finite (e.g. `NaN` or `inf`), which can not be sent to the API.
+fn parse_float_override(value: &str) -> Result<Option<f32>, String> {
+    match parse_override::<f32>(value) {
+        Ok(Some(v)) if !v.is_finite() => Err(format!("{} is not a finite number", v)),
+        result => result.map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_float_override() {
+        assert_eq!(parse_float_override(""), Ok(None));
+        assert_eq!(parse_float_override("0.7"), Ok(Some(0.7)));
+        assert!(parse_float_override("hot").is_err());
+        assert!(parse_float_override("NaN").is_err());
+        assert!(parse_float_override("inf").is_err());
+        assert!(parse_float_override("-inf").is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use super::bench::MAX_BENCH_RUNS;
+use super::styles::SEARCH_MATCH_STYLE;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+use tui_input::backend::crossterm::EventHandler;
+
+/// State of the in-chat search, started with `/`.
+#[derive(Debug, Default)]
+pub struct ChatSearch {
+    /// Search query input.
+    pub input: tui_input::Input,
+    /// Whether the query is being typed, otherwise we are navigating matches.
+    pub is_editing: bool,
+    /// The confirmed query, lowercased.
+    pub query: String,
+    /// Scroll offsets of the lines that match the query, updated on each draw.
+    pub match_offsets: Vec<u16>,
+    /// Index of the current match within `match_offsets`.
+    pub current: usize,
+    /// Whether the scroll should jump to the current match on the next draw.
+    pub jump: bool,
+}
+
+impl ChatSearch {
+    /// Move to the next match, wrapping around.
+    pub fn next_match(&mut self) {
+        if !self.match_offsets.is_empty() {
+            self.current = (self.current + 1) % self.match_offsets.len();
+            self.jump = true;
+        }
+    }
+
+    /// Move to the previous match, wrapping around.
+    pub fn prev_match(&mut self) {
+        if !self.match_offsets.is_empty() {
+            self.current = self
+                .current
+                .checked_sub(1)
+                .unwrap_or(self.match_offsets.len() - 1);
+            self.jump = true;
+        }
+    }
+
+    /// Highlights the matches within the given lines and records the scroll
+    /// offset of each matching line, with respect to the given wrapping width.
+    pub fn apply<'a>(&mut self, lines: Vec<Line<'a>>, width: u16) -> Vec<Line<'a>> {
+        self.match_offsets.clear();
+        if self.query.is_empty() {
+            return lines;
+        }
+
+        let mut offset = 0;
+        let mut result = Vec::with_capacity(lines.len());
+        for line in lines {
+            let line_height = Paragraph::new(line.clone())
+                .wrap(Wrap { trim: false })
+                .line_count(width);
+
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            if text.to_ascii_lowercase().contains(&self.query) {
+                self.match_offsets.push(offset as u16);
+                result.push(highlight_line(line, &self.query));
+            } else {
+                result.push(line);
+            }
+
+            offset += line_height;
+        }
+
+        self.current = self.current.min(self.match_offsets.len().saturating_sub(1));
+        result
+    }
+
+    /// Footer-like status of the search, e.g. `2/5 matches`.
+    pub fn status(&self) -> String {
+        if self.match_offsets.is_empty() {
+            format!("No matches for '{}'", self.query)
+        } else {
+            format!(
+                "'{}': {}/{} matches",
+                self.query,
+                self.current + 1,
+                self.match_offsets.len()
+            )
+        }
+    }
+}
+
+/// Highlight the (ASCII case-insensitive) occurrences of `query` within the spans of a line.
+///
+/// `query` is expected to be lowercased already.
+pub fn highlight_line<'a>(line: Line<'a>, query: &str) -> Line<'a> {
+    let mut spans = Vec::new();
+    for span in line.spans {
+        // ASCII lowercasing keeps byte offsets intact
+        let lower = span.content.to_ascii_lowercase();
+        let mut last = 0;
+        for (start, matched) in lower.match_indices(query) {
+            let end = start + matched.len();
+            if start > last {
+                spans.push(Span::styled(
+                    span.content[last..start].to_string(),
+                    span.style,
+                ));
+            }
+            spans.push(Span::styled(
+                span.content[start..end].to_string(),
+                span.style.patch(SEARCH_MATCH_STYLE),
+            ));
+            last = end;
+        }
+
+        if last == 0 {
+            spans.push(span);
+        } else if last < span.content.len() {
+            spans.push(Span::styled(span.content[last..].to_string(), span.style));
+        }
+    }
+
+    Line {
+        spans,
+        style: line.style,
+        alignment: line.alignment,
+    }
+}
+
+/// Commands that can be run from the `/` bar by prefixing them with `:`,
+/// similar to the command line of vim.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatCommand {
+    /// `:bench <runs> [concurrency]`, benchmark the current prompt.
+    Bench { runs: usize, concurrency: usize },
+    /// `:bench stop`, stop the running benchmark.
+    BenchStop,
+    /// `:model [name]`, chat with the loaded model that matches the name,
+    /// or with the next loaded model if no name is given.
+    Model(Option<String>),
+}
+
+impl ChatCommand {
+    /// Parse a command, returning `None` if the input is not a command
+    /// (and is therefore a search query), or an error for invalid arguments.
+    pub fn parse(input: &str) -> Option<Result<Self, String>> {
+        let mut parts = input.trim_start().strip_prefix(':')?.split_whitespace();
+        match parts.next().unwrap_or_default() {
+            "bench" => {
+                if parts.clone().next() == Some("stop") {
+                    return Some(Ok(ChatCommand::BenchStop));
+                }
+
+                let usage = || format!("Usage: :bench <runs 1-{}> [concurrency]", MAX_BENCH_RUNS);
+                let runs = parts.next().and_then(|n| n.parse().ok());
+                let concurrency = parts.next().map_or(Some(1), |n| n.parse().ok());
+                Some(match (runs, concurrency) {
+                    (Some(runs @ 1..=MAX_BENCH_RUNS), Some(concurrency @ 1..)) => {
+                        Ok(ChatCommand::Bench { runs, concurrency })
+                    }
+                    _ => Err(usage()),
+                })
+            }
+            "model" => {
+                let name = parts.collect::<Vec<_>>().join(" ");
+                Some(Ok(ChatCommand::Model((!name.is_empty()).then_some(name))))
+            }
+            command => Some(Err(format!("Unknown command '{}'", command))),
+        }
+    }
+}
+
+/// The model among `models` that is named `query`, or the only one whose name
+/// contains it (case-insensitive).
+pub fn match_model(models: &[String], query: &str) -> Result<String, String> {
+    if let Some(model) = models.iter().find(|m| *m == query) {
+        return Ok(model.clone());
+    }
+
+    let query = query.to_lowercase();
+    let matches = models
+        .iter()
+        .filter(|m| m.to_lowercase().contains(&query))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [model] => Ok((*model).clone()),
+        [] => Err(format!("No loaded model matches '{}'", query)),
+        _ => Err(format!("Several loaded models match '{}'", query)),
+    }
+}
+
+impl crate::App {
+    /// Run a command typed in the `/` bar.
+    fn run_chat_command(&mut self, command: ChatCommand) {
+        match command {
+            ChatCommand::Bench { runs, concurrency } => self.start_benchmark(runs, concurrency),
+            ChatCommand::BenchStop => match self.state.chat.bench.take() {
+                Some(bench) => {
+                    bench.task.abort();
+                    self.state.chat.show_toast("Benchmark stopped.", false);
+                }
+                None => self.state.chat.show_toast("No benchmark is running.", true),
+            },
+            ChatCommand::Model(name) => self.select_chat_model(name.as_deref()),
+        }
+    }
+
+    /// Switch the model of the active session, see [`ChatCommand::Model`].
+    fn select_chat_model(&mut self, name: Option<&str>) {
+        let models = self.loaded_models();
+        let idx = self.state.chat.active_session;
+        let model = match name {
+            Some(name) => match_model(&models, name),
+            None => {
+                let current = self.chat_model(idx);
+                let next = models
+                    .iter()
+                    .position(|m| Some(m) == current.as_ref())
+                    .map_or(0, |i| (i + 1) % models.len().max(1));
+                models
+                    .get(next)
+                    .cloned()
+                    .ok_or_else(|| "No model is loaded".to_string())
+            }
+        };
+
+        match model {
+            Ok(model) => {
+                self.state
+                    .chat
+                    .show_toast(format!("Chatting with {}", model), false);
+                self.state.chat.sessions[idx].model = Some(model);
+            }
+            Err(err) => self.state.chat.show_toast(err, true),
+        }
+    }
+
+    /// Handle input while the search is open.
+    pub(super) fn handle_chat_search_input(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.state.chat.search else {
+            return;
+        };
+
+        if search.is_editing {
+            match key.code {
+                KeyCode::Esc => self.state.chat.search = None,
+                KeyCode::Enter => {
+                    if let Some(command) = ChatCommand::parse(search.input.value()) {
+                        self.state.chat.search = None;
+                        match command {
+                            Ok(command) => self.run_chat_command(command),
+                            Err(usage) => self.state.chat.show_toast(usage, true),
+                        }
+                        return;
+                    }
+
+                    search.query = search.input.value().trim().to_ascii_lowercase();
+                    if search.query.is_empty() {
+                        self.state.chat.search = None;
+                    } else {
+                        search.is_editing = false;
+                        search.current = 0;
+                        search.jump = true;
+                    }
+                }
+                _ => {
+                    let event = crossterm::event::Event::Key(key);
+                    search.input.handle_event(&event);
+                }
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc => self.state.chat.search = None,
+                KeyCode::Char('n') => search.next_match(),
+                KeyCode::Char('N') => search.prev_match(),
+                KeyCode::Char('/') => search.is_editing = true,
+                KeyCode::Up => self.state.chat.session_mut().scroll_up(),
+                KeyCode::Down => self.state.chat.session_mut().scroll_down(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Style, Stylize};
+
+    #[test]
+    fn test_highlight_line() {
+        let line = Line::from(vec![
+            Span::raw("Hello world, "),
+            Span::styled("hello again", Style::new().bold()),
+        ]);
+        let highlighted = highlight_line(line, "hello");
+
+        let contents: Vec<&str> = highlighted
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(contents, ["Hello", " world, ", "hello", " again"]);
+        assert_eq!(highlighted.spans[0].style, SEARCH_MATCH_STYLE);
+        assert_eq!(
+            highlighted.spans[2].style,
+            Style::new().bold().patch(SEARCH_MATCH_STYLE)
+        );
+        assert_eq!(highlighted.spans[3].style, Style::new().bold());
+    }
+
+    #[test]
+    fn test_search_offsets() {
+        let mut search = ChatSearch {
+            query: "foo".to_string(),
+            ..Default::default()
+        };
+
+        // the second line wraps into two at width 10
+        let lines = vec![
+            Line::from("foo"),
+            Line::from("bar bar bar bar"),
+            Line::from(""),
+            Line::from("a FOO b"),
+        ];
+        search.apply(lines, 10);
+        assert_eq!(search.match_offsets, vec![0, 4]);
+
+        search.current = 0;
+        search.prev_match();
+        assert_eq!(search.current, 1);
+        search.next_match();
+        assert_eq!(search.current, 0);
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            ChatCommand::parse(":bench 5"),
+            Some(Ok(ChatCommand::Bench {
+                runs: 5,
+                concurrency: 1
+            }))
+        );
+        assert_eq!(
+            ChatCommand::parse(":bench 10 2"),
+            Some(Ok(ChatCommand::Bench {
+                runs: 10,
+                concurrency: 2
+            }))
+        );
+        assert!(matches!(ChatCommand::parse(":bench 0"), Some(Err(_))));
+        assert!(matches!(ChatCommand::parse(":bench"), Some(Err(_))));
+        assert_eq!(
+            ChatCommand::parse(":bench stop"),
+            Some(Ok(ChatCommand::BenchStop))
+        );
+        assert!(matches!(
+            ChatCommand::parse(":benchmark results"),
+            Some(Err(_))
+        ));
+
+        // without the prefix, it is a search query
+        assert_eq!(ChatCommand::parse("bench 5"), None);
+        assert_eq!(ChatCommand::parse("model"), None);
+        assert_eq!(
+            ChatCommand::parse(":model"),
+            Some(Ok(ChatCommand::Model(None)))
+        );
+        assert_eq!(
+            ChatCommand::parse(":model qwen 4b"),
+            Some(Ok(ChatCommand::Model(Some("qwen 4b".to_string()))))
+        );
+        assert_eq!(ChatCommand::parse(""), None);
+    }
+
+    #[test]
+    fn test_match_model() {
+        let models = [
+            "Qwen/Qwen3-4B-MLX-4bit".to_string(),
+            "Qwen/Qwen3-4B-MLX-8bit".to_string(),
+            "mlx-community/Llama-3.2-1B-Instruct-4bit".to_string(),
+        ];
+        assert_eq!(match_model(&models, "llama"), Ok(models[2].clone()));
+        assert_eq!(match_model(&models, "8BIT"), Ok(models[1].clone()));
+        assert_eq!(
+            match_model(&models, "Qwen/Qwen3-4B-MLX-4bit"),
+            Ok(models[0].clone())
+        );
+        assert!(match_model(&models, "qwen").is_err());
+        assert!(match_model(&models, "gemma").is_err());
+    }
+}
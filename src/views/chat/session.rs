@@ -0,0 +1,290 @@
+use super::ChatMessage;
+use super::utils::{StreamEvent, ToolCall};
+use crate::common::{GenerationStats, Usage};
+use ratatui::widgets::ScrollbarState;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// A single conversation within the chat view.
+///
+/// Each session has its own message history, scroll state and stream receiver,
+/// so that a response can keep streaming while another session is being viewed.
+#[derive(Debug)]
+pub struct ChatSession {
+    /// Identifier of the session, unique across runs, which keys its autosave file.
+    pub id: String,
+    /// Display name of the session, shown in the tab bar.
+    pub name: String,
+    /// System prompt sent before the conversation, empty means none.
+    pub system_prompt: String,
+    pub messages: VecDeque<ChatMessage>,
+    /// Whether a response is currently being generated (streamed).
+    pub is_generating: bool,
+    pub current_response: String,
+    pub scroll_cur: u16,
+    /// Maximum scroll position, be careful about this as it may crash the app
+    /// if set incorrectly.
+    pub scroll_max: u16,
+    /// Whether the scroll is locked, i.e. auto-scrolls to the bottom
+    /// as new tokens are arriving. If the user scrolls manually while
+    /// generating, this is set to false.
+    pub scroll_locked: bool,
+    /// Chat message receiver for streaming responses
+    pub stream_rx: Option<mpsc::UnboundedReceiver<StreamEvent>>,
+    /// Handle to the task that streams the response, used to cancel the request.
+    pub stream_task: Option<AbortHandle>,
+    /// Scrollbar for chat messages.
+    pub scroll_bar: ScrollbarState,
+    /// Pending chat message to send
+    pub pending_chat_message: Option<String>,
+    /// Timing statistics of the last (or current) response.
+    pub stats: Option<GenerationStats>,
+    /// Token usage of the current response, attached to the message once it is finished.
+    pub usage: Option<Usage>,
+    /// Tool calls of the current response, attached to the message once it is finished.
+    pub tool_calls: Vec<ToolCall>,
+    /// Index of the user message being edited, if the last message was recalled.
+    ///
+    /// When the edited message is submitted, the conversation is truncated
+    /// from this index, dropping the old message and its reply.
+    pub editing_from: Option<usize>,
+    /// Number of messages at the last autosave, see [`super::autosave::ChatSnapshot`].
+    pub autosaved_len: usize,
+    /// Whether messages are sent as raw prompts to the completions endpoint,
+    /// without the chat template or the conversation history.
+    pub raw_mode: bool,
+    /// Max tokens override for this session, `None` uses the configured value.
+    pub max_tokens: Option<u32>,
+    /// Temperature override for this session, `None` uses the configured value.
+    pub temperature: Option<f32>,
+    /// Model of this session when several are loaded, see [`crate::App::chat_model`].
+    pub model: Option<String>,
+    /// Index of the selected message while in the message-selection mode,
+    /// `None` when not in that mode.
+    pub selected: Option<usize>,
+}
+
+impl ChatSession {
+    /// Create a new session with a welcome message.
+    pub fn new(name: impl Into<String>, system_prompt: impl Into<String>) -> Self {
+        // the time alone may collide for sessions opened within the same second
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        let id = format!(
+            "{}-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut session = ChatSession {
+            id,
+            name: name.into(),
+            system_prompt: system_prompt.into(),
+            messages: VecDeque::new(),
+            is_generating: false,
+            current_response: String::new(),
+            scroll_cur: 0,
+            scroll_max: 0,
+            scroll_locked: false,
+            scroll_bar: ScrollbarState::default(),
+            stream_rx: None,
+            stream_task: None,
+            pending_chat_message: None,
+            stats: None,
+            usage: None,
+            tool_calls: Vec::new(),
+            editing_from: None,
+            autosaved_len: 0,
+            raw_mode: false,
+            max_tokens: None,
+            temperature: None,
+            model: None,
+            selected: None,
+        };
+
+        // add welcome message
+        session.messages.push_back(ChatMessage::new_system(
+            "Welcome to dnet chat! Type your message and press Enter to send.",
+        ));
+
+        session
+    }
+
+    /// Scroll up (offset shrinks), unlocking the auto-scroll.
+    pub fn scroll_up(&mut self) {
+        if self.scroll_cur > 0 {
+            self.scroll_cur -= 1;
+            self.scroll_locked = false;
+        }
+    }
+
+    /// Scroll down (offset grows), locking the auto-scroll again
+    /// if we are back at the bottom.
+    pub fn scroll_down(&mut self) {
+        if self.scroll_cur < self.scroll_max {
+            self.scroll_cur += 1;
+            self.scroll_locked = self.scroll_cur == self.scroll_max;
+        }
+    }
+
+    /// Clear the conversation history.
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.messages.push_back(ChatMessage::new_system(
+            "Chat cleared. Start a new conversation!",
+        ));
+        self.scroll_cur = 0;
+        self.stats = None;
+        self.editing_from = None;
+        self.autosaved_len = 0;
+        self.selected = None;
+    }
+
+    /// Enter the message-selection mode, selecting the last message.
+    pub fn start_selection(&mut self) {
+        self.selected = self.messages.len().checked_sub(1);
+    }
+
+    /// Select the previous (older) message.
+    pub fn select_prev(&mut self) {
+        if let Some(idx) = &mut self.selected {
+            *idx = idx.saturating_sub(1);
+        }
+    }
+
+    /// Select the next (newer) message.
+    pub fn select_next(&mut self) {
+        if let Some(idx) = &mut self.selected {
+            *idx = (*idx + 1).min(self.messages.len().saturating_sub(1));
+        }
+    }
+
+    /// Delete the selected message along with its pair, i.e. the reply of a user
+    /// message or the prompt of an assistant message, so that neither is sent again.
+    ///
+    /// Returns the number of deleted messages.
+    pub fn delete_selected(&mut self) -> usize {
+        let Some(idx) = self.selected else {
+            return 0;
+        };
+        let role_at = |i: usize| self.messages.get(i).map(|m| m.role.as_str());
+
+        let range = match role_at(idx) {
+            Some("user") if role_at(idx + 1) == Some("assistant") => idx..idx + 2,
+            Some("assistant") if idx > 0 && role_at(idx - 1) == Some("user") => idx - 1..idx + 1,
+            Some(_) => idx..idx + 1,
+            None => return 0,
+        };
+
+        let (start, num_deleted) = (range.start, range.len());
+        self.messages.drain(range);
+        self.autosaved_len = self.autosaved_len.min(start);
+        self.editing_from = None;
+        self.selected = match self.messages.len() {
+            0 => None,
+            len => Some(start.min(len - 1)),
+        };
+        num_deleted
+    }
+
+    /// Start editing the last user message, returning its content.
+    pub fn recall_last_user_message(&mut self) -> Option<String> {
+        let idx = self.messages.iter().rposition(|m| m.role == "user")?;
+        self.editing_from = Some(idx);
+        Some(self.messages[idx].content.clone())
+    }
+
+    /// Add a user message and mark the session as waiting for a response.
+    ///
+    /// If a message was being edited, it is replaced along with everything after it.
+    pub fn submit(&mut self, user_input: String) {
+        if let Some(idx) = self.editing_from.take() {
+            self.messages.truncate(idx);
+            self.autosaved_len = self.autosaved_len.min(idx);
+        }
+        self.messages.push_back(ChatMessage::new_user(&user_input));
+
+        // set generating state
+        self.is_generating = true;
+        self.scroll_locked = true;
+        self.current_response.clear();
+        self.usage = None;
+        self.tool_calls.clear();
+        self.stats = Some(GenerationStats::new(Instant::now()));
+
+        // store the message for API call
+        self.pending_chat_message = Some(user_input);
+    }
+
+    /// Abort the generation, keeping whatever has been streamed so far.
+    pub fn abort(&mut self) {
+        if !self.current_response.is_empty() {
+            self.messages
+                .push_back(ChatMessage::new_assistant(&self.current_response));
+        }
+        self.current_response.clear();
+
+        self.messages
+            .push_back(ChatMessage::new_system("Generation aborted by user."));
+        self.is_generating = false;
+        if let Some(stats) = &mut self.stats {
+            stats.finish();
+        }
+        self.cancel_stream();
+    }
+
+    /// Cancel the streaming task (which drops the HTTP request) and clear the stream.
+    pub fn cancel_stream(&mut self) {
+        if let Some(task) = self.stream_task.take() {
+            task.abort();
+        }
+        self.stream_rx = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_ids_are_unique() {
+        let first = ChatSession::new("Chat 1", "");
+        let second = ChatSession::new("Chat 1", "");
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn test_delete_selected_pair() {
+        let mut session = ChatSession::new("Chat 1", "");
+        session.messages.push_back(ChatMessage::new_user("first"));
+        session
+            .messages
+            .push_back(ChatMessage::new_assistant("first reply"));
+        session.messages.push_back(ChatMessage::new_user("second"));
+        session
+            .messages
+            .push_back(ChatMessage::new_assistant("second reply"));
+
+        // selecting the assistant message deletes its prompt as well
+        session.selected = Some(2);
+        assert_eq!(session.delete_selected(), 2);
+        let contents: Vec<_> = session
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents[1..], ["second", "second reply"]);
+        assert_eq!(session.selected, Some(1));
+
+        // selecting the user message deletes its reply as well
+        assert_eq!(session.delete_selected(), 2);
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.selected, Some(0));
+
+        // the welcome message has no pair
+        assert_eq!(session.delete_selected(), 1);
+        assert_eq!(session.selected, None);
+    }
+}
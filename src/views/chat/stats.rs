@@ -0,0 +1,19 @@
+/// Roughly estimate the number of tokens in a text, assuming ~4 characters per token.
+///
+/// This is the usual approximation for English text with BPE tokenizers, which is
+/// good enough for a gauge without pulling in the tokenizer of each model.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("Hello, world!"), 4);
+    }
+}
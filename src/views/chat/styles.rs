@@ -1,4 +1,6 @@
+use crate::config::ChatTheme;
 use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
 
 /// [`Style`] for thinking text, dimmed & transparent-like.
 pub const THINK_STYLE: Style = Style::new()
@@ -14,3 +16,54 @@ pub const CURSOR_STYLE: Style = Style::new()
 pub const USER_STYLE: Style = Style::new().fg(Color::Green).add_modifier(Modifier::BOLD);
 
 pub const TIMESTAMP_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+pub const ACTIVE_TAB_STYLE: Style = Style::new()
+    .fg(Color::Black)
+    .bg(Color::Cyan)
+    .add_modifier(Modifier::BOLD);
+
+pub const INACTIVE_TAB_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+/// Color of the generation statistics line.
+pub const STATS_COLOR: Color = Color::Cyan;
+
+/// [`Style`] for search matches within the conversation.
+pub const SEARCH_MATCH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+/// [`Style`] for the marker of the messages truncated from the context.
+pub const TRUNCATED_STYLE: Style = Style::new()
+    .fg(Color::DarkGray)
+    .add_modifier(Modifier::ITALIC);
+
+/// [`Style`] for the marker of the selected message.
+pub const SELECTED_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+/// [`Style`] for the header of a tool call block.
+pub const TOOL_CALL_STYLE: Style = Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD);
+
+/// [`Style`] for the arguments of a tool call block.
+pub const TOOL_CALL_ARGS_STYLE: Style = Style::new().fg(Color::Magenta);
+
+/// Styles of the chat roles, with the colors of the [`ChatTheme`] on top of the defaults.
+#[derive(Debug, Clone, Copy)]
+pub struct RoleStyles {
+    pub user: Style,
+    pub assistant: Style,
+    pub think: Style,
+}
+
+impl From<&ChatTheme> for RoleStyles {
+    fn from(theme: &ChatTheme) -> Self {
+        // invalid colors (e.g. from a hand-edited config) keep the default
+        let with_color = |style: Style, color: &str| match Color::from_str(color) {
+            Ok(color) => style.fg(color),
+            Err(_) => style,
+        };
+
+        Self {
+            user: with_color(USER_STYLE, &theme.user),
+            assistant: with_color(ASSISTANT_STYLE, &theme.assistant),
+            think: with_color(THINK_STYLE, &theme.thinking),
+        }
+    }
+}
@@ -1,29 +1,28 @@
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use serde::{Deserialize, Serialize};
 
 use crate::chat::styles::CURSOR_STYLE;
+use crate::common::{ApiFunction, ApiMessage, ApiToolCall, ToolCallDelta, Usage};
 
-#[derive(Debug, Serialize)]
-pub struct ChatRequest {
-    pub model: String,
-    pub messages: Vec<ApiMessage>,
-    pub max_tokens: Option<u32>,
-    pub temperature: Option<f32>,
-    pub stream: bool,
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
-    pub timestamp: String,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    /// Token usage reported by the API, only for finished assistant messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    /// Tool (function) calls made by the assistant in this message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
 }
 
 impl ChatMessage {
-    /// Returns the current local time formatted as "HH:MM".
+    /// Returns the current local time, see [`crate::config::TimestampFormat`] for display.
     #[inline]
-    pub fn now() -> String {
-        chrono::Local::now().format("%H:%M").to_string()
+    pub fn now() -> chrono::DateTime<chrono::Local> {
+        chrono::Local::now()
     }
 
     pub fn new_user(content: &str) -> Self {
@@ -31,6 +30,8 @@ impl ChatMessage {
             role: "user".to_string(),
             content: content.to_string(),
             timestamp: Self::now(),
+            usage: None,
+            tool_calls: Vec::new(),
         }
     }
 
@@ -39,6 +40,8 @@ impl ChatMessage {
             role: "assistant".to_string(),
             content: content.to_string(),
             timestamp: Self::now(),
+            usage: None,
+            tool_calls: Vec::new(),
         }
     }
 
@@ -47,14 +50,23 @@ impl ChatMessage {
             role: "system".to_string(),
             content: content.to_string(),
             timestamp: Self::now(),
+            usage: None,
+            tool_calls: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiMessage {
-    role: String,
-    content: String,
+impl From<&ToolCall> for ApiToolCall {
+    fn from(call: &ToolCall) -> Self {
+        ApiToolCall {
+            id: call.id.clone(),
+            kind: "function".to_string(),
+            function: ApiFunction {
+                name: call.name.clone(),
+                arguments: call.arguments.clone(),
+            },
+        }
+    }
 }
 
 impl From<&ChatMessage> for ApiMessage {
@@ -62,33 +74,88 @@ impl From<&ChatMessage> for ApiMessage {
         ApiMessage {
             role: msg.role.clone(),
             content: msg.content.clone(),
+            tool_calls: msg.tool_calls.iter().map(ApiToolCall::from).collect(),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct StreamChunk {
-    id: String,
-    object: String,
-    created: u64,
-    model: String,
-    pub choices: Vec<StreamChoice>,
+/// A complete tool (function) call made by the assistant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    /// Arguments of the call, a JSON string as returned by the model.
+    pub arguments: String,
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct StreamChoice {
-    index: usize,
-    pub delta: StreamDelta,
-    pub finish_reason: Option<String>,
+impl ToolCall {
+    /// Merge the tool call deltas into the list of calls.
+    ///
+    /// A delta may only continue a call or start the next one, those with an index
+    /// further ahead are skipped so that a bad stream can not allocate without bound.
+    pub fn merge_deltas(calls: &mut Vec<ToolCall>, deltas: &[ToolCallDelta]) {
+        for delta in deltas {
+            if delta.index > calls.len() {
+                continue;
+            }
+            if delta.index == calls.len() {
+                calls.push(ToolCall::default());
+            }
+
+            let call = &mut calls[delta.index];
+            if let Some(id) = &delta.id {
+                call.id.push_str(id);
+            }
+            if let Some(function) = &delta.function {
+                if let Some(name) = &function.name {
+                    call.name.push_str(name);
+                }
+                if let Some(arguments) = &function.arguments {
+                    call.arguments.push_str(arguments);
+                }
+            }
+        }
+    }
+
+    /// Render the call as a distinct block of lines, with pretty-printed arguments if they are valid JSON.
+    pub fn to_lines(&self) -> Vec<Line<'static>> {
+        use super::{TOOL_CALL_ARGS_STYLE, TOOL_CALL_STYLE};
+
+        let arguments = serde_json::from_str::<serde_json::Value>(&self.arguments)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or_else(|_| self.arguments.clone());
+
+        let mut lines = vec![Line::styled(
+            format!("⚙ Tool call: {}", self.name),
+            TOOL_CALL_STYLE,
+        )];
+        lines.extend(
+            arguments
+                .lines()
+                .map(|line| Line::styled(format!("  {}", line), TOOL_CALL_ARGS_STYLE)),
+        );
+        lines
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct StreamDelta {
-    pub role: Option<String>,
-    pub content: Option<String>,
+/// An event of a streamed response, sent from the streaming task to the session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Content of the response, already cleaned of special tokens.
+    Token(String),
+    /// Token usage, reported with the final chunk.
+    Usage(Usage),
+    /// The stream was interrupted and is being retried, with the number of the retry.
+    Retry(u32),
+    /// Tool calls of the response, sent once they are complete.
+    ToolCalls(Vec<ToolCall>),
+    /// The response is finished.
+    Done,
+    /// The stream ended before the response was finished and could not be resumed,
+    /// the response so far is kept.
+    Interrupted,
+    /// The request failed, with the error message.
+    Error(String),
 }
 
 /// Helper function to parse text with `<think>` tags,
@@ -140,8 +207,8 @@ pub fn parse_think_tags_to_lines(
     text: &str,
     is_generating: bool,
     show_thinking: bool,
+    think_style: Style,
 ) -> Vec<Line> {
-    use super::THINK_STYLE;
     use ratatui::style::Color;
 
     let (before_think, thinking, after_think) = parse_think_tags(text);
@@ -154,7 +221,7 @@ pub fn parse_think_tags_to_lines(
     if let Some(thinking) = thinking.clone() {
         if show_thinking {
             // show full thinking content
-            lines.push(Line::styled(thinking, THINK_STYLE));
+            lines.push(Line::styled(thinking, think_style));
         } else {
             // show "Thinking..." placeholder if there is no `after_think`
             // meaning that it is still thinking
@@ -167,7 +234,7 @@ pub fn parse_think_tags_to_lines(
     if let Some(after_think) = after_think {
         if thinking.is_some() && show_thinking {
             lines.push(Line::raw(""));
-            lines.push(Line::styled("---end thinking---", THINK_STYLE));
+            lines.push(Line::styled("---end thinking---", think_style));
             lines.push(Line::raw(""))
         }
         lines.push(Line::raw(after_think));
@@ -207,4 +274,54 @@ mod tests {
         assert_eq!(thinking, None);
         assert_eq!(after.unwrap(), "Unclosed thinking...");
     }
+
+    #[test]
+    fn test_merge_tool_call_deltas() {
+        let json = r#"[
+            {"index":0,"id":"call_1","function":{"name":"get_weather","arguments":""}},
+            {"index":0,"function":{"arguments":"{\"city\":"}},
+            {"index":0,"function":{"arguments":"\"Istanbul\"}"}},
+            {"index":1,"id":"call_2","function":{"name":"get_time","arguments":"{}"}}
+        ]"#;
+        let deltas: Vec<ToolCallDelta> = serde_json::from_str(json).unwrap();
+
+        let mut calls = Vec::new();
+        ToolCall::merge_deltas(&mut calls, &deltas);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_1");
+        assert_eq!(calls[0].name, "get_weather");
+        assert_eq!(calls[0].arguments, r#"{"city":"Istanbul"}"#);
+        assert_eq!(calls[1].name, "get_time");
+
+        // without an index, the deltas belong to the first call
+        let json = r#"[{"id":"call_1","function":{"name":"get_time","arguments":"{}"}}]"#;
+        let deltas: Vec<ToolCallDelta> = serde_json::from_str(json).unwrap();
+        let mut calls = Vec::new();
+        ToolCall::merge_deltas(&mut calls, &deltas);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "get_time");
+
+        // deltas that skip ahead of the next call are dropped
+        let json = r#"[{"index":1000000,"function":{"name":"get_time"}}]"#;
+        let deltas: Vec<ToolCallDelta> = serde_json::from_str(json).unwrap();
+        ToolCall::merge_deltas(&mut calls, &deltas);
+        assert_eq!(calls.len(), 1);
+    }
+
+    #[test]
+    fn test_api_message_tool_calls() {
+        let mut message = ChatMessage::new_assistant("");
+        message.tool_calls.push(ToolCall {
+            id: "call_1".to_string(),
+            name: "get_time".to_string(),
+            arguments: "{}".to_string(),
+        });
+        let json = serde_json::to_value(ApiMessage::from(&message)).unwrap();
+        assert_eq!(json["tool_calls"][0]["type"], "function");
+        assert_eq!(json["tool_calls"][0]["function"]["name"], "get_time");
+
+        // messages without tool calls are sent as before
+        let json = serde_json::to_value(ApiMessage::system("hi")).unwrap();
+        assert!(json.get("tool_calls").is_none());
+    }
 }
@@ -0,0 +1,460 @@
+use super::DeveloperView;
+use crate::AppView;
+use crate::common::{BatchStats, bench_batch};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table},
+};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+use tui_input::backend::crossterm::EventHandler;
+
+/// Largest batch size of the cluster benchmark.
+pub const MAX_BENCH_BATCH_SIZE: u32 = 32;
+
+/// Largest number of tokens that a request of the cluster benchmark generates.
+pub const MAX_BENCH_SEQ_LEN: u32 = 8192;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BenchmarkView {
+    Configuring,
+    /// Running the cases, whose results arrive one by one.
+    Running,
+}
+
+/// Field of the benchmark form that has the focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BenchField {
+    #[default]
+    Prompt,
+    BatchSizes,
+    SeqLens,
+}
+
+/// A batch size & sequence length of the benchmark, with its result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchCase {
+    pub batch_size: u32,
+    /// Tokens that each request generates at most.
+    pub seq_len: u32,
+    pub stats: BatchStats,
+}
+
+/// Benchmark of the loaded model over several batch sizes & sequence lengths.
+#[derive(Debug)]
+pub struct ClusterBenchState {
+    pub prompt: tui_input::Input,
+    /// Batch sizes, e.g. `1,2,4`.
+    pub batch_sizes: tui_input::Input,
+    /// Sequence lengths as generated tokens, e.g. `128,512`.
+    pub seq_lens: tui_input::Input,
+    pub focus: BenchField,
+    /// Model of the latest benchmark.
+    pub model: String,
+    /// Results of the latest benchmark, in the order of the runs.
+    pub cases: Vec<BenchCase>,
+    /// Number of cases of the latest benchmark.
+    pub num_cases: usize,
+    /// Receiver of the results of the running benchmark.
+    rx: Option<mpsc::UnboundedReceiver<BenchCase>>,
+    /// Handle to the benchmark task, used to cancel it.
+    task: Option<AbortHandle>,
+}
+
+impl Default for ClusterBenchState {
+    fn default() -> Self {
+        Self {
+            prompt: tui_input::Input::new(
+                "Write a short story about a robot that learns to paint.".to_string(),
+            ),
+            batch_sizes: tui_input::Input::new("1,2,4".to_string()),
+            seq_lens: tui_input::Input::new("128,512".to_string()),
+            focus: BenchField::default(),
+            model: String::new(),
+            cases: Vec::new(),
+            num_cases: 0,
+            rx: None,
+            task: None,
+        }
+    }
+}
+
+impl ClusterBenchState {
+    /// Stop the running benchmark, if any, keeping the results so far.
+    fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.rx = None;
+    }
+}
+
+/// Parse a comma-separated list of counts between 1 and `max`, e.g. `1,2,4`.
+pub fn parse_counts(input: &str, max: u32) -> Result<Vec<u32>, String> {
+    let counts = input
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.parse::<u32>() {
+            Ok(count) if (1..=max).contains(&count) => Ok(count),
+            _ => Err(format!("{} is not between 1 and {}", part, max)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if counts.is_empty() {
+        Err("Type at least one value".to_string())
+    } else {
+        Ok(counts)
+    }
+}
+
+/// Render the results as CSV, one row per case.
+pub fn bench_results_csv(cases: &[BenchCase]) -> String {
+    let mut csv =
+        "batch_size,seq_len,ok,failed,tokens,ttft_s,tokens_per_s,elapsed_s,error\n".to_string();
+    for case in cases {
+        let stats = &case.stats;
+        let fields = [
+            case.batch_size.to_string(),
+            case.seq_len.to_string(),
+            stats.num_ok.to_string(),
+            stats.num_failed.to_string(),
+            stats.num_tokens.to_string(),
+            stats
+                .ttft
+                .map(|d| format!("{:.3}", d.as_secs_f64()))
+                .unwrap_or_default(),
+            stats
+                .tokens_per_sec()
+                .map(|t| format!("{:.2}", t))
+                .unwrap_or_default(),
+            format!("{:.3}", stats.elapsed.as_secs_f64()),
+            // quoted, as errors may contain commas
+            stats
+                .error
+                .as_ref()
+                .map(|e| format!("\"{}\"", e.replace('"', "\"\"")))
+                .unwrap_or_default(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write the results to a timestamped CSV file in the current directory,
+/// returning the path of the written file.
+pub fn export_bench_results(cases: &[BenchCase]) -> color_eyre::Result<PathBuf> {
+    let path = PathBuf::from(format!(
+        "dnet-bench-{}.csv",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, bench_results_csv(cases))?;
+    Ok(path)
+}
+
+impl crate::App {
+    pub(super) fn draw_cluster_bench(&mut self, frame: &mut Frame, view: &BenchmarkView) {
+        let area = frame.area();
+        let [
+            title_area,
+            prompt_area,
+            counts_area,
+            results_area,
+            footer_area,
+        ] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Prompt
+            Constraint::Length(3), // Batch sizes & sequence lengths
+            Constraint::Min(0),    // Results
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("Cluster Benchmark").bold().yellow().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        let state = &self.state.developer.bench;
+        let [batch_area, seq_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(counts_area);
+        for (field, input, title, input_area) in [
+            (BenchField::Prompt, &state.prompt, "Prompt", prompt_area),
+            (
+                BenchField::BatchSizes,
+                &state.batch_sizes,
+                "Batch Sizes",
+                batch_area,
+            ),
+            (
+                BenchField::SeqLens,
+                &state.seq_lens,
+                "Sequence Lengths (generated tokens)",
+                seq_area,
+            ),
+        ] {
+            let block = if state.focus == field {
+                Block::bordered()
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Cyan))
+            } else {
+                Block::bordered().title(title)
+            };
+
+            // keep 2 for borders and 1 for cursor
+            let width = input_area.width.max(3) - 3;
+            let scroll = input.visual_scroll(width as usize);
+            frame.render_widget(
+                Paragraph::new(input.value())
+                    .scroll((0, scroll as u16))
+                    .block(block),
+                input_area,
+            );
+            if state.focus == field && *view == BenchmarkView::Configuring {
+                let x = input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+            }
+        }
+
+        self.draw_bench_results(frame, results_area, view);
+
+        let footer_text = match view {
+            BenchmarkView::Configuring => {
+                "Enter: Run | Tab: Next field | Ctrl+E: Export results | Esc: Back"
+            }
+            BenchmarkView::Running => "Esc: Stop the benchmark",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    /// Draw the results table of the latest benchmark.
+    fn draw_bench_results(&self, frame: &mut Frame, area: Rect, view: &BenchmarkView) {
+        let state = &self.state.developer.bench;
+        let title = match view {
+            BenchmarkView::Running => format!(
+                " Results of {}: {}/{} cases ",
+                state.model,
+                state.cases.len(),
+                state.num_cases
+            ),
+            BenchmarkView::Configuring if state.cases.is_empty() => " Results ".to_string(),
+            BenchmarkView::Configuring => format!(" Results of {} ", state.model),
+        };
+
+        let header = Row::new(
+            [
+                "Batch", "Seq Len", "OK", "Failed", "Tokens", "TTFT (s)", "Tok/s", "Time (s)",
+            ]
+            .map(|h| Cell::from(h).bold()),
+        );
+        let rows = state.cases.iter().map(|case| {
+            let stats = &case.stats;
+            let row = Row::new([
+                case.batch_size.to_string(),
+                case.seq_len.to_string(),
+                stats.num_ok.to_string(),
+                stats.num_failed.to_string(),
+                stats.num_tokens.to_string(),
+                stats
+                    .ttft
+                    .map_or_else(|| "-".to_string(), |d| format!("{:.2}", d.as_secs_f64())),
+                stats
+                    .tokens_per_sec()
+                    .map_or_else(|| "-".to_string(), |t| format!("{:.1}", t)),
+                format!("{:.1}", stats.elapsed.as_secs_f64()),
+            ]);
+            if stats.num_failed > 0 { row.red() } else { row }
+        });
+
+        let table = Table::new(rows, [Constraint::Fill(1); 8])
+            .header(header)
+            .block(Block::bordered().title(title));
+        frame.render_widget(table, area);
+    }
+
+    pub(super) fn handle_cluster_bench_input(&mut self, key: KeyEvent, view: &BenchmarkView) {
+        let state = &mut self.state.developer.bench;
+        if *view == BenchmarkView::Running {
+            if key.code == KeyCode::Esc {
+                state.stop();
+                self.status_message = "Benchmark stopped".to_string();
+                self.view =
+                    AppView::Developer(DeveloperView::Benchmark(BenchmarkView::Configuring));
+            }
+            return;
+        }
+
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => {
+                self.status_message.clear();
+                self.view = AppView::Developer(DeveloperView::Menu);
+            }
+            (_, KeyCode::Tab | KeyCode::BackTab) => {
+                let forward = key.code == KeyCode::Tab;
+                state.focus = match (state.focus, forward) {
+                    (BenchField::Prompt, true) | (BenchField::SeqLens, false) => {
+                        BenchField::BatchSizes
+                    }
+                    (BenchField::BatchSizes, true) | (BenchField::Prompt, false) => {
+                        BenchField::SeqLens
+                    }
+                    (BenchField::SeqLens, true) | (BenchField::BatchSizes, false) => {
+                        BenchField::Prompt
+                    }
+                };
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('e')) => {
+                self.status_message = if state.cases.is_empty() {
+                    "Error: There are no results to export".to_string()
+                } else {
+                    match export_bench_results(&state.cases) {
+                        Ok(path) => format!("Exported the results to {}", path.display()),
+                        Err(err) => format!("Error: Failed to export the results: {}", err),
+                    }
+                };
+            }
+            (_, KeyCode::Enter) => {
+                self.status_message = match self.start_cluster_bench() {
+                    Ok(()) => String::new(),
+                    Err(err) => format!("Error: {}", err),
+                };
+            }
+            _ => {
+                let event = crossterm::event::Event::Key(key);
+                match state.focus {
+                    BenchField::Prompt => state.prompt.handle_event(&event),
+                    BenchField::BatchSizes => state.batch_sizes.handle_event(&event),
+                    BenchField::SeqLens => state.seq_lens.handle_event(&event),
+                };
+            }
+        }
+    }
+
+    /// Run every sequence length at every batch size in the background, one case at a time.
+    fn start_cluster_bench(&mut self) -> Result<(), String> {
+        let model = self
+            .loaded_models()
+            .into_iter()
+            .next()
+            .ok_or("No model is loaded")?;
+        let state = &mut self.state.developer.bench;
+        let prompt = state.prompt.value().trim().to_string();
+        if prompt.is_empty() {
+            return Err("Type a prompt to benchmark first".to_string());
+        }
+        let batch_sizes = parse_counts(state.batch_sizes.value(), MAX_BENCH_BATCH_SIZE)
+            .map_err(|e| format!("Batch sizes: {}", e))?;
+        let seq_lens = parse_counts(state.seq_lens.value(), MAX_BENCH_SEQ_LEN)
+            .map_err(|e| format!("Sequence lengths: {}", e))?;
+
+        let api_url = self.config.api_url();
+        let temperature = self.config.temperature;
+        let (tx, rx) = mpsc::unbounded_channel();
+        state.num_cases = batch_sizes.len() * seq_lens.len();
+        state.model = model.clone();
+        state.cases.clear();
+        let task = tokio::spawn(async move {
+            for seq_len in seq_lens {
+                for &batch_size in &batch_sizes {
+                    let stats = bench_batch(
+                        &api_url,
+                        &model,
+                        &prompt,
+                        seq_len,
+                        temperature,
+                        batch_size as usize,
+                    )
+                    .await;
+                    let case = BenchCase {
+                        batch_size,
+                        seq_len,
+                        stats,
+                    };
+                    if tx.send(case).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        state.rx = Some(rx);
+        state.task = Some(task.abort_handle());
+        self.view = AppView::Developer(DeveloperView::Benchmark(BenchmarkView::Running));
+        Ok(())
+    }
+
+    pub(super) fn tick_cluster_bench(&mut self, view: &BenchmarkView) {
+        if *view != BenchmarkView::Running {
+            return;
+        }
+
+        let state = &mut self.state.developer.bench;
+        let Some(rx) = &mut state.rx else {
+            return;
+        };
+        while let Ok(case) = rx.try_recv() {
+            state.cases.push(case);
+        }
+        if state.cases.len() >= state.num_cases {
+            state.stop();
+            self.status_message = "Benchmark finished, Ctrl+E to export the results".to_string();
+            self.view = AppView::Developer(DeveloperView::Benchmark(BenchmarkView::Configuring));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_counts() {
+        assert_eq!(parse_counts("1, 2,4,", 8), Ok(vec![1, 2, 4]));
+        assert!(parse_counts("1,16", 8).is_err());
+        assert!(parse_counts("0", 8).is_err());
+        assert!(parse_counts(" ", 8).is_err());
+    }
+
+    #[test]
+    fn test_bench_results_csv() {
+        let cases = [
+            BenchCase {
+                batch_size: 2,
+                seq_len: 128,
+                stats: BatchStats {
+                    num_ok: 2,
+                    num_failed: 0,
+                    num_tokens: 256,
+                    ttft: Some(Duration::from_millis(250)),
+                    elapsed: Duration::from_secs(4),
+                    error: None,
+                },
+            },
+            BenchCase {
+                batch_size: 4,
+                seq_len: 128,
+                stats: BatchStats {
+                    num_ok: 0,
+                    num_failed: 4,
+                    elapsed: Duration::from_millis(10),
+                    error: Some("HTTP 500, busy".to_string()),
+                    ..Default::default()
+                },
+            },
+        ];
+
+        let expected = [
+            "batch_size,seq_len,ok,failed,tokens,ttft_s,tokens_per_s,elapsed_s,error",
+            "2,128,2,0,256,0.250,64.00,4.000,",
+            "4,128,0,4,0,,,0.010,\"HTTP 500, busy\"",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(bench_results_csv(&cases), expected);
+    }
+}
@@ -0,0 +1,426 @@
+use super::DeveloperView;
+use crate::AppView;
+use crate::views::topology::{ConsoleField, REQUEST_TIMEOUT, pretty_body};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState,
+    },
+};
+use reqwest::Method;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tui_input::backend::crossterm::EventHandler;
+
+/// Number of requests that are kept in the history of the console.
+const MAX_CONSOLE_HISTORY: usize = 50;
+
+/// Methods that the console cycles through.
+const METHODS: [Method; 4] = [Method::GET, Method::POST, Method::PUT, Method::DELETE];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiConsoleView {
+    Editing,
+    /// Sending the request, whose response is added to the history.
+    Sending,
+}
+
+/// Fields of the console, in the order that the focus goes through them.
+const FIELDS: [ConsoleField; 4] = [
+    ConsoleField::Method,
+    ConsoleField::Path,
+    ConsoleField::Body,
+    ConsoleField::History,
+];
+
+/// A request sent from the console, with its response.
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    pub method: Method,
+    pub path: String,
+    /// Body as typed, empty if there was none.
+    pub body: String,
+    /// Status & pretty-printed body of the response, or why there was none.
+    pub response: Result<(u16, String), String>,
+    pub elapsed: Duration,
+}
+
+/// Console to send raw requests to the manager API, e.g. to debug new endpoints.
+#[derive(Debug, Default)]
+pub struct ApiConsoleState {
+    /// Index of the method within [`METHODS`].
+    pub method: usize,
+    pub path: tui_input::Input,
+    /// Body of the request as JSON, sent if not empty.
+    pub body: tui_input::Input,
+    pub focus: ConsoleField,
+    /// Sent requests, the latest first.
+    pub history: Vec<ConsoleEntry>,
+    /// Selected entry of the history, whose response is shown.
+    pub selected: usize,
+    /// Scroll offset of the shown response.
+    pub scroll: u16,
+    /// Receiver of the request in flight along with its response, if any.
+    rx: Option<mpsc::UnboundedReceiver<ConsoleEntry>>,
+}
+
+impl ApiConsoleState {
+    fn method(&self) -> Method {
+        METHODS[self.method % METHODS.len()].clone()
+    }
+
+    /// Put an entry of the history back into the inputs, to send it again.
+    fn recall(&mut self, entry: &ConsoleEntry) {
+        self.method = METHODS
+            .iter()
+            .position(|m| *m == entry.method)
+            .unwrap_or_default();
+        self.path = tui_input::Input::new(entry.path.clone());
+        self.body = tui_input::Input::new(entry.body.clone());
+    }
+
+    /// Add the entry as the latest, dropping the oldest beyond the limit.
+    fn push(&mut self, entry: ConsoleEntry) {
+        self.history.insert(0, entry);
+        self.history.truncate(MAX_CONSOLE_HISTORY);
+        self.selected = 0;
+        self.scroll = 0;
+    }
+}
+
+/// Path & body of a request typed into the console. The path gets a leading `/`
+/// if it has none, and the body must be JSON unless it is empty.
+pub fn parse_console_request(
+    path: &str,
+    body: &str,
+) -> Result<(String, Option<serde_json::Value>), String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Path is empty".to_string());
+    }
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+
+    let body = match body.trim() {
+        "" => None,
+        body => {
+            Some(serde_json::from_str(body).map_err(|e| format!("Body is not valid JSON: {}", e))?)
+        }
+    };
+    Ok((path, body))
+}
+
+impl crate::App {
+    pub(super) fn draw_api_console(&mut self, frame: &mut Frame, view: &ApiConsoleView) {
+        let area = frame.area();
+        let [
+            title_area,
+            request_area,
+            body_area,
+            content_area,
+            footer_area,
+        ] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Method & path
+            Constraint::Length(3), // Body
+            Constraint::Min(0),    // History & response
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("API Console").bold().yellow().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        let state = &self.state.developer.console;
+        let [method_area, path_area] =
+            Layout::horizontal([Constraint::Length(10), Constraint::Min(0)]).areas(request_area);
+        frame.render_widget(
+            Paragraph::new(state.method().to_string())
+                .bold()
+                .centered()
+                .block(field_block("Method", state.focus == ConsoleField::Method)),
+            method_area,
+        );
+        for (field, input, title, input_area) in [
+            (ConsoleField::Path, &state.path, "Path", path_area),
+            (ConsoleField::Body, &state.body, "Body (JSON)", body_area),
+        ] {
+            // keep 2 for borders and 1 for cursor
+            let width = input_area.width.max(3) - 3;
+            let scroll = input.visual_scroll(width as usize);
+            frame.render_widget(
+                Paragraph::new(input.value())
+                    .scroll((0, scroll as u16))
+                    .block(field_block(title, state.focus == field)),
+                input_area,
+            );
+            if state.focus == field && *view == ApiConsoleView::Editing {
+                let x = input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+            }
+        }
+
+        let [history_area, response_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(content_area);
+        self.draw_console_history(frame, history_area);
+        self.draw_console_response(frame, response_area, view);
+
+        let footer_text = match view {
+            ApiConsoleView::Sending => "Sending...",
+            ApiConsoleView::Editing => match self.state.developer.console.focus {
+                ConsoleField::Method => {
+                    "←→: Method | Enter: Send | Tab: Next field | PgUp/PgDn: Scroll | Esc: Back"
+                }
+                ConsoleField::History => {
+                    "↑↓: Select | Enter: Recall | c: Copy response | Tab: Next field | PgUp/PgDn: Scroll | Esc: Back"
+                }
+                _ => "Enter: Send | Tab: Next field | PgUp/PgDn: Scroll | Esc: Back",
+            },
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    /// Draw the sent requests, the latest first.
+    fn draw_console_history(&self, frame: &mut Frame, area: Rect) {
+        let state = &self.state.developer.console;
+        let items = state
+            .history
+            .iter()
+            .map(|entry| {
+                let status = match &entry.response {
+                    Ok((status, _)) if (200..300).contains(status) => status.to_string().green(),
+                    Ok((status, _)) => status.to_string().red(),
+                    Err(_) => "ERR".red(),
+                };
+                ListItem::new(Line::from(vec![
+                    status,
+                    format!(" {} {}", entry.method, entry.path).into(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+
+        let mut list_state = ListState::default().with_selected(Some(state.selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(field_block("History", state.focus == ConsoleField::History))
+                .highlight_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            area,
+            &mut list_state,
+        );
+    }
+
+    /// Draw the response of the selected entry of the history.
+    fn draw_console_response(&mut self, frame: &mut Frame, area: Rect, view: &ApiConsoleView) {
+        let state = &mut self.state.developer.console;
+        let (title, text) = match (view, state.history.get(state.selected)) {
+            (ApiConsoleView::Sending, _) => (" Response ".to_string(), "Sending...".to_string()),
+            (_, None) => (
+                " Response ".to_string(),
+                "Type a path, e.g. /v1/devices, and press Enter to send it.".to_string(),
+            ),
+            (_, Some(entry)) => match &entry.response {
+                Ok((status, body)) => (
+                    format!(" {} in {} ms ", status, entry.elapsed.as_millis()),
+                    body.clone(),
+                ),
+                Err(err) => (" Error ".to_string(), err.clone()),
+            },
+        };
+
+        let lines = text.lines().map(Line::from).collect::<Vec<_>>();
+        let max_scroll = lines
+            .len()
+            .saturating_sub(area.height.saturating_sub(2) as usize);
+        state.scroll = state.scroll.min(max_scroll as u16);
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .scroll((state.scroll, 0))
+                .block(Block::bordered().title(title)),
+            area,
+        );
+
+        let mut scroll_bar = ScrollbarState::new(max_scroll).position(state.scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            area,
+            &mut scroll_bar,
+        );
+    }
+
+    pub(super) fn handle_api_console_input(&mut self, key: KeyEvent, view: &ApiConsoleView) {
+        if *view == ApiConsoleView::Sending {
+            return;
+        }
+
+        let state = &mut self.state.developer.console;
+        match (state.focus, key.code) {
+            (_, KeyCode::Esc) => {
+                self.status_message.clear();
+                self.view = AppView::Developer(DeveloperView::Menu);
+            }
+            (_, KeyCode::Tab) => state.focus = state.focus.next(&FIELDS, true),
+            (_, KeyCode::BackTab) => state.focus = state.focus.next(&FIELDS, false),
+            (_, KeyCode::PageUp) => state.scroll = state.scroll.saturating_sub(10),
+            (_, KeyCode::PageDown) => state.scroll = state.scroll.saturating_add(10),
+            (ConsoleField::Method, KeyCode::Left) => {
+                state.method = (state.method + METHODS.len() - 1) % METHODS.len();
+            }
+            (ConsoleField::Method, KeyCode::Right) => {
+                state.method = (state.method + 1) % METHODS.len();
+            }
+            (ConsoleField::History, KeyCode::Up) => {
+                state.selected = state.selected.saturating_sub(1);
+                state.scroll = 0;
+            }
+            (ConsoleField::History, KeyCode::Down) => {
+                if state.selected + 1 < state.history.len() {
+                    state.selected += 1;
+                    state.scroll = 0;
+                }
+            }
+            (ConsoleField::History, KeyCode::Enter) => {
+                if let Some(entry) = state.history.get(state.selected).cloned() {
+                    state.recall(&entry);
+                    state.focus = ConsoleField::Path;
+                }
+            }
+            (ConsoleField::History, KeyCode::Char('c')) => {
+                let Some(Ok((_, body))) = state.history.get(state.selected).map(|e| &e.response)
+                else {
+                    return;
+                };
+                self.status_message = match crossterm::execute!(
+                    std::io::stdout(),
+                    crossterm::clipboard::CopyToClipboard::to_clipboard_from(body)
+                ) {
+                    Ok(()) => "Response copied to clipboard".to_string(),
+                    Err(e) => format!("Error: Failed to copy response: {}", e),
+                };
+            }
+            (ConsoleField::History, _) => {}
+            (_, KeyCode::Enter) => {
+                match parse_console_request(state.path.value(), state.body.value()) {
+                    Ok(_) => {
+                        self.status_message.clear();
+                        self.view =
+                            AppView::Developer(DeveloperView::ApiConsole(ApiConsoleView::Sending));
+                    }
+                    Err(err) => self.status_message = format!("Error: {}", err),
+                }
+            }
+            (ConsoleField::Path, _) => {
+                state.path.handle_event(&crossterm::event::Event::Key(key));
+            }
+            (ConsoleField::Body, _) => {
+                state.body.handle_event(&crossterm::event::Event::Key(key));
+            }
+            (ConsoleField::Method, _) => {}
+        }
+    }
+
+    pub(super) async fn tick_api_console(&mut self, view: &ApiConsoleView) {
+        if *view != ApiConsoleView::Sending {
+            return;
+        }
+
+        let state = &mut self.state.developer.console;
+        let Some(rx) = &mut state.rx else {
+            // send the request in the background, so that a hanging endpoint
+            // does not freeze the interface
+            let method = state.method();
+            let body = state.body.value().trim().to_string();
+            match parse_console_request(state.path.value(), &body) {
+                Ok((path, json)) => {
+                    let api = self.api.clone();
+                    let (tx, rx) = mpsc::unbounded_channel();
+                    tokio::spawn(async move {
+                        let started_at = Instant::now();
+                        let request = api.send_raw(method.clone(), &path, json.as_ref());
+                        let response = match tokio::time::timeout(REQUEST_TIMEOUT, request).await {
+                            Ok(Ok((status, text))) => Ok((status.as_u16(), pretty_body(&text))),
+                            Ok(Err(e)) => Err(e.to_string()),
+                            Err(_) => {
+                                Err(format!("Timed out after {}s", REQUEST_TIMEOUT.as_secs()))
+                            }
+                        };
+                        tx.send(ConsoleEntry {
+                            method,
+                            path,
+                            body,
+                            response,
+                            elapsed: started_at.elapsed(),
+                        })
+                        .ok();
+                    });
+                    state.rx = Some(rx);
+                }
+                Err(err) => {
+                    self.status_message = format!("Error: {}", err);
+                    self.view =
+                        AppView::Developer(DeveloperView::ApiConsole(ApiConsoleView::Editing));
+                }
+            }
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(entry) => state.push(entry),
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.status_message = "Error: The request was dropped".to_string();
+            }
+        }
+        state.rx = None;
+        self.view = AppView::Developer(DeveloperView::ApiConsole(ApiConsoleView::Editing));
+    }
+}
+
+/// Bordered block of a field, highlighted if it has the focus.
+fn field_block(title: &str, focused: bool) -> Block<'_> {
+    if focused {
+        Block::bordered()
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan))
+    } else {
+        Block::bordered().title(title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_console_request() {
+        assert_eq!(
+            parse_console_request("v1/devices", " "),
+            Ok(("/v1/devices".to_string(), None))
+        );
+        assert_eq!(
+            parse_console_request(" /v1/load_model ", r#"{"model": "m"}"#),
+            Ok((
+                "/v1/load_model".to_string(),
+                Some(serde_json::json!({"model": "m"}))
+            ))
+        );
+        assert!(parse_console_request("", "").is_err());
+        assert!(parse_console_request("/v1/load_model", "{model}").is_err());
+    }
+}
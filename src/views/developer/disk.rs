@@ -0,0 +1,277 @@
+use super::{DeveloperView, WeightsView};
+use crate::AppView;
+use crate::utils::format_bytes;
+use crate::views::topology::{CachedModel, ShardView};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiskView {
+    Loading,
+    Loaded,
+    Error(String),
+}
+
+/// Model weights that the shards have cached, see [`ShardView::fetch_cached_models`].
+#[derive(Debug, Default)]
+pub struct DiskState {
+    /// Cached models of each shard by instance, in the order of the instances.
+    pub shards: Vec<(String, Result<Vec<CachedModel>, String>)>,
+    /// Selected row of the models table.
+    pub selected: usize,
+}
+
+/// Disk usage of a model over the shards that cached it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDiskUsage {
+    pub model: String,
+    pub num_shards: usize,
+    pub size_bytes: u64,
+}
+
+impl DiskState {
+    /// Disk usage of each model summed over the shards, the largest first.
+    pub fn models(&self) -> Vec<ModelDiskUsage> {
+        let mut models: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+        for cached in self
+            .shards
+            .iter()
+            .filter_map(|(_, r)| r.as_ref().ok())
+            .flatten()
+        {
+            let (num_shards, size_bytes) = models.entry(&cached.model).or_default();
+            *num_shards += 1;
+            *size_bytes += cached.size_bytes;
+        }
+
+        let mut models = models
+            .into_iter()
+            .map(|(model, (num_shards, size_bytes))| ModelDiskUsage {
+                model: model.to_string(),
+                num_shards,
+                size_bytes,
+            })
+            .collect::<Vec<_>>();
+        models.sort_by_key(|usage| std::cmp::Reverse(usage.size_bytes));
+        models
+    }
+}
+
+impl crate::App {
+    pub(super) fn draw_disk(&mut self, frame: &mut Frame, view: &DiskView) {
+        let area = frame.area();
+        let [title_area, content_area, footer_area] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Content
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("Disk Usage").bold().yellow().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        match view {
+            DiskView::Loading => frame.render_widget(
+                Paragraph::new("Fetching the cached models of the shards...")
+                    .block(Block::bordered())
+                    .centered(),
+                content_area,
+            ),
+            DiskView::Loaded => {
+                let [shards_area, models_area] =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(content_area);
+                self.draw_disk_shards(frame, shards_area);
+                self.draw_disk_models(frame, models_area);
+            }
+            DiskView::Error(err) => frame.render_widget(
+                Paragraph::new(format!("Error: {}", err))
+                    .block(Block::bordered())
+                    .style(Style::default().fg(Color::Red))
+                    .centered(),
+                content_area,
+            ),
+        }
+
+        let footer_text = match view {
+            DiskView::Loaded => "↑↓: Select model | d: Delete its weights | r: Refresh | Esc: Back",
+            DiskView::Loading => "Please wait...",
+            DiskView::Error(_) => "r: Retry | Esc: Back",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    /// Draw the cached models of each shard.
+    fn draw_disk_shards(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        for (instance, result) in &self.state.developer.disk.shards {
+            let name = self.config.device_name(instance).to_string();
+            match result {
+                Ok(models) => {
+                    let total = models.iter().map(|m| m.size_bytes).sum();
+                    lines.push(Line::from(vec![
+                        name.bold(),
+                        format!("  {}", format_bytes(total)).cyan(),
+                    ]));
+                    if models.is_empty() {
+                        lines.push(Line::from("  no cached models").dark_gray());
+                    }
+                    for cached in models {
+                        lines.push(Line::from(format!(
+                            "  {:<48} {}",
+                            cached.model,
+                            format_bytes(cached.size_bytes)
+                        )));
+                    }
+                }
+                Err(err) => {
+                    lines.push(Line::from(name).bold());
+                    lines.push(Line::from(format!("  {}", err)).red());
+                }
+            }
+            lines.push(Line::from(""));
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Shards")),
+            area,
+        );
+    }
+
+    /// Draw the disk usage of each model over the shards.
+    fn draw_disk_models(&self, frame: &mut Frame, area: Rect) {
+        let models = self.state.developer.disk.models();
+        let rows = models.iter().map(|usage| {
+            Row::new(vec![
+                Cell::from(usage.model.clone()),
+                Cell::from(usage.num_shards.to_string()),
+                Cell::from(format_bytes(usage.size_bytes)),
+            ])
+        });
+        let total = models.iter().map(|usage| usage.size_bytes).sum();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(6),
+                Constraint::Length(12),
+            ],
+        )
+        .header(Row::new(vec!["Model", "Shards", "Size"]).bold())
+        .block(Block::bordered().title(format!("Models ({} in total)", format_bytes(total))))
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let selected = self.state.developer.disk.selected;
+        let mut state =
+            TableState::default().with_selected((!models.is_empty()).then_some(selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    pub(super) fn handle_disk_input(&mut self, key: KeyEvent, view: &DiskView) {
+        let num_models = self.state.developer.disk.models().len();
+        let state = &mut self.state.developer.disk;
+        match key.code {
+            KeyCode::Esc => self.view = AppView::Developer(DeveloperView::Menu),
+            KeyCode::Char('r') if *view != DiskView::Loading => {
+                self.view = AppView::Developer(DeveloperView::Disk(DiskView::Loading));
+            }
+            KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+            KeyCode::Down => {
+                state.selected = (state.selected + 1).min(num_models.saturating_sub(1))
+            }
+            KeyCode::Char('d') if *view == DiskView::Loaded => {
+                // continue with the deletion of the weights of the selected model
+                if let Some(usage) = state.models().get(state.selected) {
+                    self.state.developer.weights.model = usage.model.clone();
+                    self.view =
+                        AppView::Developer(DeveloperView::Weights(WeightsView::FetchingShards));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) async fn tick_disk(&mut self, view: &DiskView) {
+        if *view != DiskView::Loading {
+            return;
+        }
+
+        let devices = match self.api.get_devices().await {
+            Ok(devices) => devices,
+            Err(err) => {
+                self.view =
+                    AppView::Developer(DeveloperView::Disk(DiskView::Error(err.to_string())));
+                return;
+            }
+        };
+        let mut shards = devices
+            .into_values()
+            .filter(|d| !d.is_manager)
+            .collect::<Vec<_>>();
+        shards.sort_by(|a, b| a.instance.cmp(&b.instance));
+
+        let fetches = shards.into_iter().map(|device| async move {
+            let models = ShardView::fetch_cached_models(&device.local_ip, device.server_port).await;
+            (device.instance, models)
+        });
+        self.state.developer.disk.shards = futures::future::join_all(fetches).await;
+        self.view = AppView::Developer(DeveloperView::Disk(DiskView::Loaded));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_models() {
+        let cached = |model: &str, size_bytes| CachedModel {
+            model: model.to_string(),
+            size_bytes,
+        };
+        let state = DiskState {
+            shards: vec![
+                (
+                    "a".to_string(),
+                    Ok(vec![cached("m1", 10), cached("m2", 50)]),
+                ),
+                ("b".to_string(), Ok(vec![cached("m1", 30)])),
+                ("c".to_string(), Err("unreachable".to_string())),
+            ],
+            selected: 0,
+        };
+        assert_eq!(
+            state.models(),
+            vec![
+                ModelDiskUsage {
+                    model: "m2".to_string(),
+                    num_shards: 1,
+                    size_bytes: 50
+                },
+                ModelDiskUsage {
+                    model: "m1".to_string(),
+                    num_shards: 2,
+                    size_bytes: 40
+                },
+            ]
+        );
+
+        // sizes are optional
+        let list: Vec<CachedModel> =
+            serde_json::from_str(r#"[{"model": "m1", "size_bytes": 10}, {"model": "m2"}]"#)
+                .unwrap();
+        assert_eq!(list[1].size_bytes, 0);
+    }
+}
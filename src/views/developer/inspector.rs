@@ -0,0 +1,427 @@
+use super::DeveloperView;
+use crate::AppView;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+use reqwest::Method;
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InspectorView {
+    Loading,
+    Loaded,
+    Error(String),
+}
+
+/// Endpoint whose raw payload is inspected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InspectorSource {
+    #[default]
+    Topology,
+    Devices,
+}
+
+impl InspectorSource {
+    pub fn path(&self) -> &'static str {
+        match self {
+            InspectorSource::Topology => "/v1/topology",
+            InspectorSource::Devices => "/v1/devices",
+        }
+    }
+
+    fn toggle(self) -> Self {
+        match self {
+            InspectorSource::Topology => InspectorSource::Devices,
+            InspectorSource::Devices => InspectorSource::Topology,
+        }
+    }
+}
+
+/// Raw payloads of the API, for the fields that the typed structs drop.
+#[derive(Debug, Default)]
+pub struct InspectorState {
+    pub source: InspectorSource,
+    pub payload: Option<Value>,
+    /// Paths of the folded objects & arrays, see [`JsonRow::path`].
+    pub folded: HashSet<String>,
+    /// Selected row of the tree.
+    pub selected: usize,
+    /// Searched text, rows that contain it are highlighted.
+    pub query: String,
+    /// Whether the search query is being typed.
+    pub is_searching: bool,
+}
+
+/// A row of the tree of a JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRow {
+    /// JSON pointer of the value, e.g. `/assignments/0/instance`.
+    pub path: String,
+    pub depth: usize,
+    /// Key (or index) & the value, or the size of an object or array.
+    pub text: String,
+    /// Whether the value is an object or array, which can be folded.
+    pub foldable: bool,
+}
+
+/// Rows of the tree of the value, skipping the children of the folded paths.
+pub fn json_rows(value: &Value, folded: &HashSet<String>) -> Vec<JsonRow> {
+    fn push_rows(
+        rows: &mut Vec<JsonRow>,
+        key: &str,
+        path: String,
+        depth: usize,
+        value: &Value,
+        folded: &HashSet<String>,
+    ) {
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v))
+                .collect(),
+            _ => {
+                rows.push(JsonRow {
+                    text: format!("{}: {}", key, value),
+                    path,
+                    depth,
+                    foldable: false,
+                });
+                return;
+            }
+        };
+
+        let (open, close) = if value.is_array() {
+            ('[', ']')
+        } else {
+            ('{', '}')
+        };
+        let is_folded = folded.contains(&path);
+        rows.push(JsonRow {
+            text: format!(
+                "{} {}: {}{}{}",
+                if is_folded { "▸" } else { "▾" },
+                key,
+                open,
+                children.len(),
+                close
+            ),
+            path: path.clone(),
+            depth,
+            foldable: true,
+        });
+        if !is_folded {
+            for (child_key, child) in children {
+                let child_path = format!(
+                    "{}/{}",
+                    path,
+                    child_key.replace('~', "~0").replace('/', "~1")
+                );
+                push_rows(rows, &child_key, child_path, depth + 1, child, folded);
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    push_rows(&mut rows, "$", String::new(), 0, value, folded);
+    rows
+}
+
+/// Index of the next row (or the previous one if not `forward`) after `from` that
+/// contains the query, case-insensitive and wrapping around.
+pub fn find_row(rows: &[JsonRow], query: &str, from: usize, forward: bool) -> Option<usize> {
+    if query.is_empty() || rows.is_empty() {
+        return None;
+    }
+    let query = query.to_lowercase();
+    let len = rows.len();
+    (1..=len)
+        .map(|step| {
+            if forward {
+                (from + step) % len
+            } else {
+                (from + len - step % len) % len
+            }
+        })
+        .find(|i| rows[*i].text.to_lowercase().contains(&query))
+}
+
+impl InspectorState {
+    fn rows(&self) -> Vec<JsonRow> {
+        self.payload
+            .as_ref()
+            .map(|payload| json_rows(payload, &self.folded))
+            .unwrap_or_default()
+    }
+}
+
+impl crate::App {
+    pub(super) fn draw_inspector(&mut self, frame: &mut Frame, view: &InspectorView) {
+        let area = frame.area();
+        let [title_area, content_area, search_area, footer_area] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Content
+            Constraint::Length(1), // Search
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let state = &self.state.developer.inspector;
+        let title = Line::from(format!("JSON Inspector: GET {}", state.source.path()))
+            .bold()
+            .yellow()
+            .centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        match view {
+            InspectorView::Loading => frame.render_widget(
+                Paragraph::new("Fetching the payload...")
+                    .block(Block::bordered())
+                    .centered(),
+                content_area,
+            ),
+            InspectorView::Loaded => {
+                let query = state.query.to_lowercase();
+                let items = state
+                    .rows()
+                    .into_iter()
+                    .map(|row| {
+                        let indent = "  ".repeat(row.depth);
+                        let text = if !query.is_empty() && row.text.to_lowercase().contains(&query)
+                        {
+                            Span::from(row.text).black().on_yellow()
+                        } else if row.foldable {
+                            Span::from(row.text).cyan()
+                        } else {
+                            Span::from(row.text)
+                        };
+                        ListItem::new(Line::from(vec![indent.into(), text]))
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut list_state = ListState::default().with_selected(Some(state.selected));
+                frame.render_stateful_widget(
+                    List::new(items)
+                        .block(Block::bordered())
+                        .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+                    content_area,
+                    &mut list_state,
+                );
+            }
+            InspectorView::Error(err) => frame.render_widget(
+                Paragraph::new(format!("Error: {}", err))
+                    .block(Block::bordered())
+                    .style(Style::default().fg(Color::Red))
+                    .centered(),
+                content_area,
+            ),
+        }
+
+        if state.is_searching || !state.query.is_empty() {
+            let cursor = if state.is_searching { "_" } else { "" };
+            frame.render_widget(
+                Paragraph::new(format!("/{}{}", state.query, cursor)).yellow(),
+                search_area,
+            );
+        }
+
+        let footer_text = match view {
+            InspectorView::Loaded if state.is_searching => {
+                "Type to search | Enter: Find | Esc: Clear search"
+            }
+            InspectorView::Loaded => {
+                "↑↓: Navigate | Enter/←→: Fold/Unfold | /: Search | n/N: Next/Previous match | Tab: Topology/Devices | c: Copy | r: Refresh | Esc: Back"
+            }
+            InspectorView::Loading => "Please wait...",
+            InspectorView::Error(_) => "Tab: Topology/Devices | r: Retry | Esc: Back",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    pub(super) fn handle_inspector_input(&mut self, key: KeyEvent, view: &InspectorView) {
+        let state = &mut self.state.developer.inspector;
+        if state.is_searching {
+            match key.code {
+                KeyCode::Esc => {
+                    state.is_searching = false;
+                    state.query.clear();
+                }
+                KeyCode::Enter => {
+                    state.is_searching = false;
+                    let rows = state.rows();
+                    // the selected row may match already
+                    let from = state.selected + rows.len().max(1) - 1;
+                    if let Some(i) = find_row(&rows, &state.query, from, true) {
+                        state.selected = i;
+                    }
+                }
+                KeyCode::Backspace => {
+                    state.query.pop();
+                }
+                KeyCode::Char(c) => state.query.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => {
+                self.view = AppView::Developer(DeveloperView::Menu);
+            }
+            KeyCode::Tab => {
+                state.source = state.source.toggle();
+                state.payload = None;
+                self.view = AppView::Developer(DeveloperView::Inspector(InspectorView::Loading));
+            }
+            KeyCode::Char('r') => {
+                self.view = AppView::Developer(DeveloperView::Inspector(InspectorView::Loading));
+            }
+            _ if *view != InspectorView::Loaded => {}
+            KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+            KeyCode::Down => {
+                state.selected = (state.selected + 1).min(state.rows().len().saturating_sub(1));
+            }
+            KeyCode::PageUp => state.selected = state.selected.saturating_sub(10),
+            KeyCode::PageDown => {
+                state.selected = (state.selected + 10).min(state.rows().len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right => {
+                let rows = state.rows();
+                let Some(row) = rows.get(state.selected) else {
+                    return;
+                };
+                if row.foldable {
+                    let fold = match key.code {
+                        KeyCode::Left => true,
+                        KeyCode::Right => false,
+                        _ => !state.folded.contains(&row.path),
+                    };
+                    if fold {
+                        state.folded.insert(row.path.clone());
+                    } else {
+                        state.folded.remove(&row.path);
+                    }
+                } else if key.code == KeyCode::Left {
+                    // go to the parent of a value
+                    if let Some(parent) = rows[..state.selected]
+                        .iter()
+                        .rposition(|r| r.depth < row.depth)
+                    {
+                        state.selected = parent;
+                    }
+                }
+            }
+            KeyCode::Char('/') => {
+                state.is_searching = true;
+                state.query.clear();
+            }
+            KeyCode::Char(c @ ('n' | 'N')) => {
+                let rows = state.rows();
+                match find_row(&rows, &state.query, state.selected, c == 'n') {
+                    Some(i) => state.selected = i,
+                    None if !state.query.is_empty() => {
+                        self.status_message = format!("No match for \"{}\"", state.query);
+                    }
+                    None => {}
+                }
+            }
+            KeyCode::Char('c') => {
+                let Some(payload) = &state.payload else {
+                    return;
+                };
+                let json = serde_json::to_string_pretty(payload).unwrap_or_default();
+                self.status_message = match crossterm::execute!(
+                    std::io::stdout(),
+                    crossterm::clipboard::CopyToClipboard::to_clipboard_from(json)
+                ) {
+                    Ok(()) => "Payload copied to clipboard".to_string(),
+                    Err(e) => format!("Error: Failed to copy payload: {}", e),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) async fn tick_inspector(&mut self, view: &InspectorView) {
+        if *view != InspectorView::Loading {
+            return;
+        }
+
+        let path = self.state.developer.inspector.source.path();
+        let result = match self.api.send_raw(Method::GET, path, None).await {
+            Ok((status, body)) if status.is_success() => serde_json::from_str::<Value>(&body)
+                .map_err(|e| format!("Response is not JSON: {}", e)),
+            Ok((status, body)) => Err(format!("({}) {}", status, body)),
+            Err(err) => Err(err.to_string()),
+        };
+
+        let state = &mut self.state.developer.inspector;
+        self.view = AppView::Developer(DeveloperView::Inspector(match result {
+            Ok(payload) => {
+                // keep the folds & selection when refreshing the same payload
+                if state.payload.is_none() {
+                    state.folded.clear();
+                    state.selected = 0;
+                }
+                state.payload = Some(payload);
+                state.selected = state.selected.min(state.rows().len().saturating_sub(1));
+                InspectorView::Loaded
+            }
+            Err(err) => {
+                state.payload = None;
+                InspectorView::Error(err)
+            }
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_rows() {
+        let value = json!({
+            "model": "m",
+            "assignments": [{"instance": "a/b", "layers": [[0, 1]]}],
+        });
+
+        let rows = json_rows(&value, &HashSet::new());
+        let texts = rows.iter().map(|r| r.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "▾ $: {2}",
+                "▾ assignments: [1]",
+                "▾ 0: {2}",
+                "instance: \"a/b\"",
+                "▾ layers: [1]",
+                "▾ 0: [2]",
+                "0: 0",
+                "1: 1",
+                "model: \"m\"",
+            ]
+        );
+        assert_eq!(rows[3].path, "/assignments/0/instance");
+        assert_eq!(rows[3].depth, 3);
+
+        let folded = HashSet::from(["/assignments/0".to_string()]);
+        let rows = json_rows(&value, &folded);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[2].text, "▸ 0: {2}");
+
+        assert_eq!(find_row(&rows, "MODEL", 0, true), Some(3));
+        assert_eq!(find_row(&rows, "assign", 0, false), Some(1));
+        assert_eq!(find_row(&rows, "assign", 1, true), Some(1));
+        assert_eq!(find_row(&rows, "missing", 0, true), None);
+        assert_eq!(find_row(&rows, "", 0, true), None);
+    }
+}
@@ -0,0 +1,224 @@
+use super::DeveloperView;
+use crate::AppView;
+use crate::common::DeviceProperties;
+use crate::views::topology::ShardView;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+};
+use std::collections::HashMap;
+
+/// Log levels that the shards accept, by their names in Python's `logging`.
+pub const LOG_LEVELS: [&str; 4] = ["DEBUG", "INFO", "WARNING", "ERROR"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogLevelView {
+    Loading,
+    Loaded,
+    /// Setting the chosen level on the selected shard, or on all of them.
+    Applying,
+    Error(String),
+}
+
+/// Log levels of the shards, set remotely via their admin endpoint.
+#[derive(Debug)]
+pub struct LogLevelState {
+    /// Shards in the order of their instances.
+    pub shards: Vec<DeviceProperties>,
+    /// Selected row, where the first row stands for all shards.
+    pub selected: usize,
+    /// Index of the chosen level within [`LOG_LEVELS`].
+    pub level: usize,
+    /// Outcome of the latest change of each shard, the level that was set or why not.
+    pub results: HashMap<String, Result<&'static str, String>>,
+}
+
+impl Default for LogLevelState {
+    fn default() -> Self {
+        Self {
+            shards: Vec::new(),
+            selected: 0,
+            // INFO
+            level: 1,
+            results: HashMap::new(),
+        }
+    }
+}
+
+impl LogLevelState {
+    /// Shards that the chosen level is applied to, per the selected row.
+    fn targets(&self) -> Vec<DeviceProperties> {
+        match self.selected {
+            0 => self.shards.clone(),
+            i => self.shards.get(i - 1).cloned().into_iter().collect(),
+        }
+    }
+}
+
+impl crate::App {
+    pub(super) fn draw_log_levels(&mut self, frame: &mut Frame, view: &LogLevelView) {
+        let area = frame.area();
+        let [title_area, level_area, content_area, footer_area] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Level
+            Constraint::Min(0),    // Shards
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("Shard Log Levels").bold().yellow().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        let state = &self.state.developer.logs;
+        let levels = LOG_LEVELS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, level)| {
+                let span = if i == state.level {
+                    format!(" {} ", level).black().on_cyan().bold()
+                } else {
+                    format!(" {} ", level).into()
+                };
+                [span, "  ".into()]
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(Line::from(levels))
+                .centered()
+                .block(Block::bordered().title("Level")),
+            level_area,
+        );
+
+        match view {
+            LogLevelView::Loading => frame.render_widget(
+                Paragraph::new("Fetching the shards...")
+                    .block(Block::bordered())
+                    .centered(),
+                content_area,
+            ),
+            LogLevelView::Loaded | LogLevelView::Applying => {
+                let result_cell = |instance: &str| match state.results.get(instance) {
+                    Some(Ok(level)) => Cell::from(format!("set to {}", level)).green(),
+                    Some(Err(err)) => Cell::from(err.clone()).red(),
+                    None => Cell::from("-").dark_gray(),
+                };
+                let mut rows = vec![Row::new(vec![
+                    Cell::from(format!("All shards ({})", state.shards.len())).bold(),
+                    Cell::from(""),
+                    Cell::from(""),
+                ])];
+                rows.extend(state.shards.iter().map(|device| {
+                    Row::new(vec![
+                        Cell::from(self.config.device_name(&device.instance).to_string()),
+                        Cell::from(format!("{}:{}", device.local_ip, device.server_port)),
+                        result_cell(&device.instance),
+                    ])
+                }));
+
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(25),
+                        Constraint::Percentage(45),
+                    ],
+                )
+                .header(Row::new(vec!["Shard", "Address", "Latest Change"]).bold())
+                .block(Block::bordered())
+                .row_highlight_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                );
+                let mut table_state = TableState::default().with_selected(Some(state.selected));
+                frame.render_stateful_widget(table, content_area, &mut table_state);
+            }
+            LogLevelView::Error(err) => frame.render_widget(
+                Paragraph::new(format!("Error: {}", err))
+                    .block(Block::bordered())
+                    .style(Style::default().fg(Color::Red))
+                    .centered(),
+                content_area,
+            ),
+        }
+
+        let footer_text = match view {
+            LogLevelView::Loaded => {
+                "↑↓: Select shard | ←→: Level | Enter: Apply | r: Refresh | Esc: Back"
+            }
+            LogLevelView::Loading | LogLevelView::Applying => "Please wait...",
+            LogLevelView::Error(_) => "r: Retry | Esc: Back",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    pub(super) fn handle_log_levels_input(&mut self, key: KeyEvent, view: &LogLevelView) {
+        let state = &mut self.state.developer.logs;
+        match key.code {
+            KeyCode::Esc => self.view = AppView::Developer(DeveloperView::Menu),
+            _ if matches!(view, LogLevelView::Loading | LogLevelView::Applying) => {}
+            KeyCode::Char('r') => {
+                self.view = AppView::Developer(DeveloperView::LogLevels(LogLevelView::Loading));
+            }
+            _ if *view != LogLevelView::Loaded => {}
+            KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+            KeyCode::Down => state.selected = (state.selected + 1).min(state.shards.len()),
+            KeyCode::Left => state.level = state.level.saturating_sub(1),
+            KeyCode::Right => state.level = (state.level + 1).min(LOG_LEVELS.len() - 1),
+            KeyCode::Enter if !state.shards.is_empty() => {
+                self.view = AppView::Developer(DeveloperView::LogLevels(LogLevelView::Applying));
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) async fn tick_log_levels(&mut self, view: &LogLevelView) {
+        match view {
+            LogLevelView::Loading => {
+                let devices = match self.api.get_devices().await {
+                    Ok(devices) => devices,
+                    Err(err) => {
+                        self.view = AppView::Developer(DeveloperView::LogLevels(
+                            LogLevelView::Error(err.to_string()),
+                        ));
+                        return;
+                    }
+                };
+                let mut shards = devices
+                    .into_values()
+                    .filter(|d| !d.is_manager)
+                    .collect::<Vec<_>>();
+                shards.sort_by(|a, b| a.instance.cmp(&b.instance));
+
+                let state = &mut self.state.developer.logs;
+                state.selected = state.selected.min(shards.len());
+                state.shards = shards;
+                self.view = AppView::Developer(DeveloperView::LogLevels(LogLevelView::Loaded));
+            }
+            LogLevelView::Applying => {
+                let state = &self.state.developer.logs;
+                let level = LOG_LEVELS[state.level];
+                let requests = state.targets().into_iter().map(|device| async move {
+                    let result =
+                        ShardView::set_log_level(&device.local_ip, device.server_port, level).await;
+                    (device.instance, result.map(|()| level))
+                });
+                let results = futures::future::join_all(requests).await;
+
+                let num_failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                self.status_message = match num_failed {
+                    0 => format!("Log level set to {}", level),
+                    n => format!("Error: Failed to set the log level of {} shard(s)", n),
+                };
+                self.state.developer.logs.results.extend(results);
+                self.view = AppView::Developer(DeveloperView::LogLevels(LogLevelView::Loaded));
+            }
+            LogLevelView::Loaded | LogLevelView::Error(_) => {}
+        }
+    }
+}
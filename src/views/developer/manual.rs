@@ -1,11 +1,14 @@
 use super::DeveloperView;
 use super::utils::{
-    determine_next_instances, find_missing_layers, format_layers, parse_layer_input,
+    EditHistory, distribute_layers, distribute_layers_by_weight, find_missing_layers,
+    format_layers, format_rounds, parse_layer_input, parse_rounds_input, parse_size_input,
+    ring_next_instances, validate_ring,
 };
 use crate::AppView;
-use crate::common::{AssignmentInfo, DeviceProperties, ShardHealth};
+use crate::common::{AssignmentInfo, DeviceProperties, ShardHealth, Topologies, TopologyInfo};
 use crate::config::{Config, KVBits};
-use crate::utils::ModelConfig;
+use crate::utils::{ModelConfig, centered_rect, format_bytes};
+use crate::views::topology::LatencyProbe;
 use color_eyre::eyre::OptionExt;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -13,38 +16,96 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::Line,
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
+    },
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ManualAssignmentView {
     SelectingModel,
     FetchingShards(String /* model name */),
+    /// Fetching the active topology to edit its assignments.
+    FetchingTopology,
     AssigningLayers,
+    /// Reviewing the request before submitting it.
+    Reviewing,
     Submitting,
     LoadingModel(String /* model name */),
     Success,
     Error(String),
 }
 
+/// What is being typed into the input popup.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ManualInput {
+    #[default]
+    Layers,
+    /// Layers of another round of the shard.
+    Round,
+    Window,
+    Residency,
+}
+
+impl ManualInput {
+    /// Name of the typed size, for the popup & errors.
+    fn label(&self) -> &'static str {
+        match self {
+            ManualInput::Layers => "Layers",
+            ManualInput::Round => "Round",
+            ManualInput::Window => "Window size",
+            ManualInput::Residency => "Residency size",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColumnSelection {
     Unassigned,
     Assigned,
 }
 
+/// Snapshot of the edited parts of [`ManualAssignmentState`], to undo & redo the edits.
+#[derive(Debug, Clone, PartialEq)]
+struct AssignmentEdit {
+    assignments: HashMap<String, Vec<Vec<u32>>>,
+    next_overrides: HashMap<String, String>,
+    windows: HashMap<String, u32>,
+    residencies: HashMap<String, u32>,
+}
+
 #[derive(Debug)]
 pub struct ManualAssignmentState {
     model: String,
     num_layers: u32,
     shards: Vec<ShardInfo>,
-    assignments: HashMap<String /* shard */, Vec<u32> /* layers */>,
+    /// Layers of the shards per round, i.e. the disjoint ranges that a shard
+    /// processes in each pass over the ring, one round unless more are added.
+    assignments: HashMap<String /* shard */, Vec<Vec<u32>> /* layers per round */>,
+    /// Next shards of the ring that are set explicitly, otherwise they follow the layers.
+    next_overrides: HashMap<String /* shard */, String /* next shard */>,
+    /// Window sizes that are set explicitly, otherwise they are the number of layers.
+    windows: HashMap<String /* shard */, u32>,
+    /// Residency sizes that are set explicitly, otherwise they are the window sizes.
+    residencies: HashMap<String /* shard */, u32>,
     selected_column: ColumnSelection,
     selected_unassigned_index: usize,
     selected_assigned_index: usize,
     is_typing: bool,
+    input: ManualInput,
+    /// Scroll offset of the reviewed request.
+    review_scroll: u16,
+    /// Instances of the excluded devices, see [`Config::excluded_devices`].
+    excluded: BTreeSet<String>,
+    /// Whether the excluded devices are listed, they are hidden by default.
+    show_excluded: bool,
+    /// Probes of the shards, for the memory of their hosts.
+    probe: LatencyProbe,
+    /// Edits of the assignments, bound to Ctrl+Z & Ctrl+Y.
+    history: EditHistory<AssignmentEdit>,
 }
 
 impl Default for ManualAssignmentState {
@@ -54,14 +115,285 @@ impl Default for ManualAssignmentState {
             num_layers: 0,
             shards: Vec::new(),
             assignments: HashMap::new(),
+            next_overrides: HashMap::new(),
+            windows: HashMap::new(),
+            residencies: HashMap::new(),
             selected_column: ColumnSelection::Unassigned,
             selected_unassigned_index: 0,
             selected_assigned_index: 0,
             is_typing: false,
+            input: ManualInput::default(),
+            review_scroll: 0,
+            excluded: BTreeSet::new(),
+            show_excluded: false,
+            probe: LatencyProbe::default(),
+            history: EditHistory::default(),
         }
     }
 }
 
+impl ManualAssignmentState {
+    /// Snapshot of the edited parts of the state.
+    fn edit(&self) -> AssignmentEdit {
+        AssignmentEdit {
+            assignments: self.assignments.clone(),
+            next_overrides: self.next_overrides.clone(),
+            windows: self.windows.clone(),
+            residencies: self.residencies.clone(),
+        }
+    }
+
+    /// Restore a snapshot of the edited parts, keeping the selection within the shards.
+    fn restore(&mut self, edit: AssignmentEdit) {
+        self.assignments = edit.assignments;
+        self.next_overrides = edit.next_overrides;
+        self.windows = edit.windows;
+        self.residencies = edit.residencies;
+
+        let (unassigned, assigned) = partition_shards(self);
+        let (num_unassigned, num_assigned) = (unassigned.len(), assigned.len());
+        self.selected_column = match self.selected_column {
+            ColumnSelection::Assigned if num_assigned == 0 => ColumnSelection::Unassigned,
+            ColumnSelection::Unassigned if num_unassigned == 0 && num_assigned > 0 => {
+                ColumnSelection::Assigned
+            }
+            column => column,
+        };
+        self.selected_unassigned_index = self
+            .selected_unassigned_index
+            .min(num_unassigned.saturating_sub(1));
+        self.selected_assigned_index = self
+            .selected_assigned_index
+            .min(num_assigned.saturating_sub(1));
+    }
+
+    /// Record an edit in the history, unless nothing changed since `before`.
+    fn record_edit(&mut self, before: AssignmentEdit) {
+        if self.edit() != before {
+            self.history.record(before);
+        }
+    }
+
+    /// Undo the latest edit, returns whether there was one.
+    fn undo(&mut self) -> bool {
+        match self.history.undo(self.edit()) {
+            Some(before) => {
+                self.restore(before);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the latest undone edit, returns whether there was one.
+    fn redo(&mut self) -> bool {
+        match self.history.redo(self.edit()) {
+            Some(after) => {
+                self.restore(after);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Probe the shards for the memory of their hosts, see [`AutoAssign::ByMemory`].
+    pub(super) fn probe_shards(&mut self) {
+        self.probe
+            .start(self.shards.iter().map(|shard| &shard.device));
+    }
+
+    /// Total memory of the host of the shard in bytes, if it has been probed.
+    fn memory_total(&self, instance: &str) -> Option<u64> {
+        self.probe
+            .get_resources(instance)
+            .and_then(|resources| resources.memory_total)
+    }
+
+    /// All layers of the shard, over all rounds.
+    fn layers(&self, instance: &str) -> Vec<u32> {
+        self.assignments
+            .get(instance)
+            .map(|rounds| rounds.concat())
+            .unwrap_or_default()
+    }
+
+    /// All layers of each shard, over all rounds.
+    fn flat_assignments(&self) -> HashMap<String, Vec<u32>> {
+        self.assignments
+            .iter()
+            .map(|(instance, rounds)| (instance.clone(), rounds.concat()))
+            .collect()
+    }
+
+    /// All assigned layers, of all shards.
+    fn assigned_layers(&self) -> HashSet<u32> {
+        self.assignments
+            .values()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Number of layers of the largest round of the shard.
+    fn largest_round(&self, instance: &str) -> u32 {
+        self.assignments
+            .get(instance)
+            .and_then(|rounds| rounds.iter().map(Vec::len).max())
+            .unwrap_or_default() as u32
+    }
+
+    /// Window size of the shard, the number of layers of its largest round unless set to fewer.
+    fn window_size(&self, instance: &str) -> u32 {
+        let num_layers = self.largest_round(instance);
+        self.windows
+            .get(instance)
+            .copied()
+            .filter(|window| *window <= num_layers)
+            .unwrap_or(num_layers)
+    }
+
+    /// Residency size of the shard, its window size unless set otherwise.
+    fn residency_size(&self, instance: &str) -> u32 {
+        let num_layers = self.layers(instance).len() as u32;
+        self.residencies
+            .get(instance)
+            .copied()
+            .filter(|residency| *residency <= num_layers)
+            .unwrap_or_else(|| self.window_size(instance))
+    }
+
+    /// State to edit the assignments of the topology, where the window & residency sizes
+    /// and the next shards are only kept if they differ from those derived from the layers.
+    ///
+    /// The devices of the topology that are not among `shards` are added to them.
+    pub fn from_topology(
+        topology: &TopologyInfo,
+        mut shards: Vec<ShardInfo>,
+        excluded: BTreeSet<String>,
+    ) -> Result<Self, String> {
+        let model = topology
+            .model
+            .clone()
+            .ok_or("The active topology has no model loaded")?;
+
+        for device in &topology.devices {
+            if !shards.iter().any(|s| s.device.instance == device.instance) {
+                shards.push(ShardInfo {
+                    device: device.clone(),
+                    model_loaded: false,
+                    assigned_layers: Vec::new(),
+                });
+            }
+        }
+
+        let mut state = Self {
+            model,
+            num_layers: topology.num_layers,
+            shards,
+            excluded,
+            ..Default::default()
+        };
+        for assignment in &topology.assignments {
+            let rounds = assignment
+                .layers
+                .iter()
+                .filter(|round| !round.is_empty())
+                .cloned()
+                .collect();
+            state
+                .assignments
+                .insert(assignment.instance.clone(), rounds);
+        }
+
+        let derived = ring_next_instances(&state.assignments, &HashMap::new());
+        for assignment in &topology.assignments {
+            let instance = &assignment.instance;
+            if derived.get(instance) != Some(&assignment.next_instance) {
+                state
+                    .next_overrides
+                    .insert(instance.clone(), assignment.next_instance.clone());
+            }
+            if assignment.window_size != state.window_size(instance) {
+                state
+                    .windows
+                    .insert(instance.clone(), assignment.window_size);
+            }
+            if assignment.residency_size != state.residency_size(instance) {
+                state
+                    .residencies
+                    .insert(instance.clone(), assignment.residency_size);
+            }
+        }
+        state.selected_column = ColumnSelection::Assigned;
+
+        Ok(state)
+    }
+
+    /// Request to prepare the assigned topology, with the load settings of the config.
+    fn request(&self, config: &Config) -> PrepareManualTopologyRequest {
+        let (shards, assignments) = (&self.shards, &self.assignments);
+
+        // Determine next instances from the layers, unless set explicitly
+        let next_instances = ring_next_instances(&self.assignments, &self.next_overrides);
+
+        // Build devices array
+        let devices: Vec<DeviceProperties> = shards
+            .iter()
+            .filter(|s| assignments.contains_key(&s.device.instance))
+            .map(|shard| shard.device.clone())
+            .collect();
+
+        // Build assignments array
+        let assignment_infos: Vec<AssignmentInfo> = shards
+            .iter()
+            .filter_map(|shard| {
+                assignments.get(&shard.device.instance).map(|rounds| {
+                    let next_instance = next_instances
+                        .get(&shard.device.instance)
+                        .and_then(|next_instance| {
+                            shards
+                                .iter()
+                                .find(|s| s.device.instance == *next_instance)
+                                .map(|s| s.device.instance.clone())
+                        })
+                        .unwrap_or_else(|| shard.device.instance.clone());
+
+                    AssignmentInfo {
+                        instance: shard.device.instance.clone(),
+                        layers: rounds.clone(),
+                        window_size: self.window_size(&shard.device.instance),
+                        residency_size: self.residency_size(&shard.device.instance),
+                        next_instance,
+                    }
+                })
+            })
+            .collect();
+
+        PrepareManualTopologyRequest {
+            model: self.model.clone(),
+            devices,
+            assignments: assignment_infos,
+            num_layers: self.num_layers,
+            kv_bits: config.kv_bits,
+            seq_len: config.seq_len,
+            max_batch_size: config.max_batch_exp,
+        }
+    }
+}
+
+/// Body of `/v1/prepare_topology_manual`, see [`ManualAssignmentState::request`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrepareManualTopologyRequest {
+    model: String,
+    devices: Vec<DeviceProperties>,
+    assignments: Vec<AssignmentInfo>,
+    num_layers: u32,
+    kv_bits: KVBits,
+    seq_len: u32,
+    max_batch_size: u8,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShardInfo {
     #[serde(flatten)]
@@ -70,23 +402,6 @@ pub struct ShardInfo {
     pub assigned_layers: Vec<u32>,
 }
 
-/// Helper function to create a centered rect for popup
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::vertical([
-        Constraint::Percentage((100 - percent_y) / 2),
-        Constraint::Percentage(percent_y),
-        Constraint::Percentage((100 - percent_y) / 2),
-    ])
-    .split(r);
-
-    Layout::horizontal([
-        Constraint::Percentage((100 - percent_x) / 2),
-        Constraint::Percentage(percent_x),
-        Constraint::Percentage((100 - percent_x) / 2),
-    ])
-    .split(popup_layout[1])[1]
-}
-
 /// Helper to partition shards into unassigned and assigned lists
 #[allow(clippy::type_complexity)] // return type makes clippy angry
 fn partition_shards(
@@ -96,11 +411,11 @@ fn partition_shards(
     let mut assigned = Vec::new();
 
     for (i, shard) in state.shards.iter().enumerate() {
-        let shard_layers = state
-            .assignments
-            .get(&shard.device.instance)
-            .cloned()
-            .unwrap_or_default();
+        if !state.show_excluded && state.excluded.contains(&shard.device.instance) {
+            continue;
+        }
+
+        let shard_layers = state.layers(&shard.device.instance);
 
         if shard_layers.is_empty() && !shard.model_loaded {
             unassigned.push((i, shard));
@@ -112,6 +427,84 @@ fn partition_shards(
     (unassigned, assigned)
 }
 
+/// Set the next shard of `instance` to the next assigned shard (in the order of their
+/// layers), going back to the one that follows the layers after the last.
+fn cycle_next_instance(state: &mut ManualAssignmentState, instance: &str) {
+    let assignments = state.flat_assignments();
+    let mut candidates = assignments
+        .iter()
+        .filter(|(other, layers)| *other != instance && !layers.is_empty())
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(_, layers)| layers.iter().min().copied());
+
+    let position = state
+        .next_overrides
+        .get(instance)
+        .and_then(|next| candidates.iter().position(|(other, _)| *other == next));
+    let next = match position {
+        None => candidates.first(),
+        Some(i) => candidates.get(i + 1),
+    };
+    match next {
+        Some((next, _)) => {
+            let next = next.to_string();
+            state.next_overrides.insert(instance.to_string(), next)
+        }
+        None => state.next_overrides.remove(instance),
+    };
+}
+
+/// How the missing layers are split by [`auto_assign`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AutoAssign {
+    /// Into chunks of equal sizes.
+    Evenly,
+    /// Into chunks proportional to the memory of the hosts of the shards.
+    ByMemory,
+}
+
+/// Assign the missing layers to the unassigned shards (other than the excluded
+/// ones), to be fine-tuned afterwards.
+fn auto_assign(state: &mut ManualAssignmentState, mode: AutoAssign) -> Result<(), String> {
+    let missing = find_missing_layers(&state.assigned_layers(), state.num_layers);
+    if missing.is_empty() {
+        return Err("All layers are assigned already".to_string());
+    }
+
+    let (unassigned, _) = partition_shards(state);
+    let instances = unassigned
+        .iter()
+        .map(|(_, shard)| shard.device.instance.clone())
+        .filter(|instance| !state.excluded.contains(instance))
+        .collect::<Vec<_>>();
+    if instances.is_empty() {
+        return Err("There are no unassigned shards".to_string());
+    }
+
+    let chunks = match mode {
+        AutoAssign::Evenly => distribute_layers(&missing, instances.len()),
+        AutoAssign::ByMemory => {
+            let memory = instances
+                .iter()
+                .map(|instance| {
+                    state
+                        .memory_total(instance)
+                        .ok_or_else(|| format!("The memory of {} is not known (yet)", instance))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            distribute_layers_by_weight(&missing, &memory)
+        }
+    };
+    for (instance, layers) in instances.into_iter().zip(chunks) {
+        if !layers.is_empty() {
+            state.assignments.insert(instance, vec![layers]);
+        }
+    }
+    state.selected_column = ColumnSelection::Assigned;
+    state.selected_assigned_index = 0;
+    Ok(())
+}
+
 impl crate::App {
     pub fn draw_manual_assignment(&mut self, frame: &mut Frame, view: &ManualAssignmentView) {
         let area = frame.area();
@@ -134,6 +527,14 @@ impl crate::App {
             ManualAssignmentView::SelectingModel => {
                 self.draw_model_selection_for_manual(frame, content_area);
             }
+            ManualAssignmentView::FetchingTopology => {
+                frame.render_widget(
+                    Paragraph::new("Fetching the active topology...")
+                        .block(Block::default().borders(Borders::ALL))
+                        .centered(),
+                    content_area,
+                );
+            }
             ManualAssignmentView::FetchingShards(_) => {
                 frame.render_widget(
                     Paragraph::new("Fetching available shards...")
@@ -145,6 +546,10 @@ impl crate::App {
             ManualAssignmentView::AssigningLayers => {
                 self.draw_layer_assignment_interface(frame, content_area);
             }
+            ManualAssignmentView::Reviewing => {
+                self.draw_layer_assignment_interface(frame, content_area);
+                self.draw_request_review(frame, content_area);
+            }
             ManualAssignmentView::Submitting => {
                 frame.render_widget(
                     Paragraph::new(format!(
@@ -191,13 +596,21 @@ impl crate::App {
         // Footer with context-specific help
         let footer_text = match view {
             ManualAssignmentView::SelectingModel => {
-                "↑↓: Select model | Enter: Continue | Esc: Back"
+                "↑↓: Select model | Type to filter | Enter: Continue | Esc: Back"
             }
             ManualAssignmentView::AssigningLayers => {
-                if self.state.developer.manual.is_typing {
-                    "Type layers (e.g., 0,1,2 or 0-5) | Enter: Save | Esc: Cancel input"
-                } else {
-                    "←→: Switch column | ↑↓: Navigate | Enter: Assign/Submit | Ctrl+D: Deassign | Esc: Back"
+                let state = &self.state.developer.manual;
+                match (state.is_typing, state.input) {
+                    (true, ManualInput::Layers) => {
+                        "Type layers (e.g., 0-5, or 0-3; 8-11 for rounds) | Enter: Save | Esc: Cancel input"
+                    }
+                    (true, ManualInput::Round) => {
+                        "Type the layers of the round (e.g., 0-5) | Enter: Save | Esc: Cancel input"
+                    }
+                    (true, _) => "Type the size in layers | Enter: Save | Esc: Cancel input",
+                    (false, _) => {
+                        "←→: Switch column | ↑↓: Navigate | Enter: Assign/Submit | a/m: Auto-assign rest evenly/by memory | n: Next shard | +/-: Add/remove round | w/r: Window/Residency | Ctrl+D: Deassign | Ctrl+Z/Y: Undo/Redo | x: Show/hide excluded | Esc: Back"
+                    }
                 }
             }
             ManualAssignmentView::Success | ManualAssignmentView::Error(_) => {
@@ -205,6 +618,10 @@ impl crate::App {
             }
             ManualAssignmentView::LoadingModel(_) => "Loading model...",
             ManualAssignmentView::FetchingShards(_) => "Fetching shards...",
+            ManualAssignmentView::FetchingTopology => "Fetching topology...",
+            ManualAssignmentView::Reviewing => {
+                "↑↓/PgUp/PgDn: Scroll | Enter: Submit | c: Copy | Esc: Back to assignments"
+            }
             ManualAssignmentView::Submitting => "Submitting topology...",
         };
 
@@ -256,11 +673,19 @@ impl crate::App {
                 } else {
                     Style::default()
                 };
-                ListItem::new(shard.device.instance.clone()).style(style)
+                let name = self.config.device_name(&shard.device.instance);
+                if state.excluded.contains(&shard.device.instance) {
+                    ListItem::new(format!("{} (excluded)", name)).style(style.dark_gray())
+                } else if let Some(memory) = state.memory_total(&shard.device.instance) {
+                    ListItem::new(format!("{} ({})", name, format_bytes(memory))).style(style)
+                } else {
+                    ListItem::new(name.to_string()).style(style)
+                }
             })
             .collect();
 
-        // Create list items for assigned shards
+        // Create list items for assigned shards, with their next shard in the ring
+        let next_instances = ring_next_instances(&state.assignments, &state.next_overrides);
         let assigned_items: Vec<ListItem> = assigned_shards
             .iter()
             .enumerate()
@@ -275,15 +700,26 @@ impl crate::App {
                 } else {
                     Style::default()
                 };
-                let shard_layers = state
+                let rounds = state
                     .assignments
                     .get(&shard.device.instance)
-                    .cloned()
-                    .unwrap_or_default();
+                    .map_or_else(|| "[]".to_string(), |rounds| format_rounds(rounds));
+                let next = next_instances
+                    .get(&shard.device.instance)
+                    .map_or("?", |next| self.config.device_name(next));
+                let marker = if state.next_overrides.contains_key(&shard.device.instance) {
+                    " (set)"
+                } else {
+                    ""
+                };
                 let display_text = format!(
-                    "{}: {}",
-                    shard.device.instance,
-                    format_layers(&shard_layers)
+                    "{}: {} (w {}, r {}) → {}{}",
+                    self.config.device_name(&shard.device.instance),
+                    rounds,
+                    state.window_size(&shard.device.instance),
+                    state.residency_size(&shard.device.instance),
+                    next,
+                    marker
                 );
                 ListItem::new(display_text).style(style)
             })
@@ -342,23 +778,48 @@ impl crate::App {
         let state = &self.state.developer.manual;
 
         // Calculate remaining (unassigned) layers
-        let all_assigned_layers: HashSet<u32> = state
-            .assignments
-            .values()
-            .flat_map(|v| v.iter().cloned())
-            .collect();
-        let remaining_layers = find_missing_layers(&all_assigned_layers, state.num_layers);
+        let remaining_layers = find_missing_layers(&state.assigned_layers(), state.num_layers);
 
         // Get the actual shard index based on current column
         let (_shard_index, shard_name) = Self::get_selected_shard_info(state);
-        let shard_name = shard_name.as_deref().unwrap_or("Unknown");
+        let instance = shard_name.as_deref().unwrap_or_default();
+        let shard_name = shard_name
+            .as_deref()
+            .map_or("Unknown", |instance| self.config.device_name(instance));
+
+        if matches!(state.input, ManualInput::Window | ManualInput::Residency) {
+            let (help, num_layers) = match state.input {
+                ManualInput::Residency => ("Layers kept in memory", state.layers(instance).len()),
+                _ => (
+                    "Layers loaded at a time",
+                    state.largest_round(instance) as usize,
+                ),
+            };
+            let content = vec![
+                Line::from(vec![
+                    format!("{} of: ", state.input.label()).into(),
+                    shard_name.bold().cyan(),
+                ]),
+                Line::from(""),
+                Line::from(vec!["Input: ".into(), self.input_buffer.clone().yellow()]),
+                Line::from(""),
+                Line::from(format!(
+                    "{}, between 1 and {} (its number of layers)",
+                    help, num_layers
+                )),
+            ];
+            let title = format!(" {} ", state.input.label());
+            self.draw_manual_input_popup(frame, area, &title, content);
+            return;
+        }
 
         // Build popup content
+        let (title, heading) = match state.input {
+            ManualInput::Round => (" Add Round ", "Adding a round to: "),
+            _ => (" Assign Layers ", "Assigning layers to: "),
+        };
         let mut content = vec![
-            Line::from(vec![
-                "Assigning layers to: ".into(),
-                shard_name.bold().cyan(),
-            ]),
+            Line::from(vec![heading.into(), shard_name.bold().cyan()]),
             Line::from(""),
             Line::from(vec!["Input: ".into(), self.input_buffer.clone().yellow()]),
             Line::from(""),
@@ -375,8 +836,65 @@ impl crate::App {
         }
 
         content.push(Line::from(""));
-        content.push(Line::from("Examples: 0,1,2 or 0-5".dark_gray()));
+        content.push(Line::from(match state.input {
+            ManualInput::Round => "Examples: 0,1,2 or 0-5".dark_gray(),
+            _ => "Examples: 0,1,2 or 0-5, or 0-3; 8-11 for rounds".dark_gray(),
+        }));
+        self.draw_manual_input_popup(frame, area, title, content);
+    }
+
+    /// The request to submit, pretty-printed.
+    fn manual_request_json(&self) -> String {
+        let request = self.state.developer.manual.request(&self.config);
+        serde_json::to_string_pretty(&request).unwrap_or_else(|e| e.to_string())
+    }
+
+    /// Draw the request to submit on top of the assignments, to review it.
+    fn draw_request_review(&mut self, frame: &mut Frame, area: Rect) {
+        let json = self.manual_request_json();
+        let popup_area = centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
 
+        let lines = json.lines().map(Line::from).collect::<Vec<_>>();
+        let max_scroll = lines
+            .len()
+            .saturating_sub(popup_area.height.saturating_sub(2) as usize);
+        let scroll = &mut self.state.developer.manual.review_scroll;
+        *scroll = (*scroll).min(max_scroll as u16);
+
+        let title = if self.status_message.is_empty() {
+            " POST /v1/prepare_topology_manual ".to_string()
+        } else {
+            format!(" {} ", self.status_message)
+        };
+        frame.render_widget(
+            Paragraph::new(lines).scroll((*scroll, 0)).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            ),
+            popup_area,
+        );
+
+        let mut scroll_bar = ScrollbarState::new(max_scroll).position(*scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            popup_area,
+            &mut scroll_bar,
+        );
+    }
+
+    /// Draw the popup of the typed input with the given content.
+    fn draw_manual_input_popup(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        content: Vec<Line>,
+    ) {
         // Create popup area
         let popup_area = centered_rect(60, 40, area);
 
@@ -387,7 +905,7 @@ impl crate::App {
         let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title(" Assign Layers ")
+                    .title(title)
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Cyan)),
             )
@@ -412,11 +930,7 @@ impl crate::App {
         .split(area);
 
         // Collect all assigned layers
-        let all_assigned_layers: HashSet<u32> = state
-            .assignments
-            .values()
-            .flat_map(|v| v.iter().cloned())
-            .collect();
+        let all_assigned_layers = state.assigned_layers();
 
         let missing_layers = find_missing_layers(&all_assigned_layers, state.num_layers);
         let all_assigned = missing_layers.is_empty();
@@ -425,10 +939,7 @@ impl crate::App {
         let selected_shard_layers: HashSet<u32> = if let Some(idx) = selected_shard_index {
             if idx < state.shards.len() {
                 state
-                    .assignments
-                    .get(&state.shards[idx].device.instance)
-                    .cloned()
-                    .unwrap_or_default()
+                    .layers(&state.shards[idx].device.instance)
                     .into_iter()
                     .collect()
             } else {
@@ -499,29 +1010,48 @@ impl crate::App {
         view: &ManualAssignmentView,
     ) {
         match view {
-            ManualAssignmentView::SelectingModel => match key.code {
-                KeyCode::Esc => {
-                    self.view = AppView::Developer(DeveloperView::Menu);
-                }
-                KeyCode::Up => {
-                    self.model_selector_state
-                        .move_up(self.available_models.len());
-                }
-                KeyCode::Down => {
-                    self.model_selector_state
-                        .move_down(self.available_models.len());
-                }
-                KeyCode::Enter => {
-                    let model = self.available_models[self.model_selector_state.selected()]
-                        .id
-                        .clone();
-                    self.view = AppView::Developer(DeveloperView::ManualAssignment(
-                        ManualAssignmentView::FetchingShards(model),
-                    ));
+            ManualAssignmentView::SelectingModel => {
+                let models = self.available_models.iter().map(|m| &m.id);
+                let num_matches = self.model_selector_state.matches(models.clone()).len();
+                match key.code {
+                    KeyCode::Esc if self.model_selector_state.filter().is_empty() => {
+                        self.view = AppView::Developer(DeveloperView::Menu);
+                    }
+                    KeyCode::Esc => self.model_selector_state.clear_filter(),
+                    KeyCode::Up => self.model_selector_state.move_up(num_matches),
+                    KeyCode::Down => self.model_selector_state.move_down(num_matches),
+                    KeyCode::Enter => {
+                        if let Some(i) = self.model_selector_state.selected_item(models) {
+                            let model = self.available_models[i].id.clone();
+                            self.view = AppView::Developer(DeveloperView::ManualAssignment(
+                                ManualAssignmentView::FetchingShards(model),
+                            ));
+                        }
+                    }
+                    KeyCode::Backspace => self.model_selector_state.pop_filter(),
+                    KeyCode::Char(c) => self.model_selector_state.push_filter(c),
+                    _ => {}
                 }
-                _ => {}
-            },
+            }
             ManualAssignmentView::AssigningLayers => {
+                let state = &mut self.state.developer.manual;
+                if !state.is_typing && key.modifiers == KeyModifiers::CONTROL {
+                    let history_action = match key.code {
+                        KeyCode::Char('z' | 'Z') => Some(("undo", state.undo())),
+                        KeyCode::Char('y' | 'Y') => Some(("redo", state.redo())),
+                        _ => None,
+                    };
+                    if let Some((action, done)) = history_action {
+                        self.status_message = if done {
+                            String::new()
+                        } else {
+                            format!("Nothing to {}", action)
+                        };
+                        return;
+                    }
+                }
+                let before = state.edit();
+
                 // Get shard info before borrowing state mutably
                 let shard_info = Self::get_selected_shard_info(&self.state.developer.manual);
                 let state = &mut self.state.developer.manual;
@@ -533,17 +1063,57 @@ impl crate::App {
                             state.is_typing = false;
                             self.input_buffer.clear();
                         }
+                        KeyCode::Enter
+                            if matches!(
+                                state.input,
+                                ManualInput::Window | ManualInput::Residency
+                            ) =>
+                        {
+                            let num_layers =
+                                shard_info.1.as_deref().map_or(0, |name| match state.input {
+                                    ManualInput::Residency => state.layers(name).len() as u32,
+                                    _ => state.largest_round(name),
+                                });
+                            match (
+                                parse_size_input(&self.input_buffer, num_layers),
+                                shard_info.1,
+                            ) {
+                                (Ok(size), Some(name)) => {
+                                    match state.input {
+                                        ManualInput::Residency => {
+                                            state.residencies.insert(name, size)
+                                        }
+                                        _ => state.windows.insert(name, size),
+                                    };
+                                    self.status_message.clear();
+                                }
+                                (Err(err), _) => {
+                                    self.status_message =
+                                        format!("Error: {}: {}", state.input.label(), err);
+                                }
+                                _ => {}
+                            }
+                            state.is_typing = false;
+                            self.input_buffer.clear();
+                        }
                         KeyCode::Enter => {
-                            // Parse and save layers with collision detection
-                            if let Some(layers) =
-                                parse_layer_input(&self.input_buffer, state.num_layers)
-                            {
+                            // Parse and save layers with collision detection, where a new
+                            // round must not collide with the other rounds of the shard either
+                            let rounds = match state.input {
+                                ManualInput::Round => {
+                                    parse_layer_input(&self.input_buffer, state.num_layers)
+                                        .map(|layers| vec![layers])
+                                }
+                                _ => parse_rounds_input(&self.input_buffer, state.num_layers),
+                            };
+                            if let Some(rounds) = rounds {
                                 if let (Some(_idx), Some(name)) = shard_info {
-                                    // Check for collisions with other shards
+                                    let adding = state.input == ManualInput::Round;
+                                    let layers = rounds.concat();
                                     let has_collision = state
-                                        .assignments
+                                        .flat_assignments()
                                         .iter()
-                                        .filter(|(instance, _)| **instance != name)
+                                        .filter(|(instance, _)| adding || **instance != name)
                                         .any(|(_, assigned_layers)| {
                                             layers.iter().any(|l| assigned_layers.contains(l))
                                         });
@@ -553,7 +1123,15 @@ impl crate::App {
                                         self.status_message = "Error: Layer collision detected! Those layers are already assigned to another shard.".to_string();
                                     } else {
                                         // No collision - proceed with assignment
-                                        state.assignments.insert(name, layers);
+                                        if adding {
+                                            state
+                                                .assignments
+                                                .entry(name)
+                                                .or_default()
+                                                .extend(rounds);
+                                        } else {
+                                            state.assignments.insert(name, rounds);
+                                        }
                                         self.status_message.clear();
 
                                         // Auto-switch to Assigned column if all shards are now assigned
@@ -572,7 +1150,7 @@ impl crate::App {
                             self.input_buffer.pop();
                         }
                         KeyCode::Char(c)
-                            if c.is_ascii_digit() || c == ',' || c == '-' || c == ' ' =>
+                            if c.is_ascii_digit() || matches!(c, ',' | '-' | ' ' | ';') =>
                         {
                             self.input_buffer.push(c);
                         }
@@ -648,30 +1226,103 @@ impl crate::App {
                         }
                         (_, KeyCode::Enter) => {
                             // Check if all layers are assigned
-                            let all_assigned_layers: HashSet<u32> = state
-                                .assignments
-                                .values()
-                                .flat_map(|v| v.iter().cloned())
-                                .collect();
                             let missing_layers =
-                                find_missing_layers(&all_assigned_layers, state.num_layers);
+                                find_missing_layers(&state.assigned_layers(), state.num_layers);
 
                             if missing_layers.is_empty() {
-                                // all layers assigned - submit!
-                                self.view = AppView::Developer(DeveloperView::ManualAssignment(
-                                    ManualAssignmentView::Submitting,
-                                ));
+                                // all layers assigned - submit if the ring is complete!
+                                let next_instances =
+                                    ring_next_instances(&state.assignments, &state.next_overrides);
+                                match validate_ring(&next_instances) {
+                                    Ok(()) => {
+                                        state.review_scroll = 0;
+                                        self.status_message.clear();
+                                        self.view =
+                                            AppView::Developer(DeveloperView::ManualAssignment(
+                                                ManualAssignmentView::Reviewing,
+                                            ));
+                                    }
+                                    Err(err) => {
+                                        self.status_message = format!("Error: {}", err);
+                                    }
+                                }
                             } else {
                                 // Not all assigned - enter typing mode
                                 state.is_typing = true;
+                                state.input = ManualInput::Layers;
                                 self.input_buffer.clear();
                                 self.status_message.clear();
                             }
                         }
+                        (_, KeyCode::Char('n'))
+                            if state.selected_column == ColumnSelection::Assigned =>
+                        {
+                            if let (_, Some(name)) = &shard_info {
+                                cycle_next_instance(state, name);
+                            }
+                        }
+                        (_, KeyCode::Char('+'))
+                            if state.selected_column == ColumnSelection::Assigned
+                                && shard_info.1.is_some() =>
+                        {
+                            state.is_typing = true;
+                            state.input = ManualInput::Round;
+                            self.input_buffer.clear();
+                            self.status_message.clear();
+                        }
+                        (_, KeyCode::Char('-'))
+                            if state.selected_column == ColumnSelection::Assigned =>
+                        {
+                            if let (_, Some(name)) = &shard_info {
+                                match state.assignments.get_mut(name) {
+                                    Some(rounds) if rounds.len() > 1 => {
+                                        rounds.pop();
+                                        self.status_message.clear();
+                                    }
+                                    _ => {
+                                        self.status_message =
+                                            "Error: The shard has a single round, deassign it with Ctrl+D instead".to_string();
+                                    }
+                                }
+                            }
+                        }
+                        (_, KeyCode::Char(c @ ('w' | 'r')))
+                            if state.selected_column == ColumnSelection::Assigned =>
+                        {
+                            if let (_, Some(name)) = &shard_info {
+                                let (input, size) = match c {
+                                    'r' => (ManualInput::Residency, state.residency_size(name)),
+                                    _ => (ManualInput::Window, state.window_size(name)),
+                                };
+                                self.input_buffer = size.to_string();
+                                state.is_typing = true;
+                                state.input = input;
+                                self.status_message.clear();
+                            }
+                        }
+                        (_, KeyCode::Char(c @ ('a' | 'm'))) => {
+                            let mode = match c {
+                                'm' => AutoAssign::ByMemory,
+                                _ => AutoAssign::Evenly,
+                            };
+                            self.status_message = match auto_assign(state, mode) {
+                                Ok(()) => String::new(),
+                                Err(err) => format!("Error: {}", err),
+                            };
+                        }
+                        (_, KeyCode::Char('x')) => {
+                            state.show_excluded = !state.show_excluded;
+                            state.selected_column = ColumnSelection::Unassigned;
+                            state.selected_unassigned_index = 0;
+                            state.selected_assigned_index = 0;
+                        }
                         (KeyModifiers::CONTROL, KeyCode::Char('d') | KeyCode::Char('D')) => {
                             // Deassign layers from the selected shard
                             if let (_, Some(name)) = shard_info {
                                 state.assignments.remove(&name);
+                                state.next_overrides.remove(&name);
+                                state.windows.remove(&name);
+                                state.residencies.remove(&name);
 
                                 // Auto-switch to Unassigned column if no more assigned shards
                                 let (unassigned, assigned) = partition_shards(state);
@@ -691,6 +1342,37 @@ impl crate::App {
                         _ => {}
                     }
                 }
+                self.state.developer.manual.record_edit(before);
+            }
+            ManualAssignmentView::Reviewing => {
+                let scroll = &mut self.state.developer.manual.review_scroll;
+                match key.code {
+                    KeyCode::Esc => {
+                        self.view = AppView::Developer(DeveloperView::ManualAssignment(
+                            ManualAssignmentView::AssigningLayers,
+                        ));
+                    }
+                    KeyCode::Enter => {
+                        self.view = AppView::Developer(DeveloperView::ManualAssignment(
+                            ManualAssignmentView::Submitting,
+                        ));
+                    }
+                    KeyCode::Up => *scroll = scroll.saturating_sub(1),
+                    KeyCode::Down => *scroll = scroll.saturating_add(1),
+                    KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+                    KeyCode::PageDown => *scroll = scroll.saturating_add(10),
+                    KeyCode::Char('c') => {
+                        let json = self.manual_request_json();
+                        self.status_message = match crossterm::execute!(
+                            std::io::stdout(),
+                            crossterm::clipboard::CopyToClipboard::to_clipboard_from(json)
+                        ) {
+                            Ok(()) => "Copied the request to the clipboard".to_string(),
+                            Err(err) => format!("Error copying the request: {}", err),
+                        };
+                    }
+                    _ => {}
+                }
             }
             ManualAssignmentView::LoadingModel(_) => {
                 // loading is in progress, just wait
@@ -739,74 +1421,11 @@ impl crate::App {
     async fn submit_manual_topology(
         &self,
         config: &Config,
-        model: &str,
-        shards: &[ShardInfo],
-        assignments: &HashMap<String, Vec<u32>>,
+        request: &PrepareManualTopologyRequest,
     ) -> color_eyre::Result<()> {
-        #[derive(Serialize, Deserialize)]
-        struct PrepareManualTopologyRequest {
-            model: String,
-            devices: Vec<DeviceProperties>,
-            assignments: Vec<AssignmentInfo>,
-            num_layers: u32,
-            kv_bits: KVBits,
-            seq_len: u32,
-            max_batch_size: u8,
-        }
-        let num_layers = ModelConfig::get_model_config(model)
-            .await?
-            .num_layers()
-            .ok_or_eyre("Could not determine number of layers")? as u32;
-
-        // Determine next instances automatically
-        let next_instances = determine_next_instances(assignments);
-
-        // Build devices array
-        let devices: Vec<DeviceProperties> = shards
-            .iter()
-            .filter(|s| assignments.contains_key(&s.device.instance))
-            .map(|shard| shard.device.clone())
-            .collect();
-
-        // Build assignments array
-        let assignment_infos: Vec<AssignmentInfo> = shards
-            .iter()
-            .filter_map(|shard| {
-                assignments.get(&shard.device.instance).map(|layers| {
-                    let next_instance = next_instances
-                        .get(&shard.device.instance)
-                        .and_then(|next_instance| {
-                            shards
-                                .iter()
-                                .find(|s| s.device.instance == *next_instance)
-                                .map(|s| s.device.instance.clone())
-                        })
-                        .unwrap_or_else(|| shard.device.instance.clone());
-
-                    AssignmentInfo {
-                        instance: shard.device.instance.clone(),
-                        layers: vec![layers.clone()],
-                        window_size: layers.len() as u32,
-                        residency_size: layers.len() as u32, // FIXME: adjust this?
-                        next_instance,
-                    }
-                })
-            })
-            .collect();
-
-        let request = PrepareManualTopologyRequest {
-            model: model.to_string(),
-            devices,
-            assignments: assignment_infos,
-            num_layers,
-            kv_bits: config.kv_bits,
-            seq_len: config.seq_len,
-            max_batch_size: config.max_batch_exp,
-        };
-
         let url = format!("{}/v1/prepare_topology_manual", config.api_url());
         let client = reqwest::Client::new();
-        let response = client.post(&url).json(&request).send().await?;
+        let response = client.post(&url).json(request).send().await?;
 
         if response.status().is_success() {
             Ok(())
@@ -818,6 +1437,38 @@ impl crate::App {
     /// Handle async operations for manual assignment state (called during tick).
     pub(super) async fn tick_manual_assignment(&mut self, view: &ManualAssignmentView) {
         match view {
+            ManualAssignmentView::FetchingTopology => {
+                let result = match self.api.get_topologies().await {
+                    Ok(Topologies { topologies, .. }) => {
+                        // the shown topology, as there may be one per loaded model
+                        let model = self.topology.as_ref().and_then(|t| t.model.clone());
+                        let topology = topologies
+                            .iter()
+                            .find(|t| model.is_some() && t.model == model)
+                            .or(topologies.first())
+                            .cloned();
+                        match (topology, self.fetch_shards_with_model().await) {
+                            (Some(topology), Ok(shards)) => ManualAssignmentState::from_topology(
+                                &topology,
+                                shards,
+                                self.config.excluded_devices.clone(),
+                            ),
+                            (None, _) => Err("There is no active topology".to_string()),
+                            (_, Err(err)) => Err(format!("{:#?}", err)),
+                        }
+                    }
+                    Err(err) => Err(format!("{:#?}", err)),
+                };
+
+                self.view = AppView::Developer(DeveloperView::ManualAssignment(match result {
+                    Ok(state) => {
+                        self.state.developer.manual = state;
+                        self.state.developer.manual.probe_shards();
+                        ManualAssignmentView::AssigningLayers
+                    }
+                    Err(err) => ManualAssignmentView::Error(err),
+                }));
+            }
             ManualAssignmentView::FetchingShards(model) => {
                 match self.fetch_shards_with_model().await {
                     Ok(shards) => {
@@ -833,12 +1484,10 @@ impl crate::App {
                                     model: model.clone(),
                                     num_layers: num_layers as u32,
                                     shards,
-                                    assignments: HashMap::new(),
-                                    selected_column: ColumnSelection::Unassigned,
-                                    selected_unassigned_index: 0,
-                                    selected_assigned_index: 0,
-                                    is_typing: false,
+                                    excluded: self.config.excluded_devices.clone(),
+                                    ..Default::default()
                                 };
+                                self.state.developer.manual.probe_shards();
                                 self.view = AppView::Developer(DeveloperView::ManualAssignment(
                                     ManualAssignmentView::AssigningLayers,
                                 ));
@@ -859,15 +1508,8 @@ impl crate::App {
             }
             ManualAssignmentView::Submitting => {
                 let model = self.state.developer.manual.model.clone();
-                match self
-                    .submit_manual_topology(
-                        &self.config,
-                        &model,
-                        &self.state.developer.manual.shards,
-                        &self.state.developer.manual.assignments,
-                    )
-                    .await
-                {
+                let request = self.state.developer.manual.request(&self.config);
+                match self.submit_manual_topology(&self.config, &request).await {
                     Ok(_) => {
                         // Topology prepared, now load the model
                         self.view = AppView::Developer(DeveloperView::ManualAssignment(
@@ -883,8 +1525,17 @@ impl crate::App {
             }
             ManualAssignmentView::LoadingModel(model) => {
                 // Load the model using the existing LoadModelState functionality
-                match self.api.load_model(model).await {
+                let started_at = std::time::Instant::now();
+                let result = self.api.load_model(model).await;
+                let record = crate::model::LoadRecord::load(
+                    model,
+                    started_at.elapsed(),
+                    &result.as_ref().cloned().map_err(|e| e.to_string()),
+                );
+                self.record_load(record);
+                match result {
                     Ok(_response) => {
+                        self.remember_loaded_model(model);
                         self.view = AppView::Developer(DeveloperView::ManualAssignment(
                             ManualAssignmentView::Success,
                         ));
@@ -900,9 +1551,79 @@ impl crate::App {
                     }
                 }
             }
+            ManualAssignmentView::AssigningLayers => {
+                self.state.developer.manual.probe.poll();
+            }
             _ => {
                 // No async operations needed for other states
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(instance: &str) -> DeviceProperties {
+        DeviceProperties {
+            is_manager: false,
+            is_busy: false,
+            instance: instance.to_string(),
+            server_port: 8081,
+            shard_port: 58081,
+            local_ip: "192.168.1.20".to_string(),
+            thunderbolt: None,
+        }
+    }
+
+    #[test]
+    fn test_state_from_topology() {
+        let assignment =
+            |instance: &str, layers: Vec<u32>, next: &str, window: u32| AssignmentInfo {
+                instance: instance.to_string(),
+                layers: vec![layers],
+                next_instance: next.to_string(),
+                window_size: window,
+                residency_size: window,
+            };
+        let topology = TopologyInfo {
+            model: Some("model".to_string()),
+            num_layers: 6,
+            devices: vec![device("a"), device("b"), device("c")],
+            assignments: vec![
+                assignment("a", vec![0, 1, 2], "c", 3),
+                assignment("b", vec![3, 4], "a", 1),
+                assignment("c", vec![5], "b", 1),
+            ],
+            solution: None,
+            kv_bits: KVBits::default(),
+        };
+        let shards = vec![ShardInfo {
+            device: device("a"),
+            model_loaded: true,
+            assigned_layers: vec![0, 1, 2],
+        }];
+
+        let state =
+            ManualAssignmentState::from_topology(&topology, shards, BTreeSet::new()).unwrap();
+        assert_eq!(state.shards.len(), 3);
+        assert_eq!(state.assignments["b"], vec![vec![3, 4]]);
+
+        // only what differs from the layers is kept
+        assert_eq!(state.next_overrides.len(), 3);
+        assert_eq!(state.windows, HashMap::from([("b".to_string(), 1)]));
+        assert!(state.residencies.is_empty());
+        assert_eq!(state.residency_size("b"), 1);
+
+        let request = state.request(&Config::default());
+        assert_eq!(request.assignments.len(), 3);
+        assert_eq!(request.num_layers, 6);
+
+        let topology = TopologyInfo {
+            model: None,
+            ..topology
+        };
+        assert!(ManualAssignmentState::from_topology(&topology, vec![], BTreeSet::new()).is_err());
+    }
+}
@@ -11,6 +11,71 @@ use crate::AppView;
 
 use super::DeveloperView;
 
+/// Entries of the developer menu, in the order they are listed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeveloperMenuItem {
+    ManualAssignment,
+    EditTopology,
+    DeleteWeights,
+    DiskUsage,
+    LoadHistory,
+    ApiConsole,
+    Inspector,
+    Benchmark,
+    LogLevels,
+    Simulate,
+}
+
+impl DeveloperMenuItem {
+    pub const ALL: [DeveloperMenuItem; 10] = [
+        DeveloperMenuItem::ManualAssignment,
+        DeveloperMenuItem::EditTopology,
+        DeveloperMenuItem::DeleteWeights,
+        DeveloperMenuItem::DiskUsage,
+        DeveloperMenuItem::LoadHistory,
+        DeveloperMenuItem::ApiConsole,
+        DeveloperMenuItem::Inspector,
+        DeveloperMenuItem::Benchmark,
+        DeveloperMenuItem::LogLevels,
+        DeveloperMenuItem::Simulate,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            DeveloperMenuItem::ManualAssignment => {
+                "Manual Layer Assignment - Manually assign layers to shards"
+            }
+            DeveloperMenuItem::EditTopology => {
+                "Edit Current Topology   - Change the assignments of the active topology"
+            }
+            DeveloperMenuItem::DeleteWeights => {
+                "Delete Model Weights    - Delete cached weights from shards"
+            }
+            DeveloperMenuItem::DiskUsage => {
+                "Disk Usage              - Cached model weights of each shard"
+            }
+            DeveloperMenuItem::LoadHistory => {
+                "Load History            - Past loads & unloads of the models"
+            }
+            DeveloperMenuItem::ApiConsole => {
+                "API Console             - Send raw requests to the manager API"
+            }
+            DeveloperMenuItem::Inspector => {
+                "JSON Inspector          - Raw topology & devices payloads of the API"
+            }
+            DeveloperMenuItem::Benchmark => {
+                "Cluster Benchmark       - Throughput & TTFT over batch sizes & lengths"
+            }
+            DeveloperMenuItem::LogLevels => {
+                "Shard Log Levels        - Set the log verbosity of the shards remotely"
+            }
+            DeveloperMenuItem::Simulate => {
+                "Simulate Solver         - Proposed topology of a model, without loading it"
+            }
+        }
+    }
+}
+
 impl crate::App {
     pub(super) fn draw_developer_menu(&mut self, frame: &mut Frame) {
         let area = frame.area();
@@ -26,10 +91,7 @@ impl crate::App {
         let title = Line::from("Developer Menu").bold().cyan().centered();
         frame.render_widget(Paragraph::new(title), title_area);
 
-        // Menu items - just one option now
-        let menu_items = ["Manual Layer Assignment - Manually assign layers to shards"];
-
-        let items: Vec<ListItem> = menu_items
+        let items: Vec<ListItem> = DeveloperMenuItem::ALL
             .iter()
             .enumerate()
             .map(|(i, item)| {
@@ -41,7 +103,7 @@ impl crate::App {
                 } else {
                     Style::default()
                 };
-                ListItem::new(*item).style(style)
+                ListItem::new(item.label()).style(style)
             })
             .collect();
 
@@ -50,7 +112,7 @@ impl crate::App {
 
         // Footer
         frame.render_widget(
-            Paragraph::new("Enter: Select | Esc: Back to main menu")
+            Paragraph::new("↑↓: Navigate | Enter: Select | Esc: Back to main menu")
                 .centered()
                 .gray(),
             footer_area,
@@ -63,14 +125,46 @@ impl crate::App {
                 self.view = AppView::Menu;
                 self.state.developer.menu_index = 0;
             }
+            (_, KeyCode::Up) => {
+                self.state.developer.menu_index = self.state.developer.menu_index.saturating_sub(1);
+            }
+            (_, KeyCode::Down) => {
+                self.state.developer.menu_index =
+                    (self.state.developer.menu_index + 1).min(DeveloperMenuItem::ALL.len() - 1);
+            }
             (_, KeyCode::Enter) => {
-                // Only one option now - Manual Layer Assignment
-                if self.state.developer.menu_index == 0 {
-                    self.view = AppView::Developer(DeveloperView::ManualAssignment(
-                        super::ManualAssignmentView::SelectingModel,
-                    ));
-                    self.model_selector_state.reset();
-                }
+                let Some(item) = DeveloperMenuItem::ALL.get(self.state.developer.menu_index) else {
+                    return;
+                };
+                self.view = AppView::Developer(match item {
+                    DeveloperMenuItem::ManualAssignment => {
+                        DeveloperView::ManualAssignment(super::ManualAssignmentView::SelectingModel)
+                    }
+                    DeveloperMenuItem::EditTopology => DeveloperView::ManualAssignment(
+                        super::ManualAssignmentView::FetchingTopology,
+                    ),
+                    DeveloperMenuItem::DeleteWeights => {
+                        DeveloperView::Weights(super::WeightsView::SelectingModel)
+                    }
+                    DeveloperMenuItem::DiskUsage => DeveloperView::Disk(super::DiskView::Loading),
+                    DeveloperMenuItem::LoadHistory => return self.open_load_history(),
+                    DeveloperMenuItem::ApiConsole => {
+                        DeveloperView::ApiConsole(super::ApiConsoleView::Editing)
+                    }
+                    DeveloperMenuItem::Inspector => {
+                        DeveloperView::Inspector(super::InspectorView::Loading)
+                    }
+                    DeveloperMenuItem::Benchmark => {
+                        DeveloperView::Benchmark(super::BenchmarkView::Configuring)
+                    }
+                    DeveloperMenuItem::LogLevels => {
+                        DeveloperView::LogLevels(super::LogLevelView::Loading)
+                    }
+                    DeveloperMenuItem::Simulate => {
+                        DeveloperView::Simulate(super::SimulateView::SelectingModel)
+                    }
+                });
+                self.model_selector_state.reset();
             }
             _ => {}
         }
@@ -1,8 +1,29 @@
 mod manual;
 pub use manual::*;
 
+mod weights;
+pub use weights::*;
+
+mod disk;
+pub use disk::*;
+
+mod console;
+pub use console::*;
+
+mod inspector;
+pub use inspector::*;
+
+mod benchmark;
+pub use benchmark::*;
+
+mod logs;
+pub use logs::*;
+
+mod simulate;
+pub use simulate::*;
+
 mod menu;
-mod utils;
+pub(crate) mod utils;
 
 use crossterm::event::KeyEvent;
 use ratatui::Frame;
@@ -11,6 +32,13 @@ use ratatui::Frame;
 pub enum DeveloperView {
     Menu,
     ManualAssignment(ManualAssignmentView),
+    Weights(WeightsView),
+    Disk(DiskView),
+    ApiConsole(ApiConsoleView),
+    Inspector(InspectorView),
+    Benchmark(BenchmarkView),
+    LogLevels(LogLevelView),
+    Simulate(SimulateView),
 }
 
 #[derive(Debug, Default)]
@@ -19,6 +47,20 @@ pub struct DeveloperState {
     pub menu_index: usize,
 
     pub manual: ManualAssignmentState,
+
+    pub weights: WeightsState,
+
+    pub disk: DiskState,
+
+    pub console: ApiConsoleState,
+
+    pub inspector: InspectorState,
+
+    pub bench: ClusterBenchState,
+
+    pub logs: LogLevelState,
+
+    pub simulate: SimulateState,
 }
 
 impl crate::App {
@@ -28,6 +70,13 @@ impl crate::App {
             DeveloperView::ManualAssignment(ma_state) => {
                 self.draw_manual_assignment(frame, ma_state)
             }
+            DeveloperView::Weights(view) => self.draw_weights(frame, view),
+            DeveloperView::Disk(view) => self.draw_disk(frame, view),
+            DeveloperView::ApiConsole(view) => self.draw_api_console(frame, view),
+            DeveloperView::Inspector(view) => self.draw_inspector(frame, view),
+            DeveloperView::Benchmark(view) => self.draw_cluster_bench(frame, view),
+            DeveloperView::LogLevels(view) => self.draw_log_levels(frame, view),
+            DeveloperView::Simulate(view) => self.draw_simulate(frame, view),
         }
     }
 
@@ -37,6 +86,13 @@ impl crate::App {
             DeveloperView::ManualAssignment(ma_state) => {
                 self.handle_manual_assignment_input(key, ma_state)
             }
+            DeveloperView::Weights(view) => self.handle_weights_input(key, view),
+            DeveloperView::Disk(view) => self.handle_disk_input(key, view),
+            DeveloperView::ApiConsole(view) => self.handle_api_console_input(key, view),
+            DeveloperView::Inspector(view) => self.handle_inspector_input(key, view),
+            DeveloperView::Benchmark(view) => self.handle_cluster_bench_input(key, view),
+            DeveloperView::LogLevels(view) => self.handle_log_levels_input(key, view),
+            DeveloperView::Simulate(view) => self.handle_simulate_input(key, view),
         }
     }
 
@@ -49,6 +105,13 @@ impl crate::App {
             DeveloperView::ManualAssignment(ma_state) => {
                 self.tick_manual_assignment(ma_state).await
             }
+            DeveloperView::Weights(view) => self.tick_weights(view).await,
+            DeveloperView::Disk(view) => self.tick_disk(view).await,
+            DeveloperView::ApiConsole(view) => self.tick_api_console(view).await,
+            DeveloperView::Inspector(view) => self.tick_inspector(view).await,
+            DeveloperView::Benchmark(view) => self.tick_cluster_bench(view),
+            DeveloperView::LogLevels(view) => self.tick_log_levels(view).await,
+            DeveloperView::Simulate(view) => self.tick_simulate(view).await,
         }
     }
 }
@@ -0,0 +1,354 @@
+use super::utils::format_rounds;
+use super::{DeveloperView, ManualAssignmentState, ManualAssignmentView};
+use crate::AppView;
+use crate::common::TopologyInfo;
+use crate::utils::centered_rect;
+use crate::views::topology::SolverDetails;
+use crate::widgets::ConfirmDialog;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, Wrap},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulateView {
+    SelectingModel,
+    /// Confirming to replace the prepared topology of the manager, e.g. a manual one.
+    Confirming(String /* model name */),
+    /// Asking the planner for a topology of the model, i.e. preparing one, which
+    /// the manager keeps as its prepared topology but loads nothing for.
+    Solving(String /* model name */),
+    /// Showing the proposed topology.
+    Solved,
+    /// Opening the proposed topology in the manual assignment editor.
+    OpeningEditor,
+    Error(String),
+}
+
+/// Topology that the planner proposes for a model, without loading it.
+#[derive(Debug, Default)]
+pub struct SimulateState {
+    pub topology: Option<TopologyInfo>,
+}
+
+/// Rows of the proposed assignments in the order of the ring, i.e. by their first layer.
+pub fn proposal_rows(topology: &TopologyInfo) -> Vec<[String; 5]> {
+    let mut assignments = topology.assignments.iter().collect::<Vec<_>>();
+    assignments.sort_by_key(|a| a.layers.iter().flatten().min().copied());
+    assignments
+        .into_iter()
+        .map(|a| {
+            let num_layers = a.layers.iter().map(Vec::len).sum::<usize>();
+            [
+                a.instance.clone(),
+                format!("{} ({})", format_rounds(&a.layers), num_layers),
+                a.window_size.to_string(),
+                a.residency_size.to_string(),
+                a.next_instance.clone(),
+            ]
+        })
+        .collect()
+}
+
+impl crate::App {
+    pub(super) fn draw_simulate(&mut self, frame: &mut Frame, view: &SimulateView) {
+        let area = frame.area();
+        let [title_area, content_area, footer_area] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Content
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("Simulate Solver").bold().yellow().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        match view {
+            SimulateView::SelectingModel => {
+                let model_names: Vec<String> = self
+                    .available_models
+                    .iter()
+                    .map(|model| model.id.clone())
+                    .collect();
+                let selector = crate::widgets::ModelSelector::new(&model_names)
+                    .block(Block::bordered().title("Select a model to simulate the solver for"));
+                frame.render_stateful_widget(
+                    selector,
+                    content_area,
+                    &mut self.model_selector_state,
+                );
+            }
+            SimulateView::Confirming(model) => {
+                let lines = vec![
+                    Line::from(vec![
+                        "Simulate the solver for ".into(),
+                        model.clone().bold().cyan(),
+                        "?".into(),
+                    ]),
+                    Line::from(""),
+                    Line::from(
+                        "The manager keeps the proposed topology as its prepared one, \
+                        replacing the current topology (e.g. a manual plan).",
+                    ),
+                ];
+                frame.render_widget(
+                    ConfirmDialog::new("Replace Topology", lines),
+                    centered_rect(60, 40, content_area),
+                );
+            }
+            SimulateView::Solving(model) => frame.render_widget(
+                Paragraph::new(format!("Solving the topology of {}...", model))
+                    .block(Block::bordered())
+                    .centered(),
+                content_area,
+            ),
+            SimulateView::OpeningEditor => frame.render_widget(
+                Paragraph::new("Fetching shards...")
+                    .block(Block::bordered())
+                    .centered(),
+                content_area,
+            ),
+            SimulateView::Solved => {
+                let [assignments_area, solver_area] =
+                    Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                        .areas(content_area);
+                self.draw_proposal(frame, assignments_area);
+                self.draw_proposal_solver(frame, solver_area);
+            }
+            SimulateView::Error(err) => frame.render_widget(
+                Paragraph::new(format!("Error: {}", err))
+                    .block(Block::bordered())
+                    .style(Style::default().fg(Color::Red))
+                    .wrap(Wrap { trim: true }),
+                content_area,
+            ),
+        }
+
+        let footer_text = match view {
+            SimulateView::SelectingModel => {
+                "↑↓: Select model | Type to filter | Enter: Simulate | Esc: Back"
+            }
+            SimulateView::Solved => {
+                "e: Edit as a manual plan | s: Simulate another model | Esc: Back"
+            }
+            SimulateView::Confirming(_) => "y/Enter: Simulate | n/Esc: Cancel",
+            SimulateView::Solving(_) | SimulateView::OpeningEditor => "Please wait...",
+            SimulateView::Error(_) => "s: Simulate another model | Esc: Back",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    /// Draw the proposed assignments in the order of the ring.
+    fn draw_proposal(&self, frame: &mut Frame, area: Rect) {
+        let Some(topology) = &self.state.developer.simulate.topology else {
+            return;
+        };
+        let rows = proposal_rows(topology).into_iter().map(|row| {
+            let [instance, layers, window, residency, next] = row;
+            Row::new(vec![
+                Cell::from(self.config.device_name(&instance).to_string()),
+                Cell::from(layers),
+                Cell::from(window),
+                Cell::from(residency),
+                Cell::from(self.config.device_name(&next).to_string()),
+            ])
+        });
+
+        let title = format!(
+            " {}: {} layers, nothing is loaded ",
+            topology.model.as_deref().unwrap_or("?"),
+            topology.num_layers
+        );
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Percentage(35),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Percentage(25),
+            ],
+        )
+        .header(Row::new(vec!["Shard", "Layers", "Window", "Residency", "Next"]).bold())
+        .block(Block::bordered().title(title));
+        frame.render_widget(table, area);
+    }
+
+    /// Draw the details of the planner's solution, if it has one.
+    fn draw_proposal_solver(&self, frame: &mut Frame, area: Rect) {
+        let Some(topology) = &self.state.developer.simulate.topology else {
+            return;
+        };
+        let lines = match topology.solution.as_ref() {
+            None => vec![Line::from("The planner returned no solution.").dark_gray()],
+            Some(solution) => match SolverDetails::from_solution(solution) {
+                Some(details) => details.lines(|i| {
+                    topology
+                        .devices
+                        .get(i)
+                        .map(|d| self.config.device_name(&d.instance))
+                }),
+                None => vec![Line::from(solution.to_string())],
+            },
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(" Solver ")),
+            area,
+        );
+    }
+
+    pub(super) fn handle_simulate_input(&mut self, key: KeyEvent, view: &SimulateView) {
+        match view {
+            SimulateView::SelectingModel => {
+                let models = self.available_models.iter().map(|m| &m.id);
+                let num_matches = self.model_selector_state.matches(models.clone()).len();
+                match key.code {
+                    KeyCode::Esc if self.model_selector_state.filter().is_empty() => {
+                        self.view = AppView::Developer(DeveloperView::Menu);
+                    }
+                    KeyCode::Esc => self.model_selector_state.clear_filter(),
+                    KeyCode::Up => self.model_selector_state.move_up(num_matches),
+                    KeyCode::Down => self.model_selector_state.move_down(num_matches),
+                    KeyCode::Enter => {
+                        let Some(i) = self.model_selector_state.selected_item(models) else {
+                            return;
+                        };
+                        let model = self.available_models[i].id.clone();
+                        // preparing a topology replaces the one of the manager
+                        if !self.loaded_models().is_empty() {
+                            self.view =
+                                AppView::Developer(DeveloperView::Simulate(SimulateView::Error(
+                                    "Unload the model first, as simulating replaces its topology"
+                                        .to_string(),
+                                )));
+                        } else if self.topology.is_some() {
+                            self.view = AppView::Developer(DeveloperView::Simulate(
+                                SimulateView::Confirming(model),
+                            ));
+                        } else {
+                            self.view = AppView::Developer(DeveloperView::Simulate(
+                                SimulateView::Solving(model),
+                            ));
+                        }
+                    }
+                    KeyCode::Backspace => self.model_selector_state.pop_filter(),
+                    KeyCode::Char(c) => self.model_selector_state.push_filter(c),
+                    _ => {}
+                }
+            }
+            SimulateView::Solved | SimulateView::Error(_) => match key.code {
+                KeyCode::Esc => self.view = AppView::Developer(DeveloperView::Menu),
+                KeyCode::Char('s') => {
+                    self.model_selector_state.reset();
+                    self.view =
+                        AppView::Developer(DeveloperView::Simulate(SimulateView::SelectingModel));
+                }
+                KeyCode::Char('e') if *view == SimulateView::Solved => {
+                    self.view =
+                        AppView::Developer(DeveloperView::Simulate(SimulateView::OpeningEditor));
+                }
+                _ => {}
+            },
+            SimulateView::Confirming(model) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.view = AppView::Developer(DeveloperView::Simulate(SimulateView::Solving(
+                        model.clone(),
+                    )));
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.view =
+                        AppView::Developer(DeveloperView::Simulate(SimulateView::SelectingModel));
+                }
+                _ => {}
+            },
+            SimulateView::Solving(_) | SimulateView::OpeningEditor => {}
+        }
+    }
+
+    pub(super) async fn tick_simulate(&mut self, view: &SimulateView) {
+        match view {
+            SimulateView::Solving(model) => {
+                let result = self.api.prepare_topology(&self.config, model).await;
+
+                // the manager now has the proposed topology as its prepared one
+                if let Ok(topology) = self.api.get_topology().await {
+                    self.topology = topology;
+                }
+                self.view = AppView::Developer(DeveloperView::Simulate(match result {
+                    Ok(mut topology) => {
+                        topology.model.get_or_insert_with(|| model.clone());
+                        self.state.developer.simulate.topology = Some(topology);
+                        SimulateView::Solved
+                    }
+                    Err(err) => SimulateView::Error(err.to_string()),
+                }));
+            }
+            SimulateView::OpeningEditor => {
+                let Some(topology) = self.state.developer.simulate.topology.clone() else {
+                    return;
+                };
+                let result = match self.fetch_shards_with_model().await {
+                    Ok(shards) => ManualAssignmentState::from_topology(
+                        &topology,
+                        shards,
+                        self.config.excluded_devices.clone(),
+                    ),
+                    Err(err) => Err(format!("{:#?}", err)),
+                };
+
+                self.view = AppView::Developer(match result {
+                    Ok(state) => {
+                        self.state.developer.manual = state;
+                        self.state.developer.manual.probe_shards();
+                        DeveloperView::ManualAssignment(ManualAssignmentView::AssigningLayers)
+                    }
+                    Err(err) => DeveloperView::Simulate(SimulateView::Error(err)),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::AssignmentInfo;
+    use crate::config::KVBits;
+
+    #[test]
+    fn test_proposal_rows() {
+        let assignment = |instance: &str, layers: Vec<Vec<u32>>, next: &str| AssignmentInfo {
+            instance: instance.to_string(),
+            window_size: 2,
+            residency_size: 1,
+            layers,
+            next_instance: next.to_string(),
+        };
+        let topology = TopologyInfo {
+            model: Some("model".to_string()),
+            num_layers: 8,
+            devices: vec![],
+            assignments: vec![
+                assignment("b", vec![vec![2, 3], vec![6, 7]], "a"),
+                assignment("a", vec![vec![0, 1], vec![4, 5]], "b"),
+            ],
+            solution: None,
+            kv_bits: KVBits::default(),
+        };
+
+        let rows = proposal_rows(&topology);
+        assert_eq!(rows[0][0], "a");
+        assert_eq!(rows[0][1], "0-1; 4-5 (4)");
+        assert_eq!(
+            rows[1],
+            ["b", "2-3; 6-7 (4)", "2", "1", "a"].map(String::from)
+        );
+    }
+}
@@ -1,5 +1,49 @@
 use std::collections::{HashMap, HashSet};
 
+/// Number of edits that can be undone, the oldest are forgotten beyond it.
+pub const MAX_EDIT_HISTORY: usize = 100;
+
+/// History of the edits of some state, as snapshots of the state before each edit.
+#[derive(Debug)]
+pub struct EditHistory<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for EditHistory<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T> EditHistory<T> {
+    /// Record the state before an edit, which can no longer be redone past it.
+    pub fn record(&mut self, before: T) {
+        if self.undo.len() == MAX_EDIT_HISTORY {
+            self.undo.remove(0);
+        }
+        self.undo.push(before);
+        self.redo.clear();
+    }
+
+    /// State before the latest edit, if any, keeping `current` to redo the edit.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let before = self.undo.pop()?;
+        self.redo.push(current);
+        Some(before)
+    }
+
+    /// State after the latest undone edit, if any, keeping `current` to undo it again.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let after = self.redo.pop()?;
+        self.undo.push(current);
+        Some(after)
+    }
+}
+
 pub fn format_layers(layers: &[u32]) -> String {
     if layers.is_empty() {
         return "[]".to_string();
@@ -74,6 +118,43 @@ pub fn parse_layer_input(input: &str, max_layers: u32) -> Option<Vec<u32>> {
     }
 }
 
+/// Format the layers of each round, e.g. `0-3; 8-11`.
+pub fn format_rounds(rounds: &[Vec<u32>]) -> String {
+    if rounds.is_empty() {
+        return "[]".to_string();
+    }
+    rounds
+        .iter()
+        .map(|round| format_layers(round))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Parse the layers of one or more rounds separated by `;`, e.g. `0-3; 8-11`.
+///
+/// Returns `None` if a round has no layers, or if the rounds share layers.
+pub fn parse_rounds_input(input: &str, max_layers: u32) -> Option<Vec<Vec<u32>>> {
+    let rounds = input
+        .split(';')
+        .map(|round| parse_layer_input(round, max_layers))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut seen = HashSet::new();
+    if rounds.iter().flatten().all(|layer| seen.insert(*layer)) {
+        Some(rounds)
+    } else {
+        None
+    }
+}
+
+/// Parse a size in layers, e.g. a window size, which must be between 1 and `max`.
+pub fn parse_size_input(input: &str, max: u32) -> Result<u32, String> {
+    match input.trim().parse::<u32>() {
+        Ok(size) if (1..=max).contains(&size) => Ok(size),
+        _ => Err(format!("Must be between 1 and {}", max)),
+    }
+}
+
 pub fn find_missing_layers(assigned: &HashSet<u32>, total: u32) -> Vec<u32> {
     let mut missing = Vec::new();
     for i in 0..total {
@@ -84,24 +165,88 @@ pub fn find_missing_layers(assigned: &HashSet<u32>, total: u32) -> Vec<u32> {
     missing
 }
 
+/// Split the layers into `num_shards` contiguous chunks of (almost) equal sizes,
+/// the first chunks taking one more layer if they do not divide evenly.
+///
+/// There are fewer chunks if there are fewer layers than shards.
+pub fn distribute_layers(layers: &[u32], num_shards: usize) -> Vec<Vec<u32>> {
+    if num_shards == 0 {
+        return Vec::new();
+    }
+
+    let (size, extra) = (layers.len() / num_shards, layers.len() % num_shards);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    for i in 0..num_shards {
+        let end = start + size + usize::from(i < extra);
+        if end > start {
+            chunks.push(layers[start..end].to_vec());
+        }
+        start = end;
+    }
+    chunks
+}
+
+/// Split the layers into contiguous chunks, one per weight, with sizes proportional
+/// to the weights, e.g. the memory of each shard. The layers left over by rounding
+/// go to the chunks with the largest remainders, and the layers are split evenly
+/// (see [`distribute_layers`]) if all weights are zero.
+///
+/// Chunks may be empty if their weights are small compared to the others.
+pub fn distribute_layers_by_weight(layers: &[u32], weights: &[u64]) -> Vec<Vec<u32>> {
+    let total = weights.iter().map(|w| *w as u128).sum::<u128>();
+    if total == 0 {
+        let mut chunks = distribute_layers(layers, weights.len());
+        chunks.resize(weights.len(), Vec::new());
+        return chunks;
+    }
+
+    // integer shares & their remainders, scaled by the total weight
+    let num_layers = layers.len() as u128;
+    let mut sizes = weights
+        .iter()
+        .map(|w| (num_layers * *w as u128 / total) as usize)
+        .collect::<Vec<_>>();
+    let mut by_remainder = (0..weights.len()).collect::<Vec<_>>();
+    by_remainder.sort_by_key(|i| std::cmp::Reverse(num_layers * weights[*i] as u128 % total));
+    let left_over = layers.len() - sizes.iter().sum::<usize>();
+    for i in by_remainder.into_iter().take(left_over) {
+        sizes[i] += 1;
+    }
+
+    let mut start = 0;
+    sizes
+        .into_iter()
+        .map(|size| {
+            let chunk = layers[start..start + size].to_vec();
+            start += size;
+            chunk
+        })
+        .collect()
+}
+
+/// Next instance of each shard in the ring, i.e. the owner of the layer after the last
+/// layer of its first round, or the owner of the first layer for the last shard.
+///
+/// The rounds of a shard are its layers per pass through the ring, so following
+/// the first round is enough, the later ones go through the ring in the same order.
 pub fn determine_next_instances(
-    assignments: &HashMap<String, Vec<u32>>,
+    assignments: &HashMap<String, Vec<Vec<u32>>>,
 ) -> HashMap<String, String> {
     let mut next_instances = HashMap::new();
 
-    // Create a map of first_layer -> shard
+    // Create a map of layer -> shard
     let mut layer_to_shard: HashMap<u32, String> = HashMap::new();
-    for (shard, layers) in assignments {
-        if !layers.is_empty() {
-            let min_layer = *layers.iter().min().unwrap();
-            layer_to_shard.insert(min_layer, shard.clone());
+    for (shard, rounds) in assignments {
+        for layer in rounds.iter().flatten() {
+            layer_to_shard.insert(*layer, shard.clone());
         }
     }
 
     // For each shard, find its next shard
-    for (shard, layers) in assignments {
-        if !layers.is_empty() {
-            let max_layer = *layers.iter().max().unwrap();
+    for (shard, rounds) in assignments {
+        if let Some(max_layer) = rounds.first().and_then(|round| round.iter().max()) {
+            let max_layer = *max_layer;
 
             // Find the shard that has max_layer + 1
             if let Some(next_shard) = layer_to_shard.get(&(max_layer + 1)) {
@@ -117,3 +262,187 @@ pub fn determine_next_instances(
 
     next_instances
 }
+
+/// Next instances of the ring, derived from the layers (see [`determine_next_instances`])
+/// except for those set explicitly in `overrides`, which only apply between assigned shards.
+pub fn ring_next_instances(
+    assignments: &HashMap<String, Vec<Vec<u32>>>,
+    overrides: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut next_instances = determine_next_instances(assignments);
+    for (instance, next) in overrides {
+        if assignments.contains_key(instance) && assignments.contains_key(next) {
+            next_instances.insert(instance.clone(), next.clone());
+        }
+    }
+    next_instances
+}
+
+/// Check that the next instances form a single ring that goes through every instance once.
+pub fn validate_ring(next_instances: &HashMap<String, String>) -> Result<(), String> {
+    let Some(start) = next_instances.keys().min() else {
+        return Ok(());
+    };
+
+    let mut visited = HashSet::new();
+    let mut current = start;
+    while visited.insert(current) {
+        current = next_instances
+            .get(current)
+            .ok_or_else(|| format!("{} has no next shard", current))?;
+    }
+
+    if current != start {
+        Err(format!("{} is the next shard of two shards", current))
+    } else if visited.len() < next_instances.len() {
+        Err("The ring does not go through all shards".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribute_layers() {
+        let layers = (0..10).collect::<Vec<_>>();
+        assert_eq!(
+            distribute_layers(&layers, 3),
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]
+        );
+
+        // fewer layers than shards, gaps are kept in order
+        assert_eq!(distribute_layers(&[3, 7], 3), vec![vec![3], vec![7]]);
+        assert!(distribute_layers(&layers, 0).is_empty());
+    }
+
+    #[test]
+    fn test_edit_history() {
+        let mut history = EditHistory::default();
+        assert_eq!(history.undo(0), None);
+
+        history.record(0);
+        history.record(1);
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), Some(0));
+        assert_eq!(history.undo(0), None);
+        assert_eq!(history.redo(0), Some(1));
+
+        // a new edit drops what could be redone
+        history.record(1);
+        assert_eq!(history.redo(5), None);
+        assert_eq!(history.undo(5), Some(1));
+
+        for i in 0..MAX_EDIT_HISTORY + 10 {
+            history.record(i);
+        }
+        let mut num_undos = 0;
+        while history.undo(0).is_some() {
+            num_undos += 1;
+        }
+        assert_eq!(num_undos, MAX_EDIT_HISTORY);
+    }
+
+    #[test]
+    fn test_distribute_layers_by_weight() {
+        let layers = (0..10).collect::<Vec<_>>();
+        let chunks = distribute_layers_by_weight(&layers, &[64, 32, 32]);
+        assert_eq!(chunks, vec![(0..5).collect(), vec![5, 6, 7], vec![8, 9]]);
+
+        // 10 * 1/3 = 3.33 for each, so the first takes the left over layer
+        let chunks = distribute_layers_by_weight(&layers, &[1, 1, 1]);
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+
+        let chunks = distribute_layers_by_weight(&layers[..2], &[100, 1, 1]);
+        assert_eq!(chunks, vec![vec![0, 1], vec![], vec![]]);
+
+        let chunks = distribute_layers_by_weight(&layers[..1], &[0, 0]);
+        assert_eq!(chunks, vec![vec![0], vec![]]);
+    }
+
+    #[test]
+    fn test_parse_rounds_input() {
+        assert_eq!(
+            parse_rounds_input("0-3; 8-9", 12),
+            Some(vec![vec![0, 1, 2, 3], vec![8, 9]])
+        );
+        assert_eq!(parse_rounds_input("0,2", 12), Some(vec![vec![0, 2]]));
+        assert_eq!(parse_rounds_input("0-3;", 12), None);
+        assert_eq!(parse_rounds_input("0-3; 2-5", 12), None);
+        assert_eq!(format_rounds(&[vec![0, 1, 2, 3], vec![8, 9]]), "0-3; 8-9");
+    }
+
+    #[test]
+    fn test_parse_size_input() {
+        assert_eq!(parse_size_input(" 4 ", 8), Ok(4));
+        assert_eq!(parse_size_input("8", 8), Ok(8));
+        assert!(parse_size_input("9", 8).is_err());
+        assert!(parse_size_input("0", 8).is_err());
+        assert!(parse_size_input("", 8).is_err());
+    }
+
+    #[test]
+    fn test_ring_next_instances() {
+        let assignments = HashMap::from([
+            ("a".to_string(), vec![vec![0, 1]]),
+            ("b".to_string(), vec![vec![2, 3]]),
+            ("c".to_string(), vec![vec![4, 5]]),
+        ]);
+        let next = ring_next_instances(&assignments, &HashMap::new());
+        assert_eq!(next["a"], "b");
+        assert_eq!(next["c"], "a");
+        assert_eq!(validate_ring(&next), Ok(()));
+
+        // a reordered ring, the override to an unassigned shard is ignored
+        let overrides = HashMap::from([
+            ("a".to_string(), "c".to_string()),
+            ("c".to_string(), "b".to_string()),
+            ("b".to_string(), "a".to_string()),
+            ("d".to_string(), "a".to_string()),
+        ]);
+        let next = ring_next_instances(&assignments, &overrides);
+        assert_eq!(next["a"], "c");
+        assert_eq!(next.len(), 3);
+        assert_eq!(validate_ring(&next), Ok(()));
+
+        // b is pointed to by both a and c
+        let overrides = HashMap::from([("c".to_string(), "b".to_string())]);
+        let next = ring_next_instances(&assignments, &overrides);
+        assert!(validate_ring(&next).is_err());
+
+        // two separate rings
+        let overrides = HashMap::from([
+            ("a".to_string(), "a".to_string()),
+            ("c".to_string(), "b".to_string()),
+        ]);
+        let next = ring_next_instances(&assignments, &overrides);
+        assert!(validate_ring(&next).is_err());
+    }
+
+    #[test]
+    fn test_determine_next_instances_of_rounds() {
+        let assignments = HashMap::from([
+            ("a".to_string(), vec![vec![0, 1], vec![4, 5]]),
+            ("b".to_string(), vec![vec![2, 3], vec![6, 7]]),
+        ]);
+        let next = determine_next_instances(&assignments);
+        assert_eq!(next["a"], "b");
+        assert_eq!(next["b"], "a");
+        assert_eq!(validate_ring(&next), Ok(()));
+
+        let assignments = HashMap::from([
+            ("a".to_string(), vec![vec![0], vec![3]]),
+            ("b".to_string(), vec![vec![1], vec![4]]),
+            ("c".to_string(), vec![vec![2], vec![5]]),
+        ]);
+        let next = determine_next_instances(&assignments);
+        assert_eq!(next["a"], "b");
+        assert_eq!(next["b"], "c");
+        assert_eq!(next["c"], "a");
+    }
+}
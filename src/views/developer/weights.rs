@@ -0,0 +1,348 @@
+use super::DeveloperView;
+use crate::AppView;
+use crate::common::DeviceProperties;
+use crate::utils::{centered_rect, format_bytes};
+use crate::views::topology::ShardView;
+use crate::widgets::ConfirmDialog;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeightsView {
+    SelectingModel,
+    FetchingShards,
+    /// Picking the shards to delete the weights from, one by one.
+    SelectingShards,
+    Deleting,
+    /// Results of the deletion on each shard.
+    Done,
+    Error(String),
+}
+
+/// Deletion of the cached weights of a model from the shards.
+#[derive(Debug, Default)]
+pub struct WeightsState {
+    pub model: String,
+    /// Shards along with whether their weights are to be deleted.
+    pub shards: Vec<(DeviceProperties, bool)>,
+    pub selected: usize,
+    /// Whether the confirmation dialog is open.
+    pub confirm: bool,
+    /// Result of the deletion on each shard by instance, with the freed bytes if known.
+    pub results: Vec<(String, Result<Option<u64>, String>)>,
+}
+
+impl WeightsState {
+    /// Shards whose weights are to be deleted.
+    pub fn checked_shards(&self) -> impl Iterator<Item = &DeviceProperties> {
+        self.shards
+            .iter()
+            .filter(|(_, checked)| *checked)
+            .map(|(device, _)| device)
+    }
+
+    /// Check the selected shard, or uncheck it.
+    pub fn toggle_selected(&mut self) {
+        if let Some((_, checked)) = self.shards.get_mut(self.selected) {
+            *checked = !*checked;
+        }
+    }
+
+    /// Total bytes freed over the shards, along with the number of shards that failed.
+    pub fn freed(&self) -> (u64, usize) {
+        self.results
+            .iter()
+            .fold((0, 0), |(freed, failed), (_, result)| match result {
+                Ok(bytes) => (freed + bytes.unwrap_or_default(), failed),
+                Err(_) => (freed, failed + 1),
+            })
+    }
+}
+
+impl crate::App {
+    pub(super) fn draw_weights(&mut self, frame: &mut Frame, view: &WeightsView) {
+        let area = frame.area();
+        let [title_area, content_area, footer_area] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Content
+            Constraint::Length(2), // Footer
+        ])
+        .areas(area);
+
+        let title = Line::from("Delete Model Weights")
+            .bold()
+            .yellow()
+            .centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        match view {
+            WeightsView::SelectingModel => {
+                let model_names: Vec<String> = self
+                    .available_models
+                    .iter()
+                    .map(|model| model.id.clone())
+                    .collect();
+                let selector = crate::widgets::ModelSelector::new(&model_names)
+                    .block(Block::bordered().title("Select a model to delete the weights of"));
+                frame.render_stateful_widget(
+                    selector,
+                    content_area,
+                    &mut self.model_selector_state,
+                );
+            }
+            WeightsView::FetchingShards | WeightsView::Deleting => {
+                let message = match view {
+                    WeightsView::FetchingShards => "Fetching shards...".to_string(),
+                    _ => format!(
+                        "Deleting the weights of {}...",
+                        self.state.developer.weights.model
+                    ),
+                };
+                frame.render_widget(
+                    Paragraph::new(message).block(Block::bordered()).centered(),
+                    content_area,
+                );
+            }
+            WeightsView::SelectingShards => self.draw_weights_shards(frame, content_area),
+            WeightsView::Done => self.draw_weights_results(frame, content_area),
+            WeightsView::Error(err) => {
+                frame.render_widget(
+                    Paragraph::new(format!("Error: {}", err))
+                        .block(Block::bordered())
+                        .style(Style::default().fg(Color::Red))
+                        .centered(),
+                    content_area,
+                );
+            }
+        }
+
+        let footer_text = match view {
+            WeightsView::SelectingModel => {
+                "↑↓: Select model | Type to filter | Enter: Continue | Esc: Back"
+            }
+            WeightsView::SelectingShards => {
+                "↑↓: Navigate | Space: Check/uncheck shard | Enter: Delete | Esc: Back"
+            }
+            WeightsView::Done | WeightsView::Error(_) => "Press Esc to go back",
+            WeightsView::FetchingShards | WeightsView::Deleting => "Please wait...",
+        };
+        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+    }
+
+    fn draw_weights_shards(&self, frame: &mut Frame, area: Rect) {
+        let state = &self.state.developer.weights;
+        let items = state
+            .shards
+            .iter()
+            .map(|(device, checked)| {
+                let checkbox = if *checked { "[x]" } else { "[ ]" };
+                ListItem::new(format!(
+                    "  {} {:<24} {}:{}",
+                    checkbox,
+                    self.config.device_name(&device.instance),
+                    device.local_ip,
+                    device.server_port
+                ))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!("Shards caching {}", state.model)))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut list_state = ListState::default().with_selected(Some(state.selected));
+        frame.render_stateful_widget(list, area, &mut list_state);
+
+        if state.confirm {
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(format!("Delete the weights of {} from:", state.model)).bold(),
+                Line::from(""),
+            ];
+            for device in state.checked_shards() {
+                lines.push(Line::from(format!(
+                    "  • {}",
+                    self.config.device_name(&device.instance)
+                )));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(
+                "The weights are downloaded again on the next load.",
+            ));
+            frame.render_widget(
+                ConfirmDialog::new("Delete Weights", lines),
+                centered_rect(60, 50, area),
+            );
+        }
+    }
+
+    fn draw_weights_results(&self, frame: &mut Frame, area: Rect) {
+        let state = &self.state.developer.weights;
+        let (freed, failed) = state.freed();
+        let mut lines = vec![
+            Line::from(format!(
+                "Freed {} over {} shards, {} failed",
+                format_bytes(freed),
+                state.results.len() - failed,
+                failed
+            ))
+            .bold(),
+            Line::from(""),
+        ];
+        for (instance, result) in &state.results {
+            let name = self.config.device_name(instance);
+            lines.push(match result {
+                Ok(Some(bytes)) => {
+                    Line::from(format!("  ✓ {}: freed {}", name, format_bytes(*bytes))).green()
+                }
+                Ok(None) => Line::from(format!("  ✓ {}: deleted", name)).green(),
+                Err(err) => Line::from(format!("  ✗ {}: {}", name, err)).red(),
+            });
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Results")),
+            area,
+        );
+    }
+
+    pub(super) fn handle_weights_input(&mut self, key: KeyEvent, view: &WeightsView) {
+        let state = &mut self.state.developer.weights;
+        match view {
+            WeightsView::SelectingModel => {
+                let models = self.available_models.iter().map(|m| &m.id);
+                let num_matches = self.model_selector_state.matches(models.clone()).len();
+                match key.code {
+                    KeyCode::Esc if self.model_selector_state.filter().is_empty() => {
+                        self.view = AppView::Developer(DeveloperView::Menu);
+                    }
+                    KeyCode::Esc => self.model_selector_state.clear_filter(),
+                    KeyCode::Up => self.model_selector_state.move_up(num_matches),
+                    KeyCode::Down => self.model_selector_state.move_down(num_matches),
+                    KeyCode::Enter => {
+                        if let Some(i) = self.model_selector_state.selected_item(models) {
+                            state.model = self.available_models[i].id.clone();
+                            self.view = AppView::Developer(DeveloperView::Weights(
+                                WeightsView::FetchingShards,
+                            ));
+                        }
+                    }
+                    KeyCode::Backspace => self.model_selector_state.pop_filter(),
+                    KeyCode::Char(c) => self.model_selector_state.push_filter(c),
+                    _ => {}
+                }
+            }
+            WeightsView::SelectingShards if state.confirm => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    state.confirm = false;
+                    self.view = AppView::Developer(DeveloperView::Weights(WeightsView::Deleting));
+                }
+                KeyCode::Char('n') | KeyCode::Esc => state.confirm = false,
+                _ => {}
+            },
+            WeightsView::SelectingShards => match key.code {
+                KeyCode::Esc => {
+                    self.view =
+                        AppView::Developer(DeveloperView::Weights(WeightsView::SelectingModel));
+                }
+                KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+                KeyCode::Down => {
+                    state.selected = (state.selected + 1).min(state.shards.len().saturating_sub(1))
+                }
+                KeyCode::Char(' ') => state.toggle_selected(),
+                KeyCode::Enter if state.checked_shards().next().is_some() => state.confirm = true,
+                _ => {}
+            },
+            WeightsView::Done | WeightsView::Error(_) => {
+                if key.code == KeyCode::Esc {
+                    self.view = AppView::Developer(DeveloperView::Menu);
+                }
+            }
+            WeightsView::FetchingShards | WeightsView::Deleting => {}
+        }
+    }
+
+    pub(super) async fn tick_weights(&mut self, view: &WeightsView) {
+        let state = &mut self.state.developer.weights;
+        match view {
+            WeightsView::FetchingShards => match self.api.get_devices().await {
+                Ok(devices) => {
+                    let mut shards = devices
+                        .into_values()
+                        .filter(|d| !d.is_manager)
+                        .map(|d| (d, true))
+                        .collect::<Vec<_>>();
+                    shards.sort_by(|(a, _), (b, _)| a.instance.cmp(&b.instance));
+                    state.shards = shards;
+                    state.selected = 0;
+                    self.view =
+                        AppView::Developer(DeveloperView::Weights(WeightsView::SelectingShards));
+                }
+                Err(err) => {
+                    self.view = AppView::Developer(DeveloperView::Weights(WeightsView::Error(
+                        err.to_string(),
+                    )));
+                }
+            },
+            WeightsView::Deleting => {
+                let model = state.model.clone();
+                let deletions = state.checked_shards().cloned().map(|device| {
+                    let model = model.clone();
+                    async move {
+                        let result =
+                            ShardView::delete_weights(&device.local_ip, device.server_port, &model)
+                                .await;
+                        (device.instance, result)
+                    }
+                });
+                state.results = futures::future::join_all(deletions).await;
+                self.view = AppView::Developer(DeveloperView::Weights(WeightsView::Done));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(instance: &str) -> DeviceProperties {
+        DeviceProperties {
+            is_manager: false,
+            is_busy: false,
+            instance: instance.to_string(),
+            server_port: 8081,
+            shard_port: 58081,
+            local_ip: "10.0.0.1".to_string(),
+            thunderbolt: None,
+        }
+    }
+
+    #[test]
+    fn test_weights_state() {
+        let mut state = WeightsState {
+            shards: vec![(device("a"), true), (device("b"), true)],
+            selected: 1,
+            ..Default::default()
+        };
+        state.toggle_selected();
+        let checked = state.checked_shards().map(|d| d.instance.as_str());
+        assert_eq!(checked.collect::<Vec<_>>(), vec!["a"]);
+
+        state.results = vec![
+            ("a".to_string(), Ok(Some(1024))),
+            ("b".to_string(), Ok(None)),
+            ("c".to_string(), Err("unreachable".to_string())),
+        ];
+        assert_eq!(state.freed(), (1024, 1));
+    }
+}
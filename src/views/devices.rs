@@ -1,20 +1,67 @@
-use crate::common::DeviceProperties;
+use crate::common::{DeviceProperties, ShardHealth, ShardResources, thunderbolt_links};
+use crate::utils::{centered_rect, format_bytes};
+use crate::views::topology::{KvCacheEstimate, LatencyProbe, ShardView, layers_that_fit};
 use crate::{App, app::AppView};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
+    symbols,
     text::Line,
-    widgets::{Block, Cell, Paragraph, Row, Table},
+    widgets::{Block, Cell, Clear, LineGauge, Paragraph, Row, Table, TableState},
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
+use tui_input::backend::crossterm::EventHandler;
+
+/// Frames of the spinner shown while the devices are being refreshed.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How long a device that went missing is still listed, after it was last seen.
+const STALE_TTL: Duration = Duration::from_secs(300);
 
 #[derive(Debug)]
 pub struct DevicesState {
     /// Last time we refreshed devices.
     pub refreshed_at: Instant,
+    /// Wall-clock time of the last successful refresh, shown in the title.
+    pub refreshed_time: Option<chrono::DateTime<chrono::Local>>,
+    /// Whether a refresh was requested by the user, ahead of the interval.
+    pub refresh_requested: bool,
+    /// Column that the table is sorted by.
+    pub sort_column: DeviceColumn,
+    /// Whether the table is sorted in descending order.
+    pub sort_descending: bool,
+    /// Selected row of the table, see [`device_rows`].
+    pub selected: usize,
+    /// Hosts (i.e. IP addresses) whose instances are hidden in the table.
+    pub collapsed_hosts: HashSet<String>,
+    /// Probes of the devices, for the memory of their hosts.
+    pub probe: LatencyProbe,
+    /// Model to check whether the devices can hold a layer of, if any.
+    pub model: Option<String>,
+    /// Memory estimate of a layer of the model to check.
+    pub layer_estimate: KvCacheEstimate,
+    /// Last time we fetched the health of the device in the details.
+    pub health_refreshed_at: Instant,
+    /// Whether the thunderbolt matrix is shown instead of the table.
+    pub show_thunderbolt: bool,
+    /// Devices seen within [`STALE_TTL`] by key, along with when they were last seen.
+    pub seen: HashMap<String, (DeviceProperties, Instant)>,
+    /// When the devices of the latest refresh were seen, see [`DevicesState::track`].
+    pub seen_at: Instant,
+    /// Devices marked to be compared by key, at most two with the oldest mark dropped.
+    pub marked: Vec<String>,
+    /// Form to register a device manually, if open.
+    pub form: Option<DeviceForm>,
+    /// Whether the devices were discovered over mDNS rather than listed by the API.
+    pub is_discovered: bool,
+    /// Discovery of the devices on the local network, when the API is offline.
+    #[cfg(feature = "mdns")]
+    pub discovery: crate::common::MdnsDiscovery,
 }
 
 impl Default for DevicesState {
@@ -22,14 +69,505 @@ impl Default for DevicesState {
         Self {
             // make this older to trigger immediate refresh
             refreshed_at: Instant::now() - Duration::from_secs(10),
+            refreshed_time: None,
+            refresh_requested: false,
+            sort_column: DeviceColumn::default(),
+            sort_descending: false,
+            selected: 0,
+            collapsed_hosts: HashSet::new(),
+            probe: LatencyProbe::default(),
+            model: None,
+            layer_estimate: KvCacheEstimate::default(),
+            health_refreshed_at: Instant::now(),
+            show_thunderbolt: false,
+            seen: HashMap::new(),
+            seen_at: Instant::now(),
+            marked: Vec::new(),
+            form: None,
+            is_discovered: false,
+            #[cfg(feature = "mdns")]
+            discovery: Default::default(),
+        }
+    }
+}
+
+impl DevicesState {
+    /// Record the devices of a refresh, forgetting those that have been missing for
+    /// longer than [`STALE_TTL`].
+    pub fn track(&mut self, devices: &HashMap<String, DeviceProperties>, now: Instant) {
+        for (key, device) in devices {
+            self.seen.insert(key.clone(), (device.clone(), now));
         }
+        self.seen_at = now;
+        self.seen
+            .retain(|_, (_, seen_at)| now.duration_since(*seen_at) <= STALE_TTL);
+    }
+
+    /// Devices to list in the table, i.e. those of the latest refresh along with the
+    /// ones that were seen before but are missing now, so that those do not silently
+    /// disappear.
+    pub fn listed(
+        &self,
+        devices: &HashMap<String, DeviceProperties>,
+    ) -> HashMap<String, DeviceProperties> {
+        let stale = self
+            .seen
+            .iter()
+            .filter(|(key, (_, seen_at))| *seen_at < self.seen_at && !devices.contains_key(*key))
+            .map(|(key, (device, _))| (key.clone(), device.clone()));
+        devices.clone().into_iter().chain(stale).collect()
     }
+
+    /// How long ago the device was last seen, if it is missing from the latest refresh.
+    pub fn last_seen(&self, key: &str) -> Option<Duration> {
+        let (_, seen_at) = self.seen.get(key)?;
+        (*seen_at < self.seen_at).then(|| seen_at.elapsed())
+    }
+
+    /// Whether the devices (or their resources) are being refreshed.
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_requested || self.is_discovering() || self.probe.is_pending()
+    }
+
+    /// Whether the devices are being discovered over mDNS.
+    pub fn is_discovering(&self) -> bool {
+        #[cfg(feature = "mdns")]
+        return self.discovery.is_pending();
+        #[cfg(not(feature = "mdns"))]
+        false
+    }
+}
+
+/// Column of the devices table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeviceColumn {
+    Instance,
+    /// Discovery name of the device, i.e. its key in the devices response.
+    Host,
+    #[default]
+    Ip,
+    HttpPort,
+    GrpcPort,
+    Manager,
+    Busy,
 }
+
+impl DeviceColumn {
+    pub const ALL: [DeviceColumn; 7] = [
+        DeviceColumn::Instance,
+        DeviceColumn::Host,
+        DeviceColumn::Ip,
+        DeviceColumn::HttpPort,
+        DeviceColumn::GrpcPort,
+        DeviceColumn::Manager,
+        DeviceColumn::Busy,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DeviceColumn::Instance => "Instance",
+            DeviceColumn::Host => "Host",
+            DeviceColumn::Ip => "IP Address",
+            DeviceColumn::HttpPort => "HTTP Port",
+            DeviceColumn::GrpcPort => "gRPC Port",
+            DeviceColumn::Manager => "Manager",
+            DeviceColumn::Busy => "Busy",
+        }
+    }
+
+    /// The column to the right (or left) of this one, wrapping around.
+    fn next(self, forward: bool) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|c| *c == self)
+            .unwrap_or_default();
+        let n = Self::ALL.len();
+        Self::ALL[if forward {
+            (i + 1) % n
+        } else {
+            (i + n - 1) % n
+        }]
+    }
+}
+
+/// Devices (along with their keys) sorted by the given column, ties are broken
+/// by the IP address and the HTTP port so that the order is stable across refreshes.
+pub fn sort_devices(
+    devices: &HashMap<String, DeviceProperties>,
+    column: DeviceColumn,
+    descending: bool,
+) -> Vec<(&String, &DeviceProperties)> {
+    // addresses are compared numerically, those that are not IPs (e.g. hostnames) go first
+    let address = |device: &DeviceProperties| {
+        (
+            device.local_ip.parse::<IpAddr>().ok(),
+            device.local_ip.clone(),
+            device.server_port,
+        )
+    };
+    let mut devices = devices.iter().collect::<Vec<_>>();
+    devices.sort_by(|(key_a, a), (key_b, b)| {
+        let ordering = match column {
+            DeviceColumn::Instance => a.instance.cmp(&b.instance),
+            DeviceColumn::Host => key_a.cmp(key_b),
+            DeviceColumn::Ip => std::cmp::Ordering::Equal,
+            DeviceColumn::HttpPort => a.server_port.cmp(&b.server_port),
+            DeviceColumn::GrpcPort => a.shard_port.cmp(&b.shard_port),
+            DeviceColumn::Manager => a.is_manager.cmp(&b.is_manager),
+            DeviceColumn::Busy => a.is_busy.cmp(&b.is_busy),
+        }
+        .then_with(|| address(a).cmp(&address(b)));
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    devices
+}
+/// Gauge of the used memory or disk of a device, which turns yellow
+/// and then red as it fills up, or is empty if the usage is not reported.
+fn usage_gauge(name: &str, used: Option<u64>, total: Option<u64>) -> LineGauge<'static> {
+    let (ratio, label) = match (used, total) {
+        (Some(used), Some(total)) if total > 0 => {
+            let ratio = (used as f64 / total as f64).min(1.0);
+            let label = format!(
+                "{}: {} / {} ({:.0}%)",
+                name,
+                format_bytes(used),
+                format_bytes(total),
+                ratio * 100.0
+            );
+            (ratio, label)
+        }
+        _ => (0.0, format!("{}: not reported", name)),
+    };
+    let color = match ratio {
+        r if r >= 0.9 => Color::Red,
+        r if r >= 0.75 => Color::Yellow,
+        _ => Color::Green,
+    };
+
+    LineGauge::default()
+        .ratio(ratio)
+        .label(label)
+        .filled_style(Style::default().fg(color))
+        .unfilled_style(Style::default().fg(Color::DarkGray))
+        .line_set(symbols::line::THICK)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DevicesView {
     Loading,
     Loaded(HashMap<String, DeviceProperties>),
     Error(String),
+    /// Details of a device, which do not need a topology unlike the shard view.
+    Detail(Box<DeviceDetail>),
+    /// Registering a device with the manager, see [`DeviceForm`].
+    Registering(DeviceProperties),
+    /// Two marked devices side by side.
+    Compare(Box<DeviceComparison>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceComparison {
+    pub devices: [DeviceProperties; 2],
+    /// Health of the devices' shards, fetched live.
+    pub health: [ShardView; 2],
+}
+
+/// Fields of two devices and of their health aligned by name, with `-` for what
+/// is not known, e.g. the health of a shard that could not be reached.
+pub fn comparison_rows(
+    devices: [&DeviceProperties; 2],
+    health: [Option<&ShardHealth>; 2],
+) -> Vec<(&'static str, [String; 2])> {
+    let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+    let bytes = |value: Option<u64>| value.map_or_else(|| "-".to_string(), format_bytes);
+    let of_health =
+        |f: &dyn Fn(&ShardHealth) -> String| health.map(|h| h.map_or_else(|| "-".to_string(), f));
+
+    vec![
+        ("Instance", devices.map(|d| d.instance.clone())),
+        ("IP Address", devices.map(|d| d.local_ip.clone())),
+        ("HTTP Port", devices.map(|d| d.server_port.to_string())),
+        ("gRPC Port", devices.map(|d| d.shard_port.to_string())),
+        ("Manager", devices.map(|d| yes_no(d.is_manager))),
+        ("Busy", devices.map(|d| yes_no(d.is_busy))),
+        (
+            "Thunderbolt",
+            devices.map(|d| {
+                d.thunderbolt
+                    .as_ref()
+                    .map_or("-", |tb| &tb.ip_addr)
+                    .to_string()
+            }),
+        ),
+        ("Status", of_health(&|h| h.status.clone())),
+        ("Model Loaded", of_health(&|h| yes_no(h.model_loaded))),
+        (
+            "Layers",
+            of_health(&|h| crate::views::developer::utils::format_layers(&h.assigned_layers)),
+        ),
+        ("Queue Size", of_health(&|h| h.queue_size.to_string())),
+        (
+            "Memory Used",
+            of_health(&|h| bytes(h.resources.memory_used)),
+        ),
+        (
+            "Memory Total",
+            of_health(&|h| bytes(h.resources.memory_total)),
+        ),
+        ("Disk Used", of_health(&|h| bytes(h.resources.disk_used))),
+        ("Disk Total", of_health(&|h| bytes(h.resources.disk_total))),
+        ("Weights", of_health(&|h| bytes(h.resources.weights_bytes))),
+        (
+            "KV Cache",
+            of_health(&|h| bytes(h.resources.kv_cache_bytes)),
+        ),
+    ]
+}
+
+/// Form to register a device with the manager by its address, for the devices
+/// that are not discovered, e.g. because mDNS does not cross subnets.
+#[derive(Debug, Default)]
+pub struct DeviceForm {
+    /// Inputs of the fields, in the order of [`DeviceForm::FIELDS`].
+    pub inputs: [tui_input::Input; 4],
+    /// Index of the field that has the focus.
+    pub focus: usize,
+}
+
+impl DeviceForm {
+    pub const FIELDS: [&str; 4] = [
+        "IP Address",
+        "HTTP Port",
+        "gRPC Port",
+        "Instance (optional)",
+    ];
+
+    /// Device of the form, whose instance defaults to its address.
+    pub fn to_device(&self) -> Result<DeviceProperties, String> {
+        let [ip, server_port, shard_port, instance] =
+            self.inputs.each_ref().map(|i| i.value().trim());
+        let ip = ip
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("Invalid IP address: {:?}", ip))?;
+        let port = |value: &str, name: &str| match value.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port),
+            _ => Err(format!("Invalid {}: {:?}", name, value)),
+        };
+        let server_port = port(server_port, "HTTP port")?;
+        let shard_port = port(shard_port, "gRPC port")?;
+        let instance = if instance.is_empty() {
+            format!("{}:{}", ip, server_port)
+        } else {
+            instance.to_string()
+        };
+
+        Ok(DeviceProperties {
+            is_manager: false,
+            is_busy: false,
+            instance,
+            server_port,
+            shard_port,
+            local_ip: ip.to_string(),
+            thunderbolt: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceDetail {
+    pub device: DeviceProperties,
+    /// Thunderbolt connections of the device, see [`thunderbolt_peers`].
+    pub peers: Vec<(String, String)>,
+    /// Health of the device's shard, fetched live.
+    pub health: ShardView,
+}
+
+/// Aggregate counts of the devices, shown above the table.
+#[derive(Debug, Default, PartialEq)]
+pub struct DevicesSummary {
+    pub num_devices: usize,
+    pub num_shards: usize,
+    pub num_managers: usize,
+    /// Devices that responded to the latest probe.
+    pub num_reachable: usize,
+    /// Devices that did not respond to the latest probe.
+    pub num_unreachable: usize,
+    /// Memory used by the devices that reported it, in bytes.
+    pub memory_used: u64,
+    /// Memory of the hosts that reported it, counting each host (i.e. IP) once.
+    pub memory_total: u64,
+}
+
+impl DevicesSummary {
+    pub fn new(devices: &HashMap<String, DeviceProperties>, probe: &LatencyProbe) -> Self {
+        let mut summary = Self {
+            num_devices: devices.len(),
+            ..Default::default()
+        };
+        let mut host_memory = HashMap::new();
+        for device in devices.values() {
+            if device.is_manager {
+                summary.num_managers += 1;
+            } else {
+                summary.num_shards += 1;
+            }
+            match probe.get(&device.instance) {
+                Some(Ok(_)) => summary.num_reachable += 1,
+                Some(Err(_)) => summary.num_unreachable += 1,
+                None => {}
+            }
+            if let Some(resources) = probe.get_resources(&device.instance) {
+                summary.memory_used += resources.memory_used.unwrap_or_default();
+                // instances share the memory of the host
+                let total = host_memory.entry(device.local_ip.as_str()).or_default();
+                *total = resources.memory_total.unwrap_or_default().max(*total);
+            }
+        }
+        summary.memory_total = host_memory.values().sum();
+        summary
+    }
+}
+
+/// Row of the devices table.
+#[derive(Debug, PartialEq)]
+pub enum DeviceRow<'a> {
+    /// Header of a host (i.e. an IP address) that runs several instances.
+    Host {
+        ip: &'a str,
+        instances: Vec<&'a str>,
+        collapsed: bool,
+    },
+    /// A device, along with its key.
+    Device(&'a String, &'a DeviceProperties),
+}
+
+/// Rows of the sorted devices, where the instances that run on the same host are
+/// grouped under a header of the host (in place of the first of them), and hidden
+/// if the host is collapsed. Hosts with a single instance are not grouped.
+pub fn device_rows<'a>(
+    sorted: &[(&'a String, &'a DeviceProperties)],
+    collapsed_hosts: &HashSet<String>,
+) -> Vec<DeviceRow<'a>> {
+    let mut hosts: HashMap<&str, Vec<_>> = HashMap::new();
+    for (key, device) in sorted {
+        hosts
+            .entry(device.local_ip.as_str())
+            .or_default()
+            .push((*key, *device));
+    }
+
+    let mut rows = Vec::new();
+    for (key, device) in sorted {
+        let ip = device.local_ip.as_str();
+        match hosts.remove(ip) {
+            Some(group) if group.len() > 1 => {
+                let collapsed = collapsed_hosts.contains(ip);
+                rows.push(DeviceRow::Host {
+                    ip,
+                    instances: group.iter().map(|(_, d)| d.instance.as_str()).collect(),
+                    collapsed,
+                });
+                if !collapsed {
+                    rows.extend(group.into_iter().map(|(k, d)| DeviceRow::Device(k, d)));
+                }
+            }
+            Some(_) => rows.push(DeviceRow::Device(key, device)),
+            // already listed under its host
+            None => {}
+        }
+    }
+    rows
+}
+
+/// Render the devices as CSV with a header, one row per device in the given order.
+///
+/// Thunderbolt connections do not fit into a row, so only the thunderbolt IP is included.
+pub fn devices_to_csv(devices: &[(&String, &DeviceProperties)]) -> String {
+    let escape = |field: &str| {
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+
+    let mut csv = String::from(
+        "key,instance,local_ip,server_port,shard_port,is_manager,is_busy,thunderbolt_ip\n",
+    );
+    for (key, device) in devices {
+        let thunderbolt_ip = device.thunderbolt.as_ref().map(|tb| tb.ip_addr.as_str());
+        let fields = [
+            escape(key),
+            escape(&device.instance),
+            escape(&device.local_ip),
+            device.server_port.to_string(),
+            device.shard_port.to_string(),
+            device.is_manager.to_string(),
+            device.is_busy.to_string(),
+            escape(thunderbolt_ip.unwrap_or_default()),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write the devices to a timestamped file in the current directory, as CSV
+/// or as pretty JSON with all properties, returning the path of the written file.
+pub fn export_devices(
+    devices: &HashMap<String, DeviceProperties>,
+    as_csv: bool,
+) -> color_eyre::Result<PathBuf> {
+    let (content, extension) = if as_csv {
+        let sorted = sort_devices(devices, DeviceColumn::default(), false);
+        (devices_to_csv(&sorted), "csv")
+    } else {
+        // sorted by key, so that exports are easy to diff
+        let devices = devices.iter().collect::<BTreeMap<_, _>>();
+        (serde_json::to_string_pretty(&devices)?, "json")
+    };
+
+    let path = PathBuf::from(format!(
+        "dnet-devices-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        extension
+    ));
+    std::fs::write(&path, content)?;
+
+    Ok(path)
+}
+
+/// Thunderbolt connections of the device as `(own connection, peer)` pairs, where
+/// the peer is the instance of the discovered device that owns the connected
+/// thunderbolt instance (matched by the domain UUID), or its name and model otherwise.
+pub fn thunderbolt_peers(
+    device: &DeviceProperties,
+    devices: &HashMap<String, DeviceProperties>,
+) -> Vec<(String, String)> {
+    let Some(thunderbolt) = &device.thunderbolt else {
+        return Vec::new();
+    };
+    let owner = |uuid: &str| {
+        devices
+            .values()
+            .find(|d| d.instance != device.instance && d.owns_thunderbolt(uuid))
+    };
+
+    let mut peers = Vec::new();
+    for (own, connected) in &thunderbolt.instances {
+        for peer in connected {
+            let name = match owner(&peer.uuid) {
+                Some(owner) => owner.instance.clone(),
+                None => format!("{} ({})", peer.name, peer.device),
+            };
+            peers.push((own.name.clone(), name));
+        }
+    }
+    peers
 }
 
 impl App {
@@ -44,16 +582,50 @@ impl App {
         let [title_area, content_area, footer_area] = vertical.areas(area);
 
         // Title
-        let title = Line::from("Discovered Devices").bold().cyan().centered();
-        frame.render_widget(Paragraph::new(title), title_area);
+        let title = match view {
+            DevicesView::Detail(detail) => Line::from(format!(
+                "Device: {}",
+                self.config.device_name(&detail.device.instance)
+            )),
+            DevicesView::Compare(comparison) => Line::from(format!(
+                "Compare: {} vs {}",
+                self.config.device_name(&comparison.devices[0].instance),
+                self.config.device_name(&comparison.devices[1].instance)
+            )),
+            _ if self.state.devices.is_discovered => Line::from("Discovered Devices (mDNS)"),
+            _ => Line::from("Discovered Devices"),
+        };
+        let mut title = vec![title.bold().cyan()];
+        if !matches!(view, DevicesView::Detail(_) | DevicesView::Compare(_)) {
+            let devices = &self.state.devices;
+            let mut status = match devices.refreshed_time {
+                Some(time) => format!("Last refreshed {}", time.format("%H:%M:%S")),
+                None => "Not refreshed yet".to_string(),
+            };
+            if devices.is_refreshing() {
+                let step = devices.refreshed_at.elapsed().as_millis() / 100;
+                status = format!(
+                    "{} Refreshing...  |  {}",
+                    SPINNER[step as usize % SPINNER.len()],
+                    status
+                );
+            }
+            title.push(Line::from(status).dark_gray());
+        }
+        frame.render_widget(Paragraph::new(title).centered(), title_area);
 
         // Content
         match view {
-            DevicesView::Loading => {
+            DevicesView::Loading | DevicesView::Registering(_) => {
+                let message = if matches!(view, DevicesView::Registering(_)) {
+                    "Registering device..."
+                } else if self.state.devices.is_discovering() {
+                    "API is offline, discovering devices on the local network..."
+                } else {
+                    "Loading devices..."
+                };
                 frame.render_widget(
-                    Paragraph::new("Loading devices...")
-                        .block(Block::bordered())
-                        .centered(),
+                    Paragraph::new(message).block(Block::bordered()).centered(),
                     content_area,
                 );
             }
@@ -82,15 +654,33 @@ impl App {
                             .centered(),
                         content_area,
                     );
+                } else if self.state.devices.show_thunderbolt {
+                    self.draw_thunderbolt_matrix(frame, content_area, devices);
                 } else {
                     self.draw_devices_list(frame, content_area, devices);
                 }
             }
+            DevicesView::Detail(detail) => self.draw_device_detail(frame, content_area, detail),
+            DevicesView::Compare(comparison) => {
+                self.draw_device_comparison(frame, content_area, comparison)
+            }
         }
+        self.draw_device_form(frame, content_area);
 
         // Footer
+        let hint = match view {
+            DevicesView::Detail(_) | DevicesView::Compare(_) => "Esc to go back to the devices",
+            _ => {
+                "↑↓ to select  |  Enter for details (or to collapse a host)  |  m to check a model  |  a to add a device  |  x to exclude a device  |  c to mark & v to compare two devices  |  e/E to export CSV/JSON  |  t to toggle thunderbolt matrix  |  ←→ to change the sort column  |  r to refresh  |  o to reverse the order  |  Esc to go back"
+            }
+        };
+        let status = if self.status_message.starts_with("Error") {
+            Line::from(self.status_message.as_str()).red()
+        } else {
+            Line::from(self.status_message.as_str()).green()
+        };
         frame.render_widget(
-            Paragraph::new("Press Esc to go back").centered().gray(),
+            Paragraph::new(vec![Line::from(hint).gray(), status]).centered(),
             footer_area,
         );
     }
@@ -101,27 +691,87 @@ impl App {
         area: ratatui::layout::Rect,
         devices: &HashMap<String, DeviceProperties>,
     ) {
-        // Convert HashMap to Vec and sort by local IP
-        let mut devices_vec: Vec<(&String, &DeviceProperties)> = devices.iter().collect();
-        devices_vec.sort_by(|a, b| {
-            format!("{}:{}", a.1.local_ip, a.1.server_port)
-                .cmp(&format!("{}:{}", b.1.local_ip, b.1.server_port))
-        });
+        let (sort_column, sort_descending) = (
+            self.state.devices.sort_column,
+            self.state.devices.sort_descending,
+        );
+        let listed = self.state.devices.listed(devices);
+        let devices_vec = sort_devices(&listed, sort_column, sort_descending);
+        let device_rows = device_rows(&devices_vec, &self.state.devices.collapsed_hosts);
+        self.state.devices.selected = self.state.devices.selected.min(device_rows.len() - 1);
 
-        // Create table headers
-        let header = Row::new(vec![
-            Cell::from("Instance").style(Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("IP Address").style(Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("HTTP Port").style(Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("gRPC Port").style(Style::default().add_modifier(Modifier::BOLD)),
-        ])
-        .style(Style::default().fg(Color::Yellow))
-        .bottom_margin(1);
+        let [summary_area, area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        self.draw_devices_summary(frame, summary_area, devices);
+
+        // Create table headers, marking the sort column with its order
+        let mut titles = DeviceColumn::ALL
+            .map(|column| match (column == sort_column, sort_descending) {
+                (true, false) => format!("{} ▲", column.title()),
+                (true, true) => format!("{} ▼", column.title()),
+                (false, _) => column.title().to_string(),
+            })
+            .to_vec();
+        // whether the devices can hold the model to check, which is not sortable
+        let model = self.state.devices.model.clone();
+        let layer_bytes = self.state.devices.layer_estimate.layer_bytes();
+        if model.is_some() {
+            titles.push("Model Fit".to_string());
+        }
+        let header =
+            Row::new(titles.into_iter().map(|title| {
+                Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))
+            }))
+            .style(Style::default().fg(Color::Yellow))
+            .bottom_margin(1);
+
+        let flag = |value: bool| if value { "yes" } else { "-" };
+
+        // memory of a host, summed over its instances that reported it
+        let probe = &self.state.devices.probe;
+        let host_memory = |instances: &[&str]| {
+            let resources = instances
+                .iter()
+                .filter_map(|i| probe.get_resources(i))
+                .collect::<Vec<_>>();
+            let used = resources.iter().filter_map(|r| r.memory_used).sum::<u64>();
+            // instances share the memory of the host
+            match resources.iter().filter_map(|r| r.memory_total).max() {
+                Some(total) => format!("Mem: {} / {}", format_bytes(used), format_bytes(total)),
+                None if used > 0 => format!("Mem: {}", format_bytes(used)),
+                None => "Mem: -".to_string(),
+            }
+        };
 
         // Create table rows
-        let rows: Vec<Row> = devices_vec
+        let rows: Vec<Row> = device_rows
             .iter()
-            .map(|(_key, device)| {
+            .map(|row| {
+                let (key, device) = match row {
+                    DeviceRow::Device(key, device) => (key, device),
+                    DeviceRow::Host {
+                        ip,
+                        instances,
+                        collapsed,
+                    } => {
+                        let marker = if *collapsed { "▶" } else { "▼" };
+                        return Row::new(vec![
+                            Cell::from(format!(
+                                "{} {} ({} instances)",
+                                marker,
+                                ip,
+                                instances.len()
+                            )),
+                            Cell::from(host_memory(instances)),
+                        ])
+                        .style(
+                            Style::default()
+                                .fg(Color::White)
+                                .add_modifier(Modifier::BOLD),
+                        );
+                    }
+                };
+
                 // Determine row style based on status
                 let style = if device.is_manager {
                     Style::default()
@@ -133,39 +783,568 @@ impl App {
                     Style::default().fg(Color::Green)
                 };
 
-                Row::new(vec![
-                    Cell::from(device.instance.clone()),
-                    Cell::from(device.local_ip.clone()),
-                    Cell::from(device.server_port.to_string()),
-                    Cell::from(device.shard_port.to_string()),
-                ])
+                // instances of a host are indented under its header
+                let indent = if device_rows
+                    .iter()
+                    .any(|r| matches!(r, DeviceRow::Host { ip, .. } if *ip == device.local_ip))
+                {
+                    "  "
+                } else {
+                    ""
+                };
+                // whether the shard is computing, e.g. during a generation
+                let last_seen = self.state.devices.last_seen(key);
+                let busy_dot = match (last_seen.is_some(), device.is_busy) {
+                    (true, _) => Cell::from("○ ?").dark_gray(),
+                    (false, true) => Cell::from("● busy").yellow(),
+                    (false, false) => Cell::from("● idle").green(),
+                };
+
+                // excluded and stale devices are listed, but dimmed
+                let (style, note) = match last_seen {
+                    Some(ago) => (
+                        Style::default().fg(Color::DarkGray),
+                        format!(" (last seen {}s ago)", ago.as_secs()),
+                    ),
+                    None if self.config.is_excluded(&device.instance) => {
+                        (style.add_modifier(Modifier::DIM), " (excluded)".to_string())
+                    }
+                    None => (style, String::new()),
+                };
+                Row::new(
+                    vec![
+                        Cell::from(format!(
+                            "{}{}{}{}",
+                            indent,
+                            if self.state.devices.marked.contains(key) {
+                                "◆ "
+                            } else {
+                                ""
+                            },
+                            self.config.device_name(&device.instance),
+                            note
+                        )),
+                        Cell::from(key.to_string()),
+                        Cell::from(device.local_ip.clone()),
+                        Cell::from(device.server_port.to_string()),
+                        Cell::from(device.shard_port.to_string()),
+                        Cell::from(flag(device.is_manager)),
+                        busy_dot,
+                    ]
+                    .into_iter()
+                    .chain(model.as_ref().map(|_| {
+                        let resources = probe.get_resources(&device.instance);
+                        match layer_bytes
+                            .zip(resources)
+                            .and_then(|(b, r)| layers_that_fit(b, r))
+                        {
+                            Some(0) => Cell::from("✗ no layer").red(),
+                            Some(n) => Cell::from(format!("✓ {} layers", n)).green(),
+                            None => Cell::from("?").dark_gray(),
+                        }
+                    }))
+                    .collect::<Vec<_>>(),
+                )
                 .style(style)
             })
             .collect();
 
         // create table with widths
+        let mut widths = vec![
+            Constraint::Percentage(22), // Instance
+            Constraint::Percentage(26), // Host
+            Constraint::Percentage(16), // IP Address
+            Constraint::Percentage(9),  // HTTP Port
+            Constraint::Percentage(9),  // gRPC Port
+            Constraint::Percentage(9),  // Manager
+            Constraint::Percentage(9),  // Busy
+        ];
+        if model.is_some() {
+            widths.push(Constraint::Length(14)); // Model Fit
+        }
+
+        let mut title = format!("{} Devices", devices.len());
+        if let Some(model) = &model {
+            let estimate = match layer_bytes {
+                Some(bytes) => format!("~{} per layer", format_bytes(bytes)),
+                None if self.state.devices.layer_estimate.is_pending() => {
+                    "estimating...".to_string()
+                }
+                None => "unknown layer size".to_string(),
+            };
+            title.push_str(&format!(" | Model: {} ({})", model, estimate));
+        }
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .title_style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .column_spacing(1)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut table_state = TableState::default().with_selected(self.state.devices.selected);
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+
+    /// Draw the fields of the compared devices aligned, highlighting those that differ.
+    fn draw_device_comparison(&self, frame: &mut Frame, area: Rect, comparison: &DeviceComparison) {
+        let health = comparison.health.each_ref().map(|health| match health {
+            ShardView::Loaded(health) => Some(health.as_ref()),
+            _ => None,
+        });
+        let rows = comparison_rows(comparison.devices.each_ref(), health)
+            .into_iter()
+            .map(|(field, [a, b])| {
+                let style = if a == b {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::Yellow)
+                };
+                Row::new(vec![Cell::from(field).bold(), Cell::from(a), Cell::from(b)]).style(style)
+            });
+
+        // the health is loading, or the shard could not be reached
+        let status = |health: &ShardView| match health {
+            ShardView::Loaded(_) => String::new(),
+            ShardView::Error(err) => format!(" ({})", err),
+            ShardView::Loading | ShardView::Restarting => " (loading...)".to_string(),
+        };
+        let header = Row::new(
+            std::iter::once(Cell::from("")).chain(
+                comparison
+                    .devices
+                    .iter()
+                    .zip(&comparison.health)
+                    .map(|(device, health)| {
+                        let name = self.config.device_name(&device.instance);
+                        Cell::from(format!("{}{}", name, status(health)))
+                    }),
+            ),
+        )
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+
         let widths = [
-            Constraint::Percentage(56), // Instance
-            Constraint::Percentage(24), // IP Address
-            Constraint::Percentage(10), // HTTP Port
-            Constraint::Percentage(10), // gRPC Port
+            Constraint::Length(16),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
         ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title("Comparison"))
+            .column_spacing(2);
+        frame.render_widget(table, area);
+    }
 
+    /// Draw the aggregate counts of the devices in a single line.
+    fn draw_devices_summary(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        devices: &HashMap<String, DeviceProperties>,
+    ) {
+        let summary = DevicesSummary::new(devices, &self.state.devices.probe);
+        let memory = if summary.memory_total > 0 {
+            format!(
+                "{} / {}",
+                format_bytes(summary.memory_used),
+                format_bytes(summary.memory_total)
+            )
+        } else {
+            "-".to_string()
+        };
+        let unreachable = format!("{} unreachable", summary.num_unreachable);
+        let line = Line::from(vec![
+            format!("{} devices", summary.num_devices).bold(),
+            format!(
+                " ({} shards, {} managers)  |  Memory: {}  |  ",
+                summary.num_shards, summary.num_managers, memory
+            )
+            .into(),
+            format!("{} reachable", summary.num_reachable).green(),
+            ", ".into(),
+            if summary.num_unreachable > 0 {
+                unreachable.red()
+            } else {
+                unreachable.dark_gray()
+            },
+        ]);
+        frame.render_widget(Paragraph::new(line).centered(), area);
+    }
+
+    /// Exclude the device from the topologies, or include it back, and save the config.
+    fn toggle_excluded_device(&mut self, instance: String) {
+        let excluded = &mut self.config.excluded_devices;
+        let message = if excluded.remove(&instance) {
+            "Included"
+        } else {
+            excluded.insert(instance.clone());
+            "Excluded"
+        };
+
+        self.status_message = match self.config.save_to_dria() {
+            Ok(()) => format!(
+                "{} {} in topologies",
+                message,
+                self.config.device_name(&instance)
+            ),
+            Err(e) => format!("Error: Could not save config: {}", e),
+        };
+    }
+
+    /// Draw the form to register a device (if open) on top of the devices.
+    fn draw_device_form(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(form) = &self.state.devices.form else {
+            return;
+        };
+
+        let popup_area = centered_rect(50, 70, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::bordered()
+            .title(" Add Device ")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [fields_area, hint_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let field_areas = Layout::vertical([Constraint::Length(3); 4]).split(fields_area);
+
+        for (i, (input, title)) in form.inputs.iter().zip(DeviceForm::FIELDS).enumerate() {
+            let input_area = field_areas[i];
+            let block = if form.focus == i {
+                Block::bordered()
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Cyan))
+            } else {
+                Block::bordered().title(title)
+            };
+
+            // keep 2 for borders and 1 for cursor
+            let width = input_area.width.max(3) - 3;
+            let scroll = input.visual_scroll(width as usize);
+            frame.render_widget(
+                Paragraph::new(input.value())
+                    .scroll((0, scroll as u16))
+                    .block(block),
+                input_area,
+            );
+            if form.focus == i {
+                let x = input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new("Enter: Register | Tab: Next field | Esc: Cancel")
+                .centered()
+                .gray(),
+            hint_area,
+        );
+    }
+
+    /// Handle input while the form to register a device is open.
+    fn handle_device_form_input(&mut self, key: KeyEvent) {
+        let Some(form) = &mut self.state.devices.form else {
+            return;
+        };
+
+        let n = DeviceForm::FIELDS.len();
+        match key.code {
+            KeyCode::Esc => {
+                self.state.devices.form = None;
+                self.status_message.clear();
+            }
+            KeyCode::Tab | KeyCode::Down => form.focus = (form.focus + 1) % n,
+            KeyCode::BackTab | KeyCode::Up => form.focus = (form.focus + n - 1) % n,
+            KeyCode::Enter => match form.to_device() {
+                Ok(device) => {
+                    self.state.devices.form = None;
+                    self.status_message.clear();
+                    self.view = AppView::Devices(DevicesView::Registering(device));
+                }
+                // keep the form open so that the input can be fixed
+                Err(err) => self.status_message = format!("Error: {}", err),
+            },
+            _ => {
+                form.inputs[form.focus].handle_event(&crossterm::event::Event::Key(key));
+            }
+        }
+    }
+
+    /// Draw which devices are connected to each other over thunderbolt, as an
+    /// adjacency matrix whose columns are numbered as the rows.
+    fn draw_thunderbolt_matrix(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        devices: &HashMap<String, DeviceProperties>,
+    ) {
+        let (sort_column, sort_descending) = (
+            self.state.devices.sort_column,
+            self.state.devices.sort_descending,
+        );
+        let sorted = sort_devices(devices, sort_column, sort_descending)
+            .into_iter()
+            .map(|(_, device)| device)
+            .collect::<Vec<_>>();
+        let links = thunderbolt_links(&sorted);
+        let is_linked = |i: usize, j: usize| links.contains(&(i.min(j), i.max(j)));
+
+        let header = Row::new(
+            std::iter::once(Cell::from("Device"))
+                .chain((1..=sorted.len()).map(|j| Cell::from(format!("{:>2}", j)))),
+        )
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+
+        let rows = sorted.iter().enumerate().map(|(i, device)| {
+            let name = format!(
+                "{:>2}. {}",
+                i + 1,
+                self.config.device_name(&device.instance)
+            );
+            let cells = (0..sorted.len()).map(|j| match (i == j, is_linked(i, j)) {
+                (true, _) => Cell::from(" -").dark_gray(),
+                (false, true) => Cell::from(" ●").green(),
+                (false, false) => Cell::from(" ·").dark_gray(),
+            });
+            // devices without thunderbolt can not be linked at all
+            let style = if device.thunderbolt.is_some() {
+                Style::default()
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            };
+            Row::new(std::iter::once(Cell::from(name)).chain(cells)).style(style)
+        });
+
+        let widths = std::iter::once(Constraint::Length(30))
+            .chain(sorted.iter().map(|_| Constraint::Length(2)));
         let table = Table::new(rows, widths)
             .header(header)
             .block(
                 Block::bordered()
-                    .title(format!("{} Devices", devices.len()))
+                    .title(format!("Thunderbolt Links ({})", links.len()))
                     .title_style(Style::default().add_modifier(Modifier::BOLD)),
             )
             .column_spacing(1);
-
         frame.render_widget(table, area);
     }
 
-    pub(crate) fn handle_devices_input(&mut self, key: KeyEvent, _view: &DevicesView) {
-        if key.code == KeyCode::Esc {
-            self.view = AppView::Menu;
+    /// Draw the properties & thunderbolt connections of the device next to its health.
+    fn draw_device_detail(&mut self, frame: &mut Frame, area: Rect, detail: &DeviceDetail) {
+        let [device_area, health_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(area);
+        let [properties_area, usage_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(4)]).areas(device_area);
+
+        let device = &detail.device;
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        let mut lines = vec![
+            Line::from(""),
+            "━━━ Properties ━━━".bold().cyan().into(),
+            format!("  Instance:       {}", device.instance).into(),
+            format!("  IP Address:     {}", device.local_ip).into(),
+            format!("  HTTP Port:      {}", device.server_port).into(),
+            format!("  gRPC Port:      {}", device.shard_port).into(),
+            format!("  Manager:        {}", yes_no(device.is_manager)).into(),
+            format!("  Busy:           {}", yes_no(device.is_busy)).into(),
+            "".into(),
+            "━━━ Thunderbolt ━━━".bold().cyan().into(),
+        ];
+        match &device.thunderbolt {
+            None => lines.push("  Not available".dark_gray().into()),
+            Some(thunderbolt) => {
+                lines.push(format!("  IP Address:     {}", thunderbolt.ip_addr).into());
+                if detail.peers.is_empty() {
+                    lines.push("  No connected peers".dark_gray().into());
+                }
+                for (own, peer) in &detail.peers {
+                    lines.push(format!("  {} → {}", own, self.config.device_name(peer)).into());
+                }
+            }
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Device")),
+            properties_area,
+        );
+
+        // usage from the live health, or from the latest probe until it loads
+        let default = ShardResources::default();
+        let resources = match &detail.health {
+            ShardView::Loaded(health) => &health.resources,
+            _ => self
+                .state
+                .devices
+                .probe
+                .get_resources(&device.instance)
+                .unwrap_or(&default),
+        };
+        let block = Block::bordered().title("Usage");
+        let [memory_area, disk_area] =
+            Layout::vertical([Constraint::Length(1); 2]).areas(block.inner(usage_area));
+        frame.render_widget(block, usage_area);
+        frame.render_widget(
+            usage_gauge("Memory", resources.memory_used, resources.memory_total),
+            memory_area,
+        );
+        frame.render_widget(
+            usage_gauge("Disk", resources.disk_used, resources.disk_total),
+            disk_area,
+        );
+
+        match &detail.health {
+            ShardView::Loaded(health) => self.draw_shard_health(frame, health_area, health),
+            ShardView::Error(err) => frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(""),
+                    Line::from("Error Loading Shard Health").bold().red(),
+                    Line::from(""),
+                    Line::from(err.as_str()),
+                ])
+                .block(Block::bordered().title("Health Status"))
+                .style(Style::default().fg(Color::Red))
+                .centered(),
+                health_area,
+            ),
+            ShardView::Loading | ShardView::Restarting => frame.render_widget(
+                Paragraph::new("Loading shard health...")
+                    .block(Block::bordered().title("Health Status"))
+                    .centered(),
+                health_area,
+            ),
+        }
+    }
+
+    pub(crate) fn handle_devices_input(&mut self, key: KeyEvent, view: &DevicesView) {
+        if self.state.devices.form.is_some() {
+            return self.handle_device_form_input(key);
+        }
+
+        let devices = &mut self.state.devices;
+        match (view, key.code) {
+            (DevicesView::Detail(_) | DevicesView::Compare(_), KeyCode::Esc) => {
+                self.view = AppView::Devices(DevicesView::Loading);
+            }
+            (DevicesView::Detail(_) | DevicesView::Compare(_) | DevicesView::Registering(_), _) => {
+            }
+            (_, KeyCode::Esc) => {
+                self.status_message.clear();
+                self.view = AppView::Menu;
+            }
+            (DevicesView::Loaded(all), KeyCode::Char(c @ ('e' | 'E'))) => {
+                self.status_message = match export_devices(all, c == 'e') {
+                    Ok(path) => format!("Exported to {}", path.display()),
+                    Err(e) => format!("Error: Could not export devices: {}", e),
+                };
+            }
+            (_, KeyCode::Up) => devices.selected = devices.selected.saturating_sub(1),
+            (_, KeyCode::Down) => devices.selected = devices.selected.saturating_add(1),
+            (_, KeyCode::Left) => devices.sort_column = devices.sort_column.next(false),
+            (_, KeyCode::Right) => devices.sort_column = devices.sort_column.next(true),
+            (_, KeyCode::Char('r')) => devices.refresh_requested = true,
+            (_, KeyCode::Char('o')) => devices.sort_descending = !devices.sort_descending,
+            (_, KeyCode::Char('t')) => devices.show_thunderbolt = !devices.show_thunderbolt,
+            // discovered devices are not known to the API, which is offline
+            (_, KeyCode::Char('a' | 'x')) if devices.is_discovered => {
+                self.status_message =
+                    "Error: The API is offline, discovered devices are read-only".to_string();
+            }
+            (_, KeyCode::Char('a')) => devices.form = Some(DeviceForm::default()),
+            (DevicesView::Loaded(all), KeyCode::Char('c')) => {
+                let listed = devices.listed(all);
+                let sorted = sort_devices(&listed, devices.sort_column, devices.sort_descending);
+                match device_rows(&sorted, &devices.collapsed_hosts).get(devices.selected) {
+                    // missing devices can not be compared
+                    Some(DeviceRow::Device(key, _)) if all.contains_key(*key) => {
+                        match devices.marked.iter().position(|marked| marked == *key) {
+                            Some(i) => {
+                                devices.marked.remove(i);
+                            }
+                            None => {
+                                devices.marked.push(key.to_string());
+                                if devices.marked.len() > 2 {
+                                    devices.marked.remove(0);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            (DevicesView::Loaded(all), KeyCode::Char('v')) => {
+                let marked = devices
+                    .marked
+                    .iter()
+                    .filter_map(|key| all.get(key).cloned())
+                    .collect::<Vec<_>>();
+                match <[DeviceProperties; 2]>::try_from(marked) {
+                    Ok(devices) => {
+                        let comparison = DeviceComparison {
+                            devices,
+                            health: [ShardView::Loading, ShardView::Loading],
+                        };
+                        self.view = AppView::Devices(DevicesView::Compare(Box::new(comparison)));
+                    }
+                    Err(_) => {
+                        self.status_message =
+                            "Error: Mark two devices with c to compare them".to_string();
+                    }
+                }
+            }
+            (DevicesView::Loaded(all), KeyCode::Char('x')) => {
+                let listed = devices.listed(all);
+                let sorted = sort_devices(&listed, devices.sort_column, devices.sort_descending);
+                if let Some(DeviceRow::Device(_, device)) =
+                    device_rows(&sorted, &devices.collapsed_hosts).get(devices.selected)
+                {
+                    let instance = device.instance.clone();
+                    self.toggle_excluded_device(instance);
+                }
+            }
+            (_, KeyCode::Char('m')) => {
+                // cycle through the available models, and back to none
+                let models = self.available_models.iter().map(|m| &m.id);
+                let next = match &devices.model {
+                    None => models.clone().next(),
+                    Some(model) => models.skip_while(|m| *m != model).nth(1),
+                };
+                devices.model = next.cloned();
+            }
+            (DevicesView::Loaded(all), KeyCode::Enter | KeyCode::Char(' ')) => {
+                let listed = devices.listed(all);
+                let sorted = sort_devices(&listed, devices.sort_column, devices.sort_descending);
+                match device_rows(&sorted, &devices.collapsed_hosts).get(devices.selected) {
+                    Some(DeviceRow::Host { ip, .. }) => {
+                        let ip = ip.to_string();
+                        if !devices.collapsed_hosts.remove(&ip) {
+                            devices.collapsed_hosts.insert(ip);
+                        }
+                    }
+                    Some(DeviceRow::Device(_, device)) if key.code == KeyCode::Enter => {
+                        let detail = DeviceDetail {
+                            device: (*device).clone(),
+                            peers: thunderbolt_peers(device, all),
+                            health: ShardView::Loading,
+                        };
+                        self.view = AppView::Devices(DevicesView::Detail(Box::new(detail)));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
         }
     }
 
@@ -173,13 +1352,106 @@ impl App {
     pub(crate) async fn tick_devices(&mut self, view: &DevicesView) {
         use std::time::Duration;
 
+        if let DevicesView::Detail(detail) = view {
+            self.tick_device_detail(detail).await;
+            return;
+        }
+        if let DevicesView::Compare(comparison) = view {
+            self.tick_device_comparison(comparison).await;
+            return;
+        }
+        if let DevicesView::Registering(device) = view {
+            self.status_message = match self.api.register_device(device).await {
+                Ok(()) => format!("Registered {}", device.instance),
+                Err(err) => format!("Error: {}", err),
+            };
+            self.view = AppView::Devices(DevicesView::Loading);
+            return;
+        }
+
         let refresh_interval = Duration::from_secs(self.config.devices_refresh_interval);
-        let should_refresh = self.state.devices.refreshed_at.elapsed() >= refresh_interval;
+        let should_refresh = self.state.devices.refresh_requested
+            || self.state.devices.refreshed_at.elapsed() >= refresh_interval;
 
-        // Refresh if loading or if refresh interval has elapsed
-        if matches!(view, DevicesView::Loading) || should_refresh {
+        // Refresh if loading or if refresh interval has elapsed, while discovering
+        // the API is only retried on the interval
+        let is_loading =
+            matches!(view, DevicesView::Loading) && !self.state.devices.is_discovering();
+        if is_loading || should_refresh {
             self.load_devices().await;
         }
+        self.state.devices.probe.poll();
+
+        if let Some(model) = &self.state.devices.model {
+            let estimate = &mut self.state.devices.layer_estimate;
+            estimate.update(model, self.config.seq_len, self.config.kv_bits);
+        }
+        self.state.devices.layer_estimate.poll();
+
+        #[cfg(feature = "mdns")]
+        if let Some(discovered) = self.state.devices.discovery.poll() {
+            self.view = match discovered {
+                Ok(devices) => {
+                    self.state.devices.probe.start(devices.values());
+                    self.state.devices.is_discovered = true;
+                    self.state.devices.refreshed_time = Some(chrono::Local::now());
+                    self.state.devices.track(&devices, Instant::now());
+                    AppView::Devices(DevicesView::Loaded(devices))
+                }
+                Err(err) => AppView::Devices(DevicesView::Error(err)),
+            };
+        }
+    }
+
+    /// Fetch the health of the device in the details, periodically like the shard view.
+    async fn tick_device_detail(&mut self, detail: &DeviceDetail) {
+        let refresh_interval = Duration::from_secs(self.config.shard_refresh_interval);
+        let should_refresh = !refresh_interval.is_zero()
+            && self.state.devices.health_refreshed_at.elapsed() >= refresh_interval;
+        if !matches!(detail.health, ShardView::Loading) && !should_refresh {
+            return;
+        }
+
+        let device = &detail.device;
+        let health =
+            match ShardView::fetch_with_resources(&device.local_ip, device.server_port).await {
+                Ok(health) => ShardView::Loaded(Box::new(health)),
+                Err(err) => ShardView::Error(err),
+            };
+        self.view = AppView::Devices(DevicesView::Detail(Box::new(DeviceDetail {
+            health,
+            ..detail.clone()
+        })));
+        self.state.devices.health_refreshed_at = Instant::now();
+    }
+
+    /// Fetch the health of the compared devices, periodically like the details.
+    async fn tick_device_comparison(&mut self, comparison: &DeviceComparison) {
+        let refresh_interval = Duration::from_secs(self.config.shard_refresh_interval);
+        let should_refresh = !refresh_interval.is_zero()
+            && self.state.devices.health_refreshed_at.elapsed() >= refresh_interval;
+        let is_loading = comparison
+            .health
+            .iter()
+            .any(|health| matches!(health, ShardView::Loading));
+        if !is_loading && !should_refresh {
+            return;
+        }
+
+        let [a, b] = &comparison.devices;
+        let (a, b) = tokio::join!(
+            ShardView::fetch_with_resources(&a.local_ip, a.server_port),
+            ShardView::fetch_with_resources(&b.local_ip, b.server_port)
+        );
+        let health = [a, b].map(|health| match health {
+            Ok(health) => ShardView::Loaded(Box::new(health)),
+            Err(err) => ShardView::Error(err),
+        });
+        self.view = AppView::Devices(DevicesView::Compare(Box::new(DeviceComparison {
+            health,
+            ..comparison.clone()
+        })));
+        self.state.devices.health_refreshed_at = Instant::now();
     }
 
     /// Load devices asynchronously and update state.
@@ -188,13 +1460,292 @@ impl App {
 
         match self.api.get_devices().await {
             Ok(devices) => {
+                self.state.devices.probe.start(devices.values());
+                self.state.devices.is_discovered = false;
+                self.state.devices.refreshed_time = Some(chrono::Local::now());
+                self.state.devices.track(&devices, Instant::now());
                 self.view = AppView::Devices(DevicesView::Loaded(devices));
             }
+            // fall back to discovering the devices on the local network
+            #[cfg(feature = "mdns")]
+            Err(_) => self.state.devices.discovery.start(),
+            #[cfg(not(feature = "mdns"))]
             Err(err) => {
                 self.view = AppView::Devices(DevicesView::Error(err.to_string()));
             }
         };
 
         self.state.devices.refreshed_at = Instant::now();
+        self.state.devices.refresh_requested = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{ThunderboltData, ThunderboltInstance};
+
+    fn device(instance: &str, ip: &str, is_busy: bool) -> DeviceProperties {
+        DeviceProperties {
+            is_manager: false,
+            is_busy,
+            instance: instance.to_string(),
+            server_port: 8080,
+            shard_port: 58080,
+            local_ip: ip.to_string(),
+            thunderbolt: None,
+        }
+    }
+
+    #[test]
+    fn test_thunderbolt_peers() {
+        let instance = |uuid: &str, name: &str| ThunderboltInstance {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+            device: "Mac15,12".to_string(),
+        };
+        let mut a = device("shard-a", "10.0.0.1", false);
+        a.thunderbolt = Some(ThunderboltData {
+            ip_addr: "169.254.0.1".to_string(),
+            instances: vec![(
+                instance("uuid-a", "bus_0"),
+                vec![
+                    instance("uuid-b", "bus_1"),
+                    instance("uuid-x", "Macbook Air"),
+                ],
+            )],
+        });
+        let mut b = device("shard-b", "10.0.0.2", false);
+        b.thunderbolt = Some(ThunderboltData {
+            ip_addr: "169.254.0.2".to_string(),
+            instances: vec![(instance("uuid-b", "bus_1"), vec![])],
+        });
+        let devices = HashMap::from([("a".to_string(), a.clone()), ("b".to_string(), b.clone())]);
+
+        assert_eq!(
+            thunderbolt_peers(&a, &devices),
+            vec![
+                ("bus_0".to_string(), "shard-b".to_string()),
+                ("bus_0".to_string(), "Macbook Air (Mac15,12)".to_string()),
+            ]
+        );
+        assert!(thunderbolt_peers(&b, &devices).is_empty());
+        assert!(thunderbolt_peers(&device("shard-c", "10.0.0.3", false), &devices).is_empty());
+    }
+
+    #[test]
+    fn test_comparison_rows() {
+        let a = device("shard-a", "10.0.0.1", false);
+        let b = device("shard-b", "10.0.0.2", true);
+        let health = ShardHealth {
+            status: "ok".to_string(),
+            running: true,
+            model_loaded: true,
+            model_path: None,
+            assigned_layers: vec![0, 1, 2, 3],
+            queue_size: 2,
+            grpc_port: 58080,
+            http_port: 8080,
+            instance: "shard-a".to_string(),
+            resources: ShardResources {
+                memory_total: Some(16 * 1024 * 1024 * 1024),
+                ..Default::default()
+            },
+            download: None,
+        };
+
+        let rows = comparison_rows([&a, &b], [Some(&health), None]);
+        let field = |name: &str| rows.iter().find(|(f, _)| *f == name).unwrap().1.clone();
+        assert_eq!(field("Busy"), ["no".to_string(), "yes".to_string()]);
+        assert_eq!(field("HTTP Port"), ["8080".to_string(), "8080".to_string()]);
+        assert_eq!(field("Queue Size"), ["2".to_string(), "-".to_string()]);
+        assert_eq!(field("Memory Used"), ["-".to_string(), "-".to_string()]);
+        assert_eq!(field("Memory Total")[1], "-");
+        assert_ne!(field("Memory Total")[0], "-");
+    }
+
+    #[test]
+    fn test_devices_summary() {
+        let mut manager = device("api", "10.0.0.1", false);
+        manager.is_manager = true;
+        let devices = HashMap::from([
+            ("api".to_string(), manager),
+            ("a".to_string(), device("shard-a", "10.0.0.1", false)),
+            ("b".to_string(), device("shard-b", "10.0.0.1", false)),
+            ("c".to_string(), device("shard-c", "10.0.0.2", false)),
+        ]);
+
+        let mut probe = LatencyProbe::default();
+        let resources = |used: u64, total: u64| ShardResources {
+            memory_used: Some(used),
+            memory_total: Some(total),
+            ..Default::default()
+        };
+        for instance in ["shard-a", "shard-b"] {
+            probe
+                .latencies
+                .insert(instance.to_string(), Ok(Duration::from_millis(5)));
+            probe
+                .resources
+                .insert(instance.to_string(), resources(4, 16));
+        }
+        probe
+            .latencies
+            .insert("shard-c".to_string(), Err("timeout".to_string()));
+
+        let summary = DevicesSummary::new(&devices, &probe);
+        assert_eq!(
+            summary,
+            DevicesSummary {
+                num_devices: 4,
+                num_shards: 3,
+                num_managers: 1,
+                num_reachable: 2,
+                num_unreachable: 1,
+                memory_used: 8,
+                // both shards are on the same host
+                memory_total: 16,
+            }
+        );
+    }
+
+    #[test]
+    fn test_track_devices() {
+        let mut state = DevicesState::default();
+        let now = Instant::now();
+        let devices = HashMap::from([
+            ("a".to_string(), device("shard-a", "10.0.0.1", false)),
+            ("b".to_string(), device("shard-b", "10.0.0.2", false)),
+        ]);
+        state.track(&devices, now);
+        assert_eq!(state.listed(&devices).len(), 2);
+        assert_eq!(state.last_seen("a"), None);
+
+        // b went missing, so it is only listed
+        let devices = HashMap::from([("a".to_string(), device("shard-a", "10.0.0.1", true))]);
+        state.track(&devices, now + Duration::from_secs(1));
+        let listed = state.listed(&devices);
+        assert_eq!(listed.len(), 2);
+        assert!(listed["a"].is_busy);
+        assert_eq!(state.last_seen("a"), None);
+        assert!(state.last_seen("b").is_some());
+        assert_eq!(state.last_seen("c"), None);
+
+        // and forgotten once it has been missing for too long
+        state.track(&devices, now + STALE_TTL + Duration::from_secs(2));
+        assert_eq!(state.listed(&devices).len(), 1);
+        assert_eq!(state.last_seen("b"), None);
+    }
+
+    #[test]
+    fn test_device_form() {
+        let form = |values: [&str; 4]| DeviceForm {
+            inputs: values.map(|v| tui_input::Input::new(v.to_string())),
+            focus: 0,
+        };
+
+        let device = form(["10.0.1.5", "8081", "58081", ""]).to_device().unwrap();
+        assert_eq!(device.instance, "10.0.1.5:8081");
+        assert_eq!(device.local_ip, "10.0.1.5");
+        assert_eq!((device.server_port, device.shard_port), (8081, 58081));
+
+        let device = form([" 10.0.1.5 ", "8081", "58081", "shard-9"]).to_device();
+        assert_eq!(device.unwrap().instance, "shard-9");
+
+        assert!(form(["mac-mini", "8081", "58081", ""]).to_device().is_err());
+        assert!(form(["10.0.1.5", "0", "58081", ""]).to_device().is_err());
+        assert!(form(["10.0.1.5", "8081", "", ""]).to_device().is_err());
+    }
+
+    #[test]
+    fn test_device_rows() {
+        let devices = HashMap::from([
+            ("a".to_string(), device("shard-a", "10.0.0.1", false)),
+            ("b".to_string(), device("shard-b", "10.0.0.2", false)),
+            ("c".to_string(), device("shard-c", "10.0.0.1", false)),
+        ]);
+        let sorted = sort_devices(&devices, DeviceColumn::Instance, false);
+
+        let rows = device_rows(&sorted, &HashSet::new());
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[0],
+            DeviceRow::Host {
+                ip: "10.0.0.1",
+                instances: vec!["shard-a", "shard-c"],
+                collapsed: false
+            }
+        );
+        // the instances of the host come right after its header
+        assert!(matches!(rows[2], DeviceRow::Device(_, d) if d.instance == "shard-c"));
+        assert!(matches!(rows[3], DeviceRow::Device(_, d) if d.instance == "shard-b"));
+
+        let rows = device_rows(&sorted, &HashSet::from(["10.0.0.1".to_string()]));
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(
+            rows[0],
+            DeviceRow::Host {
+                collapsed: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_devices_to_csv() {
+        let (a, b) = ("a".to_string(), "b,c".to_string());
+        let mut device_b = device("shard-b", "10.0.0.2", true);
+        device_b.thunderbolt = Some(ThunderboltData {
+            ip_addr: "169.254.0.2".to_string(),
+            instances: vec![],
+        });
+        let csv = devices_to_csv(&[(&a, &device("shard-a", "10.0.0.1", false)), (&b, &device_b)]);
+
+        let expected = [
+            "key,instance,local_ip,server_port,shard_port,is_manager,is_busy,thunderbolt_ip",
+            "a,shard-a,10.0.0.1,8080,58080,false,false,",
+            "\"b,c\",shard-b,10.0.0.2,8080,58080,false,true,169.254.0.2",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn test_sort_devices() {
+        let devices = HashMap::from([
+            ("a".to_string(), device("shard-b", "10.0.0.2", true)),
+            ("b".to_string(), device("shard-a", "10.0.0.3", false)),
+            ("c".to_string(), device("shard-c", "10.0.0.1", false)),
+        ]);
+        let instances = |column, descending| {
+            sort_devices(&devices, column, descending)
+                .into_iter()
+                .map(|(_, d)| d.instance.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            instances(DeviceColumn::Ip, false),
+            ["shard-c", "shard-b", "shard-a"]
+        );
+        assert_eq!(
+            instances(DeviceColumn::Instance, true),
+            ["shard-c", "shard-b", "shard-a"]
+        );
+        // ties are ordered by the IP address
+        assert_eq!(
+            instances(DeviceColumn::Busy, false),
+            ["shard-c", "shard-a", "shard-b"]
+        );
+        assert_eq!(DeviceColumn::Busy.next(true), DeviceColumn::Instance);
+
+        // addresses are ordered by their numbers, not as text
+        let devices = HashMap::from([
+            ("a".to_string(), device("shard-a", "10.0.0.10", false)),
+            ("b".to_string(), device("shard-b", "10.0.0.9", false)),
+        ]);
+        let sorted = sort_devices(&devices, DeviceColumn::Ip, false);
+        assert_eq!(sorted[0].1.instance, "shard-b");
     }
 }
@@ -1,5 +1,6 @@
 use std::time::{Duration, Instant};
 
+use crate::common::Topologies;
 use crate::developer::DeveloperView;
 use crate::model::{LoadModelView, UnloadModelView};
 use crate::topology::TopologyView;
@@ -49,6 +50,7 @@ pub enum MenuItem {
     ViewTopology,
     LoadModel,
     UnloadModel,
+    SwitchModel,
     Settings,
     Developer,
     Exit,
@@ -57,41 +59,54 @@ pub enum MenuItem {
 // TODO: smelly code here, should be much simpler
 
 impl MenuItem {
-    pub const ALL: [MenuItem; 8] = [
+    pub const ALL: [MenuItem; 9] = [
         MenuItem::Chat,
         MenuItem::ViewDevices,
         MenuItem::ViewTopology,
         MenuItem::LoadModel,
         MenuItem::UnloadModel,
+        MenuItem::SwitchModel,
         MenuItem::Settings,
         MenuItem::Developer,
         MenuItem::Exit,
     ];
 
     /// Determines if the menu item should be disabled based on current app state.
+    ///
+    /// `is_multi_model` tells whether the API can load several models at once.
     pub fn is_disabled(
         &self,
         model_loaded: bool,
         topology_loaded: bool,
         is_api_online: bool,
+        is_multi_model: bool,
     ) -> bool {
         match self {
             MenuItem::Chat => !model_loaded,
-            MenuItem::LoadModel => model_loaded || !is_api_online,
+            // another model can only be loaded if the API supports several at once
+            MenuItem::LoadModel => !is_api_online || (model_loaded && !is_multi_model),
             MenuItem::UnloadModel => !model_loaded,
+            MenuItem::SwitchModel => !model_loaded || !is_api_online,
             MenuItem::ViewTopology => !topology_loaded,
             // FIXME: we treat this as API disabled, but we should have a bool for that
-            MenuItem::ViewDevices => !is_api_online,
+            // devices can be discovered over mDNS without the API
+            MenuItem::ViewDevices => !is_api_online && !cfg!(feature = "mdns"),
 
             _ => false,
         }
     }
     /// Formats a menu item for display.
-    pub fn fmt(&self, model_loaded: bool, topology_loaded: bool, is_api_online: bool) -> String {
+    pub fn fmt(
+        &self,
+        model_loaded: bool,
+        topology_loaded: bool,
+        is_api_online: bool,
+        is_multi_model: bool,
+    ) -> String {
         format!(
             "{:<15}: {}",
             self.label(),
-            self.description(model_loaded, topology_loaded, is_api_online)
+            self.description(model_loaded, topology_loaded, is_api_online, is_multi_model)
         )
     }
 
@@ -102,6 +117,7 @@ impl MenuItem {
             MenuItem::ViewTopology => "View Topology",
             MenuItem::LoadModel => "Load Model",
             MenuItem::UnloadModel => "Unload Model",
+            MenuItem::SwitchModel => "Switch Model",
             MenuItem::Settings => "Settings",
             MenuItem::Developer => "Developer",
             MenuItem::Exit => "Exit",
@@ -113,6 +129,7 @@ impl MenuItem {
         model_loaded: bool,
         topology_loaded: bool,
         is_api_online: bool,
+        is_multi_model: bool,
     ) -> &str {
         match self {
             MenuItem::Chat => {
@@ -125,6 +142,8 @@ impl MenuItem {
             MenuItem::ViewDevices => {
                 if is_api_online {
                     "View devices"
+                } else if cfg!(feature = "mdns") {
+                    "View devices (via mDNS)"
                 } else {
                     "View devices (API unavailable)"
                 }
@@ -137,12 +156,14 @@ impl MenuItem {
                 }
             }
             MenuItem::LoadModel => {
-                if model_loaded {
+                if !is_api_online {
+                    "Load a model (API unavailable)"
+                } else if model_loaded && is_multi_model {
+                    "Load another model"
+                } else if model_loaded {
                     "Load a model (model already loaded)"
-                } else if is_api_online {
-                    "Load a model"
                 } else {
-                    "Load a model (API unavailable)"
+                    "Load a model"
                 }
             }
             MenuItem::UnloadModel => {
@@ -152,6 +173,15 @@ impl MenuItem {
                     "Unload model (no model loaded)"
                 }
             }
+            MenuItem::SwitchModel => {
+                if !model_loaded {
+                    "Switch to another model (no model loaded)"
+                } else if is_api_online {
+                    "Switch to another model"
+                } else {
+                    "Switch to another model (API unavailable)"
+                }
+            }
             MenuItem::Settings => "Edit configuration",
             MenuItem::Developer => "Advanced developer tools",
             MenuItem::Exit => "Quit application",
@@ -164,10 +194,18 @@ impl MenuItem {
     }
 
     /// The total width of the menu when fully rendered.
-    pub fn total_width(model_loaded: bool, topology_loaded: bool, is_api_online: bool) -> u16 {
+    pub fn total_width(
+        model_loaded: bool,
+        topology_loaded: bool,
+        is_api_online: bool,
+        is_multi_model: bool,
+    ) -> u16 {
         Self::ALL
             .iter()
-            .map(|item| item.fmt(model_loaded, topology_loaded, is_api_online).len() as u16)
+            .map(|item| {
+                item.fmt(model_loaded, topology_loaded, is_api_online, is_multi_model)
+                    .len() as u16
+            })
             .max()
             .unwrap_or(0)
     }
@@ -195,7 +233,10 @@ impl App {
             // API is online, check models if we haven't fetched them yet
             if self.available_models.is_empty() {
                 match self.api.get_models().await {
-                    Ok(models) => self.available_models = models,
+                    Ok(models) => {
+                        self.available_models = models;
+                        self.sort_models();
+                    }
                     Err(_) => self.is_api_online = false,
                 }
             }
@@ -205,8 +246,21 @@ impl App {
                 >= Self::TOPOLOGY_CHECK_INTERVAL
             {
                 self.state.menu.last_topology_check = now;
-                match self.api.get_topology().await {
-                    Ok(topology) => self.topology = topology,
+                match self.api.get_topologies().await {
+                    Ok(Topologies {
+                        topologies,
+                        is_multi_model,
+                    }) => {
+                        // keep the same topology shown, as there may be one per loaded model
+                        let active = self
+                            .topology
+                            .as_ref()
+                            .and_then(|old| topologies.iter().find(|t| t.model == old.model))
+                            .or(topologies.first());
+                        self.topology = active.cloned();
+                        self.state.topology.topologies = topologies;
+                        self.state.topology.is_multi_model = is_multi_model;
+                    }
                     Err(_) => self.is_api_online = false,
                 }
             }
@@ -249,7 +303,8 @@ impl App {
 
         let is_api_online = self.is_api_online;
         let is_topology_loaded = self.topology.is_some();
-        let is_model_loaded = self.topology.as_ref().is_some_and(|t| t.model.is_some());
+        let is_model_loaded = !self.loaded_models().is_empty();
+        let is_multi_model = self.state.topology.is_multi_model;
 
         // Menu items
         let menu_items: Vec<ListItem> = MenuItem::ALL
@@ -257,8 +312,12 @@ impl App {
             .enumerate()
             .map(|(i, item)| {
                 // decide style based on selection and availability
-                let is_disabled =
-                    item.is_disabled(is_model_loaded, is_topology_loaded, is_api_online);
+                let is_disabled = item.is_disabled(
+                    is_model_loaded,
+                    is_topology_loaded,
+                    is_api_online,
+                    is_multi_model,
+                );
                 let is_selected = i == self.state.menu.selection_idx;
 
                 let style = match (is_selected, is_disabled) {
@@ -278,8 +337,13 @@ impl App {
                     (false, false) => Style::default(),
                 };
 
-                ListItem::new(item.fmt(is_model_loaded, is_topology_loaded, is_api_online))
-                    .style(style)
+                ListItem::new(item.fmt(
+                    is_model_loaded,
+                    is_topology_loaded,
+                    is_api_online,
+                    is_multi_model,
+                ))
+                .style(style)
             })
             .collect();
 
@@ -294,7 +358,12 @@ impl App {
         .areas(menu_area);
 
         // calculate horizontal centering for menu
-        let menu_width = MenuItem::total_width(is_model_loaded, is_topology_loaded, is_api_online);
+        let menu_width = MenuItem::total_width(
+            is_model_loaded,
+            is_topology_loaded,
+            is_api_online,
+            is_multi_model,
+        );
         let left_padding = (vertical_centered_area.width.saturating_sub(menu_width)) / 2;
         let [_, centered_menu_area, _] = Layout::horizontal([
             Constraint::Length(left_padding),
@@ -320,13 +389,44 @@ impl App {
             Span::styled(" | Press Esc quit", Style::default().fg(Color::DarkGray)),
         ]);
         frame.render_widget(
-            Paragraph::new(footer_line)
+            Paragraph::new(vec![footer_line, self.chat_background_line()])
                 .style(Style::default().fg(Color::DarkGray))
                 .centered(),
             footer_area,
         );
     }
 
+    /// A line about the chat sessions generating in the background,
+    /// or the notification of a finished one.
+    fn chat_background_line(&self) -> Line<'static> {
+        let generating = self
+            .state
+            .chat
+            .sessions
+            .iter()
+            .filter(|s| s.is_generating)
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        if !generating.is_empty() {
+            return Line::styled(
+                format!("{} generating…", generating.join(", ")),
+                Style::default().fg(Color::Yellow),
+            );
+        }
+
+        match self.state.chat.recent_toast() {
+            Some(toast) => Line::styled(
+                toast.message.clone(),
+                Style::default().fg(if toast.is_error {
+                    Color::Red
+                } else {
+                    Color::Green
+                }),
+            ),
+            None => Line::default(),
+        }
+    }
+
     pub fn handle_menu_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => self.quit(),
@@ -353,16 +453,17 @@ impl App {
     fn select_menu_item(&mut self) {
         let is_api_online = self.is_api_online;
         let topology_loaded = self.topology.is_some();
-        let model_loaded = self.topology.as_ref().is_some_and(|t| t.model.is_some());
+        let loaded_models = self.loaded_models();
+        let model_loaded = !loaded_models.is_empty();
         match MenuItem::ALL[self.state.menu.selection_idx] {
             MenuItem::Chat => {
                 // only allow entering chat if model is loaded
                 if model_loaded {
-                    self.view = AppView::Chat(crate::chat::ChatView::Active);
+                    self.enter_chat();
                 }
             }
             MenuItem::ViewDevices => {
-                if is_api_online {
+                if is_api_online || cfg!(feature = "mdns") {
                     self.view = AppView::Devices(crate::devices::DevicesView::Loading);
                 }
             }
@@ -374,11 +475,14 @@ impl App {
                 }
             }
             MenuItem::LoadModel => {
-                // if model already loaded, do nothing (item is disabled)
-                if !model_loaded && is_api_online {
+                // if a model is loaded and the API serves one at a time, do nothing (item is disabled)
+                if is_api_online && (!model_loaded || self.state.topology.is_multi_model) {
                     self.view = AppView::Model(super::model::ModelView::Load(
                         LoadModelView::SelectingModel,
                     ));
+                    self.state.model.switch_from = None;
+                    self.state.model.cluster = Default::default();
+                    self.state.model.keep_topology = false;
                     self.model_selector_state.reset();
                     self.status_message.clear();
                 }
@@ -386,8 +490,27 @@ impl App {
             MenuItem::UnloadModel => {
                 // if topology not loaded, do nothing (item is disabled)
                 if model_loaded && topology_loaded {
-                    self.view =
-                        AppView::Model(super::model::ModelView::Unload(UnloadModelView::Unloading));
+                    self.view = AppView::Model(super::model::ModelView::Unload(
+                        match loaded_models.as_slice() {
+                            [model] => UnloadModelView::Confirming(model.clone()),
+                            _ => UnloadModelView::SelectingModel,
+                        },
+                    ));
+                    self.state.model.unload_selected = 0;
+                    self.status_message.clear();
+                }
+            }
+            MenuItem::SwitchModel => {
+                // the current model is unloaded once the new one is picked
+                if model_loaded && is_api_online {
+                    self.state.model.switch_from =
+                        self.topology.as_ref().and_then(|t| t.model.clone());
+                    self.view = AppView::Model(super::model::ModelView::Load(
+                        LoadModelView::SelectingModel,
+                    ));
+                    self.state.model.cluster = Default::default();
+                    self.state.model.keep_topology = false;
+                    self.model_selector_state.reset();
                     self.status_message.clear();
                 }
             }
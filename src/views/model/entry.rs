@@ -0,0 +1,134 @@
+use super::{LoadModelView, ModelView};
+use crate::AppView;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Paragraph},
+};
+use tui_input::backend::crossterm::EventHandler;
+
+/// Entry of a model id that the manager does not list, e.g. one not yet downloaded.
+#[derive(Debug, Default)]
+pub struct ModelEntry {
+    pub input: tui_input::Input,
+    /// Model id that was submitted, whose config is fetched via the preview.
+    pub submitted: Option<String>,
+    /// Whether the submitted id is still being validated.
+    pub is_validating: bool,
+}
+
+/// Check that the model id looks like a HuggingFace repo id, i.e. `owner/name`.
+pub fn validate_model_id(model: &str) -> Result<(), String> {
+    let is_valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    match model.split_once('/') {
+        Some((owner, name)) if is_valid_part(owner) && is_valid_part(name) => Ok(()),
+        _ => Err(format!("Invalid model id {:?}, expected owner/name", model)),
+    }
+}
+
+impl crate::App {
+    /// Draw the model id entry, along with the details of the model being validated.
+    pub(super) fn draw_model_entry(&mut self, frame: &mut Frame, area: Rect) {
+        let entry = &self.state.model.entry;
+        let [input_area, preview_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        // keep 2 for borders and 1 for cursor
+        let width = input_area.width.max(3) - 3;
+        let scroll = entry.input.visual_scroll(width as usize);
+        frame.render_widget(
+            Paragraph::new(entry.input.value())
+                .scroll((0, scroll as u16))
+                .block(
+                    Block::bordered()
+                        .title("Model ID (e.g. mlx-community/Qwen3-4B-4bit)")
+                        .border_style(Style::default().fg(Color::Cyan)),
+                ),
+            input_area,
+        );
+        let x = entry.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        if let Some(model) = entry.submitted.clone() {
+            self.draw_model_preview(frame, preview_area, Some(&model));
+        }
+    }
+
+    /// Handle input in the model id entry, where Enter validates the id against
+    /// its config and then loads it.
+    pub(super) fn handle_model_entry_input(&mut self, key: KeyEvent) {
+        let entry = &mut self.state.model.entry;
+        match key.code {
+            KeyCode::Esc => {
+                entry.submitted = None;
+                entry.is_validating = false;
+                self.status_message.clear();
+                self.view = AppView::Model(ModelView::Load(LoadModelView::SelectingModel));
+            }
+            KeyCode::Enter => {
+                let model = entry.input.value().trim().to_string();
+                match validate_model_id(&model) {
+                    Ok(()) => {
+                        self.state.model.preview.request(&model);
+                        self.status_message = format!("Validating {}...", model);
+                        entry.submitted = Some(model);
+                        entry.is_validating = true;
+                    }
+                    Err(err) => self.status_message = format!("Error: {}", err),
+                }
+            }
+            _ => {
+                entry.input.handle_event(&crossterm::event::Event::Key(key));
+            }
+        }
+    }
+
+    /// Load the entered model once its config is fetched, if it has layers.
+    pub(super) fn tick_model_entry(&mut self) {
+        self.state.model.preview.poll();
+        let entry = &self.state.model.entry;
+        let Some(model) = entry.submitted.clone().filter(|_| entry.is_validating) else {
+            return;
+        };
+
+        // the details of an invalid model are kept on screen along with the error
+        match self.state.model.preview.get(&model) {
+            None => return,
+            Some(Ok(summary)) if summary.num_layers.is_some() => {
+                self.status_message.clear();
+                self.state.model.entry.submitted = None;
+                self.begin_model_load(model);
+            }
+            Some(Ok(_)) => {
+                self.status_message = format!("Error: The config of {} has no layers", model);
+            }
+            Some(Err(err)) => {
+                self.status_message =
+                    format!("Error: Could not fetch the config of {}: {}", model, err);
+            }
+        }
+        self.state.model.entry.is_validating = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_model_id() {
+        assert!(validate_model_id("mlx-community/Qwen3-4B-4bit").is_ok());
+        assert!(validate_model_id("Qwen/Qwen2.5_7B").is_ok());
+        assert!(validate_model_id("Qwen3-4B").is_err());
+        assert!(validate_model_id("mlx-community/").is_err());
+        assert!(validate_model_id("a/b/c").is_err());
+        assert!(validate_model_id("mlx community/model").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::common::{ApiClient, DeviceProperties};
+use crate::views::devices::DevicesSummary;
+use crate::views::topology::LatencyProbe;
+use ratatui::{style::Stylize, text::Span};
+use std::collections::HashMap;
+
+/// Fraction of the memory of the cluster that the weights can comfortably take,
+/// leaving room for the KV cache and the rest of the system.
+const FITS_RATIO: f64 = 0.6;
+
+/// Fraction of the memory of the cluster beyond which the weights won't fit, as
+/// macOS only lets the GPU wire about this much of the unified memory by default.
+const TIGHT_RATIO: f64 = 0.75;
+
+/// Whether the weights of a model fit into the memory of the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFit {
+    Fits,
+    Tight,
+    WontFit,
+}
+
+impl ModelFit {
+    /// Fit of the weights into the total memory of the cluster, see [`FITS_RATIO`] & [`TIGHT_RATIO`].
+    ///
+    /// Returns `None` if the memory of the cluster is unknown.
+    pub fn new(weight_bytes: u64, memory_total: u64) -> Option<Self> {
+        if memory_total == 0 {
+            return None;
+        }
+
+        let ratio = weight_bytes as f64 / memory_total as f64;
+        Some(if ratio <= FITS_RATIO {
+            ModelFit::Fits
+        } else if ratio <= TIGHT_RATIO {
+            ModelFit::Tight
+        } else {
+            ModelFit::WontFit
+        })
+    }
+
+    /// Badge of the fit, shown next to the model in the selector.
+    pub fn badge(&self) -> Span<'static> {
+        match self {
+            ModelFit::Fits => " ✓ fits".green(),
+            ModelFit::Tight => " ~ tight".yellow(),
+            ModelFit::WontFit => " ✗ won't fit".red(),
+        }
+    }
+}
+
+/// Memory of the shards of the cluster, probed from their health when a model is
+/// to be loaded, so that the models can be checked against it.
+#[derive(Debug, Default)]
+pub struct ClusterMemory {
+    /// Shards that can hold layers, `None` until they are listed.
+    devices: Option<HashMap<String, DeviceProperties>>,
+    probe: LatencyProbe,
+}
+
+impl ClusterMemory {
+    /// List the shards (once) and probe their memory, then collect the probes.
+    pub async fn update(&mut self, api: &ApiClient, config: &crate::Config) {
+        if self.devices.is_none() {
+            let devices = api
+                .get_devices()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, d)| !d.is_manager && !config.is_excluded(&d.instance))
+                .collect::<HashMap<_, _>>();
+            self.probe.start(devices.values());
+            self.devices = Some(devices);
+        }
+        self.probe.poll();
+    }
+
+    /// Total memory of the hosts of the shards in bytes, zero if unknown.
+    pub fn total(&self) -> u64 {
+        self.devices.as_ref().map_or(0, |devices| {
+            DevicesSummary::new(devices, &self.probe).memory_total
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_fit() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        assert_eq!(ModelFit::new(8 * GIB, 32 * GIB), Some(ModelFit::Fits));
+        assert_eq!(ModelFit::new(22 * GIB, 32 * GIB), Some(ModelFit::Tight));
+        assert_eq!(ModelFit::new(30 * GIB, 32 * GIB), Some(ModelFit::WontFit));
+        assert_eq!(ModelFit::new(8 * GIB, 0), None);
+    }
+}
@@ -0,0 +1,332 @@
+use super::ModelView;
+use crate::AppView;
+use crate::common::LoadModelResponse;
+use crate::config::Config;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LoadAction {
+    #[serde(rename = "load")]
+    Load,
+    #[serde(rename = "unload")]
+    Unload,
+}
+
+/// An attempt to load or unload a model, kept in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadRecord {
+    pub at: chrono::DateTime<chrono::Local>,
+    pub action: LoadAction,
+    pub model: String,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Shards that failed to load the model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_shards: Vec<String>,
+}
+
+impl LoadRecord {
+    /// Record of a load, where a response may still report failing shards.
+    pub fn load(
+        model: &str,
+        duration: Duration,
+        result: &Result<LoadModelResponse, String>,
+    ) -> Self {
+        let (success, error, failed_shards) = match result {
+            Ok(response) => (
+                response.success,
+                response.message.clone().filter(|_| !response.success),
+                response
+                    .shard_statuses
+                    .iter()
+                    .filter(|status| !status.success)
+                    .map(|status| status.instance.clone())
+                    .collect(),
+            ),
+            Err(err) => (false, Some(err.clone()), Vec::new()),
+        };
+        Self {
+            at: chrono::Local::now(),
+            action: LoadAction::Load,
+            model: model.to_string(),
+            duration_ms: duration.as_millis() as u64,
+            success,
+            error,
+            failed_shards,
+        }
+    }
+
+    /// Record of an unload.
+    pub fn unload(model: &str, duration: Duration, result: Result<(), String>) -> Self {
+        Self {
+            at: chrono::Local::now(),
+            action: LoadAction::Unload,
+            model: model.to_string(),
+            duration_ms: duration.as_millis() as u64,
+            success: result.is_ok(),
+            error: result.err(),
+            failed_shards: Vec::new(),
+        }
+    }
+}
+
+/// History of the loads & unloads, appended to a local file with a record per line.
+pub struct LoadHistory;
+
+impl LoadHistory {
+    pub const FILE_NAME: &'static str = "load-history.jsonl";
+
+    /// Get the path to `$HOME/.dria/dnet/load-history.jsonl`
+    pub fn path() -> PathBuf {
+        Config::dria_dir().join(Self::FILE_NAME)
+    }
+
+    /// Append the record to the history.
+    pub fn append(record: &LoadRecord) -> color_eyre::Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    /// Read the records of the history, the latest first, skipping unreadable lines.
+    pub fn read() -> Vec<LoadRecord> {
+        let content = fs::read_to_string(Self::path()).unwrap_or_default();
+        Self::parse(&content)
+    }
+
+    fn parse(content: &str) -> Vec<LoadRecord> {
+        let mut records = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect::<Vec<_>>();
+        records.reverse();
+        records
+    }
+}
+
+/// Number of the failed loads of each shard, the flakiest first.
+pub fn failures_by_shard(records: &[LoadRecord]) -> Vec<(&str, usize)> {
+    let mut failures: HashMap<&str, usize> = HashMap::new();
+    for shard in records.iter().flat_map(|r| &r.failed_shards) {
+        *failures.entry(shard).or_default() += 1;
+    }
+
+    let mut failures = failures.into_iter().collect::<Vec<_>>();
+    failures.sort_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+    failures
+}
+
+/// Past loads & unloads, read from [`LoadHistory`] when the view is opened.
+#[derive(Debug, Default)]
+pub struct HistoryState {
+    pub records: Vec<LoadRecord>,
+    pub selected: usize,
+}
+
+impl crate::App {
+    /// Append the record to the history, reporting (but not failing on) errors.
+    pub(crate) fn record_load(&mut self, record: LoadRecord) {
+        if let Err(e) = LoadHistory::append(&record) {
+            self.status_message = format!("Error: Could not write load history: {}", e);
+        }
+    }
+
+    /// Open the history of the loads & unloads.
+    pub(crate) fn open_load_history(&mut self) {
+        self.state.model.history = HistoryState {
+            records: LoadHistory::read(),
+            selected: 0,
+        };
+        self.view = AppView::Model(ModelView::History);
+    }
+
+    pub(super) fn draw_load_history(&mut self, frame: &mut Frame) {
+        let [
+            title_area,
+            summary_area,
+            table_area,
+            details_area,
+            footer_area,
+        ] = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Length(1), // Failures by shard
+            Constraint::Min(0),    // Records
+            Constraint::Length(4), // Details of the selected record
+            Constraint::Length(1), // Footer
+        ])
+        .areas(frame.area());
+
+        let title = Line::from("Load History").bold().blue().centered();
+        frame.render_widget(Paragraph::new(title), title_area);
+
+        let history = &self.state.model.history;
+        let failures = failures_by_shard(&history.records)
+            .into_iter()
+            .map(|(shard, n)| format!("{} ×{}", self.config.device_name(shard), n))
+            .collect::<Vec<_>>();
+        let summary = if failures.is_empty() {
+            Line::from("No shard has failed to load").dark_gray()
+        } else {
+            Line::from(format!("Failed loads by shard: {}", failures.join(", "))).yellow()
+        };
+        frame.render_widget(Paragraph::new(summary).centered(), summary_area);
+
+        self.draw_load_records(frame, table_area);
+
+        let history = &self.state.model.history;
+        let details = match history.records.get(history.selected) {
+            Some(record) => {
+                let mut lines = Vec::new();
+                if !record.failed_shards.is_empty() {
+                    let shards = record
+                        .failed_shards
+                        .iter()
+                        .map(|shard| self.config.device_name(shard))
+                        .collect::<Vec<_>>();
+                    lines.push(Line::from(format!("Failed shards: {}", shards.join(", "))).red());
+                }
+                if let Some(error) = &record.error {
+                    lines.push(Line::from(error.as_str()).red());
+                }
+                lines
+            }
+            None => Vec::new(),
+        };
+        frame.render_widget(
+            Paragraph::new(details)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title("Details")),
+            details_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "↑↓: Select | Esc: Back  |  {}",
+                LoadHistory::path().display()
+            ))
+            .centered()
+            .gray(),
+            footer_area,
+        );
+    }
+
+    fn draw_load_records(&self, frame: &mut Frame, area: Rect) {
+        let history = &self.state.model.history;
+        let rows = history.records.iter().map(|record| {
+            let action = match record.action {
+                LoadAction::Load => "load",
+                LoadAction::Unload => "unload",
+            };
+            let (outcome, color) = match (record.success, record.failed_shards.len()) {
+                (true, _) => ("✓ ok".to_string(), Color::Green),
+                (false, 0) => ("✗ failed".to_string(), Color::Red),
+                (false, n) => (format!("✗ {} shards failed", n), Color::Red),
+            };
+            Row::new(vec![
+                Cell::from(record.at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                Cell::from(action),
+                Cell::from(record.model.clone()),
+                Cell::from(format!("{:.1}s", record.duration_ms as f64 / 1000.0)),
+                Cell::from(outcome).fg(color),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(19),
+                Constraint::Length(6),
+                Constraint::Min(20),
+                Constraint::Length(8),
+                Constraint::Length(18),
+            ],
+        )
+        .header(Row::new(vec!["Time", "Action", "Model", "Duration", "Outcome"]).bold())
+        .block(Block::bordered().title(format!("Attempts ({})", history.records.len())))
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let mut state = TableState::default()
+            .with_selected((!history.records.is_empty()).then_some(history.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    pub(super) fn handle_load_history_input(&mut self, key: KeyEvent) {
+        let history = &mut self.state.model.history;
+        match key.code {
+            // opened from the developer menu
+            KeyCode::Esc => self.view = AppView::Developer(crate::developer::DeveloperView::Menu),
+            KeyCode::Up => history.selected = history.selected.saturating_sub(1),
+            KeyCode::Down => {
+                history.selected =
+                    (history.selected + 1).min(history.records.len().saturating_sub(1))
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ShardLoadStatus;
+
+    #[test]
+    fn test_load_records() {
+        let status = |instance: &str, success: bool| ShardLoadStatus {
+            instance: instance.to_string(),
+            success,
+            layers_loaded: None,
+            message: None,
+        };
+        let response = LoadModelResponse {
+            model: "model".to_string(),
+            success: false,
+            shard_statuses: vec![status("shard-a", true), status("shard-b", false)],
+            message: Some("Some shards failed".to_string()),
+        };
+        let failed = LoadRecord::load("model", Duration::from_secs(3), &Ok(response));
+        assert_eq!(failed.failed_shards, vec!["shard-b"]);
+        assert_eq!(failed.duration_ms, 3000);
+        let unload = LoadRecord::unload("model", Duration::ZERO, Err("timeout".to_string()));
+        assert!(!unload.success);
+
+        let content = [&failed, &unload, &failed]
+            .iter()
+            .map(|record| serde_json::to_string(record).unwrap())
+            .chain(["not a record".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n");
+        let records = LoadHistory::parse(&content);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1], unload);
+        assert_eq!(failures_by_shard(&records), vec![("shard-b", 2)]);
+    }
+}
@@ -0,0 +1,247 @@
+use super::{LoadModelView, ModelView};
+use crate::AppView;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tui_input::backend::crossterm::EventHandler;
+
+/// Models endpoint of the HuggingFace Hub API.
+const HUB_MODELS_URL: &str = "https://huggingface.co/api/models";
+
+/// Number of the repos listed per search.
+const HUB_SEARCH_LIMIT: usize = 50;
+
+/// A model repo on the HuggingFace Hub.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HubModel {
+    /// Repo id, e.g. `mlx-community/Qwen3-4B-4bit`.
+    pub id: String,
+    #[serde(default)]
+    pub downloads: u64,
+    #[serde(default)]
+    pub likes: u64,
+}
+
+/// Search the Hub for the MLX repos that match the query, the most downloaded first.
+pub async fn search_hub(query: &str) -> color_eyre::Result<Vec<HubModel>> {
+    let limit = HUB_SEARCH_LIMIT.to_string();
+    let response = reqwest::Client::new()
+        .get(HUB_MODELS_URL)
+        .query(&[
+            ("search", query),
+            ("filter", "mlx"),
+            ("sort", "downloads"),
+            ("direction", "-1"),
+            ("limit", &limit),
+        ])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        color_eyre::eyre::bail!("Failed to search the Hub: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+/// Browser of the MLX repos on the Hub, to load a model that the manager does not list.
+#[derive(Debug, Default)]
+pub struct HubBrowser {
+    pub query: tui_input::Input,
+    /// Query of the listed results, to tell whether Enter searches or loads.
+    pub searched: Option<String>,
+    pub results: Vec<HubModel>,
+    /// Error of the last search, if it failed.
+    pub error: Option<String>,
+    pub selected: usize,
+    /// Receiver of the results of the running search.
+    rx: Option<mpsc::UnboundedReceiver<Result<Vec<HubModel>, String>>>,
+}
+
+impl HubBrowser {
+    /// Search for the query in the background, replacing a running search.
+    pub fn search(&mut self) {
+        let query = self.query.value().trim().to_string();
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.searched = Some(query.clone());
+        tokio::spawn(async move {
+            tx.send(search_hub(&query).await.map_err(|e| e.to_string()))
+                .ok();
+        });
+        self.rx = Some(rx);
+    }
+
+    /// Collect the results of the search, if it has finished.
+    pub fn poll(&mut self) {
+        let Some(rx) = &mut self.rx else {
+            return;
+        };
+        if let Ok(results) = rx.try_recv() {
+            (self.results, self.error) = match results {
+                Ok(results) => (results, None),
+                Err(err) => (Vec::new(), Some(err)),
+            };
+            self.selected = 0;
+            self.rx = None;
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Repo that is highlighted, if the results are of the current query.
+    fn selected_model(&self) -> Option<&HubModel> {
+        if self.searched.as_deref() != Some(self.query.value().trim()) {
+            return None;
+        }
+        self.results.get(self.selected)
+    }
+}
+
+/// Downloads or likes in a short form, e.g. `12.3k`.
+fn format_count(count: u64) -> String {
+    match count {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1e6),
+        n if n >= 1_000 => format!("{:.1}k", n as f64 / 1e3),
+        n => n.to_string(),
+    }
+}
+
+impl crate::App {
+    /// Draw the search box and the results of the Hub browser.
+    pub(super) fn draw_hub_browser(&mut self, frame: &mut Frame, area: Rect) {
+        let hub = &self.state.model.hub;
+        let [query_area, results_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        // keep 2 for borders and 1 for cursor
+        let width = query_area.width.max(3) - 3;
+        let scroll = hub.query.visual_scroll(width as usize);
+        frame.render_widget(
+            Paragraph::new(hub.query.value())
+                .scroll((0, scroll as u16))
+                .block(
+                    Block::bordered()
+                        .title("Search MLX models on HuggingFace")
+                        .border_style(Style::default().fg(Color::Cyan)),
+                ),
+            query_area,
+        );
+        let x = hub.query.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((query_area.x + x as u16, query_area.y + 1));
+
+        let block = Block::bordered().title("Results");
+        match (&hub.error, &hub.results) {
+            _ if hub.is_searching() => frame.render_widget(
+                Paragraph::new("Searching...").block(block).centered(),
+                results_area,
+            ),
+            (Some(err), _) => frame.render_widget(
+                Paragraph::new(err.as_str()).red().block(block),
+                results_area,
+            ),
+            (None, results) if results.is_empty() => {
+                let message = match hub.searched {
+                    Some(_) => "No MLX models found",
+                    None => "Type a query and press Enter to search",
+                };
+                frame.render_widget(
+                    Paragraph::new(message).dark_gray().block(block).centered(),
+                    results_area,
+                );
+            }
+            (None, results) => {
+                let items = results
+                    .iter()
+                    .map(|model| {
+                        ListItem::new(Line::from(vec![
+                            format!("  {:<60}", model.id).into(),
+                            format!(
+                                " ↓ {:<8} ♥ {}",
+                                format_count(model.downloads),
+                                format_count(model.likes)
+                            )
+                            .dark_gray(),
+                        ]))
+                    })
+                    .collect::<Vec<_>>();
+                let list = List::new(items).block(block).highlight_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                );
+                let mut state = ListState::default().with_selected(Some(hub.selected));
+                frame.render_stateful_widget(list, results_area, &mut state);
+            }
+        }
+    }
+
+    /// Handle input in the Hub browser, where Enter searches for a new query
+    /// and loads the highlighted repo otherwise.
+    pub(super) fn handle_hub_browser_input(&mut self, key: KeyEvent) {
+        let hub = &mut self.state.model.hub;
+        let num_results = hub.results.len();
+        match key.code {
+            KeyCode::Esc => {
+                self.view = AppView::Model(ModelView::Load(LoadModelView::SelectingModel));
+            }
+            KeyCode::Up => hub.selected = hub.selected.saturating_sub(1),
+            KeyCode::Down => hub.selected = (hub.selected + 1).min(num_results.saturating_sub(1)),
+            KeyCode::Enter => match hub.selected_model() {
+                Some(model) => {
+                    let model = model.id.clone();
+                    self.begin_model_load(model);
+                }
+                None if !hub.query.value().trim().is_empty() => hub.search(),
+                None => {}
+            },
+            _ => {
+                hub.query.handle_event(&crossterm::event::Event::Key(key));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hub_models() {
+        let response = r#"[
+            {"_id": "1", "id": "mlx-community/Qwen3-4B-4bit", "downloads": 12345, "likes": 7, "tags": ["mlx"]},
+            {"_id": "2", "id": "mlx-community/gemma-3-1b-it-4bit"}
+        ]"#;
+        let models: Vec<HubModel> = serde_json::from_str(response).unwrap();
+        assert_eq!(models[0].id, "mlx-community/Qwen3-4B-4bit");
+        assert_eq!(format_count(models[0].downloads), "12.3k");
+        assert_eq!(models[1].downloads, 0);
+        assert_eq!(format_count(2_500_000), "2.5M");
+    }
+
+    #[test]
+    fn test_hub_browser_selection() {
+        let mut hub = HubBrowser {
+            query: tui_input::Input::new("qwen".to_string()),
+            searched: Some("qwen".to_string()),
+            results: vec![HubModel {
+                id: "mlx-community/Qwen3-4B-4bit".to_string(),
+                downloads: 0,
+                likes: 0,
+            }],
+            ..Default::default()
+        };
+        assert!(hub.selected_model().is_some());
+
+        // editing the query means that Enter searches again
+        hub.query = tui_input::Input::new("qwen3".to_string());
+        assert!(hub.selected_model().is_none());
+    }
+}
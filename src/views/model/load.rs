@@ -1,24 +1,52 @@
-use super::ModelView;
-use crate::common::LoadModelResponse;
+use super::{LoadRecord, ModelFit, ModelLoad, ModelView, WarmUpState};
+use crate::common::{LoadModelResponse, TopologyInfo};
 use crate::{App, AppView};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout},
     style::{Color, Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Paragraph},
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LoadModelView {
     SelectingModel,
+    /// Searching the HuggingFace Hub for a model that the manager does not list.
+    BrowsingHub,
+    /// Typing the id of a model that the manager does not list.
+    EnteringModel,
+    /// Picking a saved profile to load, see [`crate::config::LoadProfile`].
+    SelectingProfile,
+    /// Unloading the current model before loading another, see [`super::ModelState::switch_from`].
+    UnloadingModel(String /* model name */),
     PreparingTopology(String /* model name */),
     LoadingModel(String /* model name */),
     Error(String),
     Success(LoadModelResponse),
 }
 
+/// Check that the topology is a manual one (i.e. has no solution) that can be
+/// reused to load a model with the given number of layers (if known), instead
+/// of preparing a new topology with the solver.
+pub fn check_manual_topology(
+    topology: Option<&TopologyInfo>,
+    num_layers: Option<u64>,
+) -> Result<(), String> {
+    let Some(topology) = topology.filter(|t| t.solution.is_none() && !t.assignments.is_empty())
+    else {
+        return Err("There is no manual topology to keep".to_string());
+    };
+    match num_layers {
+        Some(num_layers) if num_layers != topology.num_layers as u64 => Err(format!(
+            "The manual topology has {} layers, but the model has {}",
+            topology.num_layers, num_layers
+        )),
+        _ => Ok(()),
+    }
+}
+
 impl App {
     pub(super) fn draw_load_model(&mut self, frame: &mut Frame, view: &LoadModelView) {
         let area = frame.area();
@@ -31,7 +59,30 @@ impl App {
         let [title_area, content_area, footer_area] = vertical.areas(area);
 
         // Title
-        let title = Line::from("Load Model").bold().blue().centered();
+        let title = match self.state.model.switch_from {
+            Some(_) => "Switch Model",
+            None => "Load Model",
+        };
+        let title = Line::from(title).bold().blue().centered();
+
+        // steps of a model switch are shown above the content
+        let content_area = match self.state.model.switch_from {
+            Some(_)
+                if matches!(
+                    view,
+                    LoadModelView::UnloadingModel(_)
+                        | LoadModelView::PreparingTopology(_)
+                        | LoadModelView::LoadingModel(_)
+                ) =>
+            {
+                let [steps_area, content_area] =
+                    Layout::vertical([Constraint::Length(5), Constraint::Min(0)])
+                        .areas(content_area);
+                self.draw_switch_steps(frame, steps_area, view);
+                content_area
+            }
+            _ => content_area,
+        };
         frame.render_widget(Paragraph::new(title), title_area);
 
         // Content
@@ -39,22 +90,29 @@ impl App {
             LoadModelView::SelectingModel => {
                 self.draw_model_selection(frame, content_area);
             }
-            LoadModelView::PreparingTopology(model) => {
+            LoadModelView::BrowsingHub => self.draw_hub_browser(frame, content_area),
+            LoadModelView::EnteringModel => self.draw_model_entry(frame, content_area),
+            LoadModelView::SelectingProfile => self.draw_profile_list(frame, content_area),
+            LoadModelView::UnloadingModel(_) => {
+                let model = self.state.model.switch_from.as_deref();
                 frame.render_widget(
-                    Paragraph::new(format!("Preparing topology for {}...", model))
+                    Paragraph::new(format!("Unloading {}...", model.unwrap_or("model")))
                         .block(Block::bordered())
                         .centered(),
                     content_area,
                 );
             }
-            LoadModelView::LoadingModel(model) => {
+            LoadModelView::PreparingTopology(model) => {
                 frame.render_widget(
-                    Paragraph::new(format!("Loading model {}...", model))
+                    Paragraph::new(format!("Preparing topology for {}...", model))
                         .block(Block::bordered())
                         .centered(),
                     content_area,
                 );
             }
+            LoadModelView::LoadingModel(model) => {
+                self.draw_load_progress(frame, content_area, model);
+            }
             LoadModelView::Error(err) => {
                 frame.render_widget(
                     Paragraph::new(format!("Error: {}", err))
@@ -72,25 +130,81 @@ impl App {
         // Footer
         let footer_text = match view {
             LoadModelView::SelectingModel => {
-                "Use ↑↓ to select model  |  Enter to load  |  Esc to go back"
+                "Use ↑↓ to select model  |  Type to filter  |  Ctrl+F to star  |  Tab to search HuggingFace  |  Ctrl+N to enter an ID  |  Ctrl+S to save a profile  |  Ctrl+P for profiles  |  Enter to load  |  Esc to go back"
+            }
+            LoadModelView::SelectingProfile => {
+                "Use ↑↓ to select profile  |  Enter to apply and load  |  d to delete  |  Esc to go back"
+            }
+            LoadModelView::BrowsingHub => {
+                "Type a query  |  ↑↓ to select  |  Enter to search (or load the selected)  |  Esc to go back"
+            }
+            LoadModelView::EnteringModel => {
+                "Type a model ID  |  Enter to validate and load  |  Esc to go back"
             }
             LoadModelView::Error(_) | LoadModelView::Success(_) => "Press Esc to go back",
             _ => "Loading...",
         };
-        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+        let status = if self.status_message.starts_with("Error") {
+            Line::from(self.status_message.as_str()).red()
+        } else {
+            Line::from(self.status_message.as_str()).green()
+        };
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(footer_text).gray(), status]).centered(),
+            footer_area,
+        );
     }
 
     fn draw_model_selection(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let [area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(area);
+
+        // favorite & recent models are sorted to the top, see `sort_models`
         let model_names: Vec<String> = self
             .available_models
             .iter()
-            .map(|model| model.id.clone())
+            .map(|model| match self.config.model_rank(&model.id) {
+                (0, _) => format!("★ {}", model.id),
+                (1, _) => format!("↺ {}", model.id),
+                _ => model.id.clone(),
+            })
+            .collect();
+
+        // fit of the weights into the cluster, once both are known
+        let memory_total = self.state.model.cluster.total();
+        let badges: Vec<Span> = self
+            .available_models
+            .iter()
+            .map(|model| {
+                let weight_bytes = match self.state.model.preview.get(&model.id) {
+                    Some(Ok(summary)) => summary.weight_bytes,
+                    _ => None,
+                };
+                weight_bytes
+                    .and_then(|bytes| ModelFit::new(bytes, memory_total))
+                    .map_or_else(|| Span::raw(""), |fit| fit.badge())
+            })
             .collect();
 
+        let title = if self.state.model.keep_topology {
+            "Select a model (keeping the manual topology, Ctrl+T to prepare a new one)"
+        } else if check_manual_topology(self.topology.as_ref(), None).is_ok() {
+            "Select a model (Ctrl+T to keep the manual topology)"
+        } else {
+            "Select a model"
+        };
         let selector = crate::widgets::ModelSelector::new(&model_names)
-            .block(Block::bordered().title("Select a model"));
+            .badges(&badges)
+            .block(Block::bordered().title(title));
 
         frame.render_stateful_widget(selector, area, &mut self.model_selector_state);
+
+        let model = self.highlighted_model().map(str::to_string);
+        self.draw_model_preview(frame, preview_area, model.as_deref());
+        if let Some(model) = &model {
+            self.draw_profile_prompt(frame, area, model);
+        }
     }
 
     fn draw_load_success(
@@ -154,19 +268,68 @@ impl App {
             }
         }
 
+        let warm_up = &self.state.model.warm_up;
+        if let Some(result) = &warm_up.result {
+            lines.push(Line::from(""));
+            match result {
+                Ok(warm_up) => {
+                    let ttft = warm_up
+                        .stats
+                        .ttft()
+                        .map_or_else(|| "?".to_string(), |d| format!("{:.2}s", d.as_secs_f64()));
+                    lines.push(Line::from(format!("Warm-up: TTFT {}", ttft)).green());
+                    lines.push(Line::from(format!("  \"{}\"", warm_up.preview(60))).dim());
+                }
+                Err(err) => lines.push(Line::from(format!("Warm-up failed: {}", err)).red()),
+            }
+        } else if warm_up.is_running() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Warming up...").yellow());
+        }
+
         let paragraph = Paragraph::new(lines).block(Block::bordered().title("Load Complete"));
         frame.render_widget(paragraph, area);
     }
 
     pub(super) fn handle_load_model_input(&mut self, key: KeyEvent, state: &LoadModelView) {
         match state {
+            LoadModelView::SelectingModel if self.state.model.profiles.name.is_some() => {
+                if let Some(model) = self.highlighted_model().map(str::to_string) {
+                    self.handle_profile_prompt_input(key, &model);
+                }
+            }
             LoadModelView::SelectingModel => match (key.modifiers, key.code) {
-                (_, KeyCode::Esc) => self.view = AppView::Menu,
+                (_, KeyCode::Esc) if self.model_selector_state.filter().is_empty() => {
+                    self.view = AppView::Menu
+                }
+                (_, KeyCode::Esc) => self.model_selector_state.clear_filter(),
                 (_, KeyCode::Up) => self.model_up(),
                 (_, KeyCode::Down) => self.model_down(),
                 (_, KeyCode::Enter) => self.start_model_load(),
+                (_, KeyCode::Backspace) => self.model_selector_state.pop_filter(),
+                (KeyModifiers::CONTROL, KeyCode::Char('f')) => self.toggle_favorite_model(),
+                (KeyModifiers::CONTROL, KeyCode::Char('t')) => self.toggle_keep_topology(),
+                (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
+                    if let Some(model) = self.highlighted_model().map(str::to_string) {
+                        self.open_profile_prompt(&model);
+                    }
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('p')) => {
+                    self.status_message.clear();
+                    self.view = AppView::Model(ModelView::Load(LoadModelView::SelectingProfile));
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('n')) => {
+                    self.view = AppView::Model(ModelView::Load(LoadModelView::EnteringModel));
+                }
+                (_, KeyCode::Tab) => {
+                    self.view = AppView::Model(ModelView::Load(LoadModelView::BrowsingHub));
+                }
+                (_, KeyCode::Char(c)) => self.model_selector_state.push_filter(c),
                 _ => {}
             },
+            LoadModelView::BrowsingHub => self.handle_hub_browser_input(key),
+            LoadModelView::EnteringModel => self.handle_model_entry_input(key),
+            LoadModelView::SelectingProfile => self.handle_profile_list_input(key),
             LoadModelView::Error(_) | LoadModelView::Success(_) => {
                 // only allow escape
                 if key.code == KeyCode::Esc {
@@ -177,53 +340,230 @@ impl App {
         }
     }
 
+    /// Number of the models that match the filter of the selector.
+    fn num_model_matches(&self) -> usize {
+        let models = self.available_models.iter().map(|m| &m.id);
+        self.model_selector_state.matches(models).len()
+    }
+
     fn model_up(&mut self) {
-        self.model_selector_state
-            .move_up(self.available_models.len());
+        let num_matches = self.num_model_matches();
+        self.model_selector_state.move_up(num_matches);
     }
 
     fn model_down(&mut self) {
-        self.model_selector_state
-            .move_down(self.available_models.len());
+        let num_matches = self.num_model_matches();
+        self.model_selector_state.move_down(num_matches);
+    }
+
+    /// Sort the favorite and the recent models to the top, see [`crate::config::Config::model_rank`].
+    pub(crate) fn sort_models(&mut self) {
+        let config = &self.config;
+        self.available_models
+            .sort_by_key(|model| config.model_rank(&model.id));
+    }
+
+    /// Remember the loaded model as a recent one, and save the config.
+    pub(crate) fn remember_loaded_model(&mut self, model: &str) {
+        self.config.record_recent_model(model);
+        self.sort_models();
+        if let Err(e) = self.config.save_to_dria() {
+            self.status_message = format!("Error: Could not save config: {}", e);
+        }
+    }
+
+    /// Star the highlighted model (or unstar it), keeping it highlighted.
+    fn toggle_favorite_model(&mut self) {
+        let Some(model) = self.highlighted_model().map(str::to_string) else {
+            return;
+        };
+        if !self.config.favorite_models.remove(&model) {
+            self.config.favorite_models.insert(model.clone());
+        }
+        self.sort_models();
+
+        let models = self.available_models.iter().map(|m| &m.id);
+        let matches = self.model_selector_state.matches(models);
+        if let Some(i) = matches
+            .iter()
+            .position(|&i| self.available_models[i].id == model)
+        {
+            self.model_selector_state.select(i);
+        }
+
+        self.status_message = match self.config.save_to_dria() {
+            Ok(()) => String::new(),
+            Err(e) => format!("Error: Could not save config: {}", e),
+        };
+    }
+
+    /// Keep the manual topology when loading (or not), if there is one.
+    fn toggle_keep_topology(&mut self) {
+        let keep_topology = &mut self.state.model.keep_topology;
+        match check_manual_topology(self.topology.as_ref(), None) {
+            _ if *keep_topology => *keep_topology = false,
+            Ok(()) => *keep_topology = true,
+            Err(err) => self.status_message = format!("Error: {}", err),
+        }
+    }
+
+    /// Model that is highlighted in the selector, if any matches the filter.
+    fn highlighted_model(&self) -> Option<&str> {
+        let models = self.available_models.iter().map(|m| &m.id);
+        let i = self.model_selector_state.selected_item(models)?;
+        Some(&self.available_models[i].id)
     }
 
     fn start_model_load(&mut self) {
-        let model = self.available_models[self.model_selector_state.selected()]
-            .id
-            .clone();
-        self.view = AppView::Model(ModelView::Load(LoadModelView::PreparingTopology(model)));
+        let Some(model) = self.highlighted_model().map(str::to_string) else {
+            return;
+        };
+        self.begin_model_load(model);
+    }
+
+    /// Load the model, unloading the current one first if switching models.
+    pub(super) fn begin_model_load(&mut self, model: String) {
+        if self.state.model.switch_from.is_none() && self.loaded_models().contains(&model) {
+            self.status_message = format!("Error: {} is already loaded", model);
+            return;
+        }
+        self.view = AppView::Model(ModelView::Load(match self.state.model.switch_from {
+            Some(_) => LoadModelView::UnloadingModel(model),
+            None => LoadModelView::PreparingTopology(model),
+        }));
     }
 
     /// Handle async operations for load model state (called during tick).
     pub(super) async fn tick_load_model(&mut self, state: &LoadModelView) {
         match state {
+            LoadModelView::SelectingModel => {
+                // the highlighted model comes first, then the rest for their badges
+                let highlighted = self.highlighted_model().map(str::to_string);
+                let preview = &mut self.state.model.preview;
+                preview.poll();
+                let unknown = self
+                    .available_models
+                    .iter()
+                    .find(|m| preview.get(&m.id).is_none())
+                    .map(|m| m.id.clone());
+                match highlighted {
+                    Some(model) if preview.get(&model).is_none() => preview.request(&model),
+                    _ if preview.is_pending() => {}
+                    _ => {
+                        if let Some(model) = unknown {
+                            preview.request(&model);
+                        }
+                    }
+                }
+
+                self.state
+                    .model
+                    .cluster
+                    .update(&self.api, &self.config)
+                    .await;
+            }
+            LoadModelView::UnloadingModel(model) => {
+                let started_at = std::time::Instant::now();
+                let from = self.state.model.switch_from.clone().unwrap_or_default();
+                let result = self
+                    .api
+                    .unload_model(Some(&from))
+                    .await
+                    .map_err(|e| e.to_string());
+                let record =
+                    LoadRecord::unload(&from, started_at.elapsed(), result.clone().map(|_| ()));
+                self.record_load(record);
+
+                match result {
+                    Ok(_) => {
+                        self.forget_loaded_model(&from);
+                        self.view = AppView::Model(ModelView::Load(
+                            LoadModelView::PreparingTopology(model.clone()),
+                        ));
+                    }
+                    Err(err) => {
+                        self.state.model.switch_from = None;
+                        self.view = AppView::Model(ModelView::Load(LoadModelView::Error(format!(
+                            "Could not unload the current model: {}",
+                            err
+                        ))));
+                    }
+                }
+            }
+            LoadModelView::PreparingTopology(model) if self.state.model.keep_topology => {
+                // the manager already has the manual topology, so the model is loaded onto it
+                let num_layers = match self.state.model.preview.get(model) {
+                    Some(Ok(summary)) => summary.num_layers,
+                    _ => None,
+                };
+                self.view = AppView::Model(ModelView::Load(
+                    match check_manual_topology(self.topology.as_ref(), num_layers) {
+                        Ok(()) => {
+                            let load =
+                                ModelLoad::start(self.api.clone(), model, self.topology.as_ref());
+                            self.state.model.load = Some(load);
+                            LoadModelView::LoadingModel(model.clone())
+                        }
+                        Err(err) => LoadModelView::Error(err),
+                    },
+                ));
+            }
             LoadModelView::PreparingTopology(model) => {
+                let started_at = std::time::Instant::now();
                 match self.api.prepare_topology(&self.config, model).await {
                     Ok(topology) => {
-                        // move to loading model state and trigger load
+                        // load the model in the background, tracking its shards
+                        let load = ModelLoad::start(self.api.clone(), model, Some(&topology));
+                        self.state.model.load = Some(load);
+                        self.topology = Some(topology);
                         self.view = AppView::Model(ModelView::Load(LoadModelView::LoadingModel(
                             model.clone(),
                         )));
-                        self.topology = Some(topology);
+                    }
+                    Err(err) => {
+                        let result = Err(format!("Failed to prepare topology: {}", err));
+                        self.record_load(LoadRecord::load(model, started_at.elapsed(), &result));
 
-                        // load the model
-                        match self.api.load_model(model).await {
-                            Ok(load_response) => {
-                                self.view = AppView::Model(ModelView::Load(
-                                    LoadModelView::Success(load_response),
-                                ));
-                            }
-                            Err(err) => {
-                                self.view = AppView::Model(ModelView::Load(LoadModelView::Error(
-                                    err.to_string(),
-                                )));
-                            }
+                        // the previous model is gone by now, so make that clear
+                        let err = match self.state.model.switch_from.take() {
+                            Some(from) => format!("{} was unloaded, but {}", from, err),
+                            None => err.to_string(),
+                        };
+                        self.view = AppView::Model(ModelView::Load(LoadModelView::Error(err)));
+                    }
+                }
+            }
+            LoadModelView::BrowsingHub => self.state.model.hub.poll(),
+            LoadModelView::Success(_) => self.state.model.warm_up.poll(),
+            LoadModelView::EnteringModel => self.tick_model_entry(),
+            LoadModelView::LoadingModel(model) => {
+                let result = self.state.model.load.as_mut().and_then(ModelLoad::poll);
+                if let Some(result) = result {
+                    let load = self.state.model.load.take();
+                    self.state.model.switch_from = None;
+                    if let Some(load) = &load {
+                        let elapsed = load.started_at.elapsed();
+                        self.record_load(LoadRecord::load(model, elapsed, &result));
+                        // saved along with the recent model
+                        if result.is_ok() {
+                            self.config
+                                .record_load_duration(load.duration_key(model), elapsed);
                         }
                     }
-                    Err(err) => {
-                        self.view =
-                            AppView::Model(ModelView::Load(LoadModelView::Error(err.to_string())));
+                    self.state.model.warm_up = WarmUpState::default();
+                    if result.is_ok() {
+                        self.remember_loaded_model(model);
+                        if self.config.warm_up {
+                            self.state
+                                .model
+                                .warm_up
+                                .start(self.config.api_url(), model.clone());
+                        }
                     }
+                    self.view = AppView::Model(ModelView::Load(match result {
+                        Ok(response) => LoadModelView::Success(response),
+                        Err(err) => LoadModelView::Error(err),
+                    }));
                 }
             }
             _ => {
@@ -232,3 +572,35 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::AssignmentInfo;
+    use crate::config::KVBits;
+
+    #[test]
+    fn test_check_manual_topology() {
+        let mut topology = TopologyInfo {
+            model: None,
+            num_layers: 28,
+            devices: vec![],
+            assignments: vec![AssignmentInfo {
+                instance: "shard-a".to_string(),
+                layers: vec![(0..28).collect()],
+                next_instance: "shard-a".to_string(),
+                window_size: 28,
+                residency_size: 28,
+            }],
+            solution: None,
+            kv_bits: KVBits::default(),
+        };
+        assert!(check_manual_topology(Some(&topology), None).is_ok());
+        assert!(check_manual_topology(Some(&topology), Some(28)).is_ok());
+        assert!(check_manual_topology(Some(&topology), Some(36)).is_err());
+
+        topology.solution = Some(serde_json::json!({"Optimized": {}}));
+        assert!(check_manual_topology(Some(&topology), None).is_err());
+        assert!(check_manual_topology(None, None).is_err());
+    }
+}
@@ -4,18 +4,119 @@ pub use load::*;
 mod unload;
 pub use unload::*;
 
+mod preview;
+pub use preview::*;
+
+mod progress;
+pub use progress::*;
+
+mod hub;
+pub use hub::*;
+
+mod entry;
+pub use entry::*;
+
+mod switch;
+pub use switch::*;
+
+mod fit;
+pub use fit::*;
+
+mod profiles;
+pub use profiles::*;
+
+mod history;
+pub use history::*;
+
+mod warmup;
+pub use warmup::*;
+
+#[derive(Debug, Default)]
+pub struct ModelState {
+    /// Summaries of the models highlighted when loading a model.
+    pub preview: ModelPreview,
+    /// Model being loaded, if any.
+    pub load: Option<ModelLoad>,
+    /// Browser of the models on the HuggingFace Hub.
+    pub hub: HubBrowser,
+    /// Entry of a model id that is not listed.
+    pub entry: ModelEntry,
+    /// Model being switched from, if the loaded model is to be unloaded first.
+    pub switch_from: Option<String>,
+    /// Memory of the cluster, to check whether the models fit into it.
+    pub cluster: ClusterMemory,
+    /// Saved load profiles to pick from, and the prompt to save one.
+    pub profiles: ProfilesState,
+    /// Whether the manual topology is kept when loading, instead of preparing a new one.
+    pub keep_topology: bool,
+    /// Past loads & unloads.
+    pub history: HistoryState,
+    /// Smoke test of the loaded model.
+    pub warm_up: WarmUpState,
+    /// Index of the model picked to unload, when several are loaded.
+    pub unload_selected: usize,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ModelView {
     Load(LoadModelView),
     Unload(UnloadModelView),
+    /// History of the loads & unloads, see [`LoadHistory`].
+    History,
 }
 
 impl crate::App {
+    /// Models that are loaded, one per active topology, the shown topology's first.
+    pub fn loaded_models(&self) -> Vec<String> {
+        let mut models: Vec<String> = self
+            .topology
+            .iter()
+            .filter_map(|t| t.model.clone())
+            .collect();
+        for model in self
+            .state
+            .topology
+            .topologies
+            .iter()
+            .filter_map(|t| t.model.as_ref())
+        {
+            if !models.contains(model) {
+                models.push(model.clone());
+            }
+        }
+        models
+    }
+
+    /// Topology of a loaded model, if known.
+    pub fn model_topology(&self, model: &str) -> Option<&crate::common::TopologyInfo> {
+        self.topology
+            .iter()
+            .chain(&self.state.topology.topologies)
+            .find(|t| t.model.as_deref() == Some(model))
+    }
+
+    /// Forget the topology of an unloaded model, showing that of another loaded model if any.
+    pub(crate) fn forget_loaded_model(&mut self, model: &str) {
+        let topologies = &mut self.state.topology.topologies;
+        topologies.retain(|t| t.model.as_deref() != Some(model));
+        if self.topology.as_ref().and_then(|t| t.model.as_deref()) == Some(model) {
+            match topologies.first() {
+                Some(topology) => self.topology = Some(topology.clone()),
+                None => {
+                    if let Some(topology) = &mut self.topology {
+                        topology.model = None;
+                    }
+                }
+            }
+        }
+    }
+
     /// Handle async operations for model state (called during tick).
     pub(crate) async fn tick_model(&mut self, view: &ModelView) {
         match view {
             ModelView::Load(load_state) => self.tick_load_model(load_state).await,
             ModelView::Unload(unload_state) => self.tick_unload_model(unload_state).await,
+            ModelView::History => {}
         }
     }
 
@@ -24,6 +125,7 @@ impl crate::App {
         match view {
             ModelView::Load(load_state) => self.draw_load_model(frame, load_state),
             ModelView::Unload(unload_state) => self.draw_unload_model(frame, unload_state),
+            ModelView::History => self.draw_load_history(frame),
         }
     }
 
@@ -36,6 +138,7 @@ impl crate::App {
         match state {
             ModelView::Load(load_state) => self.handle_load_model_input(key, load_state),
             ModelView::Unload(unload_state) => self.handle_unload_model_input(key, unload_state),
+            ModelView::History => self.handle_load_history_input(key),
         }
     }
 }
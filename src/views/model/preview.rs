@@ -0,0 +1,159 @@
+use crate::utils::{ModelConfig, format_bytes};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Paragraph, Wrap},
+};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// Metadata of a model, read from its config before it is loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSummary {
+    pub num_layers: Option<u64>,
+    /// Estimated number of parameters, see [`ModelConfig::num_params`].
+    pub num_params: Option<u64>,
+    /// Quantization of the weights, e.g. `4-bit` or `bfloat16`.
+    pub quantization: Option<String>,
+    /// Estimated size of the weights, in bytes.
+    pub weight_bytes: Option<u64>,
+}
+
+impl ModelSummary {
+    pub fn new(config: &ModelConfig) -> Self {
+        let num_params = config.num_params();
+        Self {
+            num_layers: config.num_layers(),
+            num_params,
+            quantization: config.quantization(),
+            weight_bytes: num_params.map(|n| (n as f64 * config.bytes_per_weight()) as u64),
+        }
+    }
+}
+
+/// Summaries of the models highlighted in the selector, keyed by model.
+///
+/// The config of a model is fetched in the background the first time it is
+/// highlighted, and only the latest request is kept when scrolling quickly.
+#[derive(Debug, Default)]
+pub struct ModelPreview {
+    summaries: HashMap<String, Result<ModelSummary, String>>,
+    /// Model being fetched, along with the receiver of its summary.
+    pending: Option<(
+        String,
+        mpsc::UnboundedReceiver<Result<ModelSummary, String>>,
+    )>,
+}
+
+impl ModelPreview {
+    /// Fetch the summary of the model, unless it is known or being fetched.
+    pub fn request(&mut self, model: &str) {
+        let is_pending = self.pending.as_ref().is_some_and(|(m, _)| m == model);
+        if is_pending || self.summaries.contains_key(model) {
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let repo_id = model.to_string();
+        tokio::spawn(async move {
+            let summary = ModelConfig::get_model_config(&repo_id)
+                .await
+                .map(|config| ModelSummary::new(&config))
+                .map_err(|e| e.to_string());
+            tx.send(summary).ok();
+        });
+        self.pending = Some((model.to_string(), rx));
+    }
+
+    /// Collect the summary being fetched, if it has arrived.
+    pub fn poll(&mut self) {
+        let Some((model, rx)) = &mut self.pending else {
+            return;
+        };
+        if let Ok(summary) = rx.try_recv() {
+            self.summaries.insert(model.clone(), summary);
+            self.pending = None;
+        }
+    }
+
+    /// Whether a summary is being fetched.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Summary of the model, if it was fetched.
+    pub fn get(&self, model: &str) -> Option<&Result<ModelSummary, String>> {
+        self.summaries.get(model)
+    }
+}
+
+/// Number of parameters in a short form, e.g. `7.6B` or `494M`.
+pub fn format_params(num_params: u64) -> String {
+    match num_params {
+        n if n >= 1_000_000_000 => format!("{:.1}B", n as f64 / 1e9),
+        n if n >= 1_000_000 => format!("{:.0}M", n as f64 / 1e6),
+        n => n.to_string(),
+    }
+}
+
+impl crate::App {
+    /// Draw the summary of the highlighted model next to the selector.
+    pub(super) fn draw_model_preview(&self, frame: &mut Frame, area: Rect, model: Option<&str>) {
+        let block = Block::bordered().title("Model Details");
+        let lines = match model.map(|m| (m, self.state.model.preview.get(m))) {
+            None => vec![Line::from("No model matches the filter.").dark_gray()],
+            Some((_, None)) => vec![Line::from("Fetching model config...").dark_gray()],
+            Some((_, Some(Err(err)))) => vec![
+                Line::from("Could not fetch the model config").red(),
+                Line::from(err.as_str()).dark_gray(),
+            ],
+            Some((model, Some(Ok(summary)))) => {
+                let or_unknown =
+                    |value: Option<String>| value.unwrap_or_else(|| "unknown".to_string());
+                vec![
+                    Line::from(model.to_string()).bold().cyan(),
+                    Line::from(""),
+                    Line::from(format!(
+                        "Layers:       {}",
+                        or_unknown(summary.num_layers.map(|n| n.to_string()))
+                    )),
+                    Line::from(format!(
+                        "Parameters:   ~{}",
+                        or_unknown(summary.num_params.map(format_params))
+                    )),
+                    Line::from(format!(
+                        "Quantization: {}",
+                        or_unknown(summary.quantization.clone())
+                    )),
+                    Line::from(format!(
+                        "Weights:      ~{}",
+                        or_unknown(summary.weight_bytes.map(format_bytes))
+                    )),
+                    Line::from(""),
+                    Line::from("Estimated from the model config.").dark_gray(),
+                ]
+            }
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_params() {
+        assert_eq!(format_params(7_615_616_512), "7.6B");
+        assert_eq!(format_params(494_032_768), "494M");
+        assert_eq!(format_params(1_000), "1000");
+    }
+}
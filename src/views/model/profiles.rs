@@ -0,0 +1,189 @@
+use super::{LoadModelView, ModelView};
+use crate::AppView;
+use crate::config::LoadProfile;
+use crate::utils::centered_rect;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use tui_input::backend::crossterm::EventHandler;
+
+/// Saved load profiles, see [`LoadProfile`].
+#[derive(Debug, Default)]
+pub struct ProfilesState {
+    /// Selected profile in the quick-load list.
+    pub selected: usize,
+    /// Name of the profile being saved for the highlighted model, if the prompt is open.
+    pub name: Option<tui_input::Input>,
+}
+
+impl crate::App {
+    /// Open the prompt to save the highlighted model with the current settings.
+    pub(super) fn open_profile_prompt(&mut self, model: &str) {
+        // short name of the repo as the default, e.g. `Qwen3-4B-4bit`
+        let name = model.rsplit('/').next().unwrap_or(model).to_string();
+        self.state.model.profiles.name = Some(tui_input::Input::new(name));
+    }
+
+    /// Draw the prompt to name the profile (if open) on top of the selector.
+    pub(super) fn draw_profile_prompt(&self, frame: &mut Frame, area: Rect, model: &str) {
+        let Some(input) = &self.state.model.profiles.name else {
+            return;
+        };
+
+        let popup_area = centered_rect(60, 30, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .title(" Save Load Profile ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [help_area, input_area, hint_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let help = vec![
+            Line::from(vec!["Model: ".into(), model.to_string().bold().cyan()]),
+            Line::from(
+                format!(
+                    "KV bits: {}  |  Seq len: {}  |  Max batch exp: {}",
+                    self.config.kv_bits, self.config.seq_len, self.config.max_batch_exp
+                )
+                .dark_gray(),
+            ),
+        ];
+        frame.render_widget(Paragraph::new(help), help_area);
+
+        // keep 2 for borders and 1 for cursor
+        let width = input_area.width.max(3) - 3;
+        let scroll = input.visual_scroll(width as usize);
+        frame.render_widget(
+            Paragraph::new(input.value())
+                .scroll((0, scroll as u16))
+                .block(
+                    Block::bordered()
+                        .title("Name")
+                        .border_style(Style::default().fg(Color::Cyan)),
+                ),
+            input_area,
+        );
+        let x = input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        frame.render_widget(
+            Paragraph::new("Enter: Save | Esc: Cancel")
+                .centered()
+                .gray(),
+            hint_area,
+        );
+    }
+
+    /// Handle input while the prompt to name the profile is open.
+    pub(super) fn handle_profile_prompt_input(&mut self, key: KeyEvent, model: &str) {
+        let Some(input) = &mut self.state.model.profiles.name else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.state.model.profiles.name = None,
+            KeyCode::Enter => {
+                let name = input.value().trim().to_string();
+                if name.is_empty() {
+                    self.status_message = "Error: The profile needs a name".to_string();
+                    return;
+                }
+                self.state.model.profiles.name = None;
+                let profile = LoadProfile::new(&name, model, &self.config);
+                self.config.save_profile(profile);
+                self.status_message = match self.config.save_to_dria() {
+                    Ok(()) => format!("Saved profile {}", name),
+                    Err(e) => format!("Error: Could not save config: {}", e),
+                };
+            }
+            _ => {
+                input.handle_event(&crossterm::event::Event::Key(key));
+            }
+        }
+    }
+
+    /// Draw the saved profiles to pick one to load.
+    pub(super) fn draw_profile_list(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title("Load Profiles");
+        let profiles = &self.config.load_profiles;
+        if profiles.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No saved profiles, press Ctrl+S in the selector to save one.")
+                    .dark_gray()
+                    .block(block)
+                    .centered(),
+                area,
+            );
+            return;
+        }
+
+        let items = profiles
+            .iter()
+            .map(|profile| {
+                ListItem::new(Line::from(vec![
+                    format!("  {:<24}", profile.name).bold(),
+                    format!("{:<48}", profile.model).into(),
+                    format!(
+                        " kv {} | seq {} | batch 2^{}",
+                        profile.kv_bits, profile.seq_len, profile.max_batch_exp
+                    )
+                    .dark_gray(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items).block(block).highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        let selected = self.state.model.profiles.selected.min(profiles.len() - 1);
+        let mut state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Handle input in the list of profiles, where Enter applies the settings of
+    /// the selected profile and loads its model.
+    pub(super) fn handle_profile_list_input(&mut self, key: KeyEvent) {
+        let num_profiles = self.config.load_profiles.len();
+        let selected = &mut self.state.model.profiles.selected;
+        *selected = (*selected).min(num_profiles.saturating_sub(1));
+        match key.code {
+            KeyCode::Esc => {
+                self.view = AppView::Model(ModelView::Load(LoadModelView::SelectingModel));
+            }
+            KeyCode::Up => *selected = selected.saturating_sub(1),
+            KeyCode::Down => *selected = (*selected + 1).min(num_profiles.saturating_sub(1)),
+            KeyCode::Char('d') | KeyCode::Delete if num_profiles > 0 => {
+                let profile = self.config.load_profiles.remove(*selected);
+                self.status_message = match self.config.save_to_dria() {
+                    Ok(()) => format!("Deleted profile {}", profile.name),
+                    Err(e) => format!("Error: Could not save config: {}", e),
+                };
+            }
+            KeyCode::Enter if num_profiles > 0 => {
+                let profile = self.config.load_profiles[*selected].clone();
+                let model = self.config.apply_profile(&profile);
+                self.status_message = match self.config.save_to_dria() {
+                    Ok(()) => format!("Applied profile {}", profile.name),
+                    Err(e) => format!("Error: Could not save config: {}", e),
+                };
+                self.begin_model_load(model);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use crate::common::{
+    ApiClient, DeviceProperties, DownloadProgress, LoadModelResponse, ShardHealth, TopologyInfo,
+};
+use crate::utils::format_bytes;
+use crate::views::topology::ShardView;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// How often the health of the shards is polled while the model loads.
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress of a shard while the model loads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShardProgress {
+    /// The shard has not been reached yet.
+    Pending,
+    /// The shard is downloading the weights of its layers, along with the
+    /// progress of the download if the shard reports it.
+    Downloading(Option<DownloadProgress>),
+    /// The shard is loading its layers.
+    Loading,
+    Done,
+    Failed(String),
+}
+
+impl ShardProgress {
+    /// Progress of a shard from its health, where either the download is reported
+    /// or the status is expected to mention it while the weights are being downloaded.
+    pub fn from_health(health: &ShardHealth) -> Self {
+        if health.model_loaded {
+            ShardProgress::Done
+        } else if let Some(download) = health.download.filter(|d| !d.is_complete()) {
+            ShardProgress::Downloading(Some(download))
+        } else if health.status.to_lowercase().contains("download") {
+            ShardProgress::Downloading(None)
+        } else {
+            ShardProgress::Loading
+        }
+    }
+
+    /// Symbol & color of the progress, along with its label.
+    fn display(&self) -> (&'static str, Color, String) {
+        match self {
+            ShardProgress::Pending => ("○", Color::DarkGray, "pending".to_string()),
+            ShardProgress::Downloading(None) => ("↓", Color::Cyan, "downloading".to_string()),
+            ShardProgress::Downloading(Some(download)) => {
+                ("↓", Color::Cyan, format_download(download))
+            }
+            ShardProgress::Loading => ("◐", Color::Yellow, "loading".to_string()),
+            ShardProgress::Done => ("✓", Color::Green, "done".to_string()),
+            ShardProgress::Failed(err) => ("✗", Color::Red, format!("failed: {}", err)),
+        }
+    }
+}
+
+/// A model being loaded in the background, along with the progress of its shards
+/// which is polled from their health so that long loads are not a black box.
+#[derive(Debug)]
+pub struct ModelLoad {
+    /// Progress of each shard by instance, in the order of the topology.
+    pub shards: Vec<(String, ShardProgress)>,
+    /// Shards to poll the health of.
+    devices: Vec<DeviceProperties>,
+    pub started_at: Instant,
+    /// Last time the health of the shards was polled.
+    polled_at: Option<Instant>,
+    /// Receiver of the result of the load.
+    rx: mpsc::UnboundedReceiver<Result<LoadModelResponse, String>>,
+    /// Receiver of the health of the shards being polled.
+    health_rx: Option<mpsc::UnboundedReceiver<(String, Result<ShardHealth, String>)>>,
+}
+
+impl ModelLoad {
+    /// Load the model with the manager in the background, tracking the shards of the topology.
+    pub fn start(api: ApiClient, model: &str, topology: Option<&TopologyInfo>) -> Self {
+        let devices = topology.map_or_else(Vec::new, |topology| {
+            topology
+                .devices
+                .iter()
+                .filter(|d| topology.assignment_of(&d.instance).is_some())
+                .cloned()
+                .collect()
+        });
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let model = model.to_string();
+        tokio::spawn(async move {
+            tx.send(api.load_model(&model).await.map_err(|e| e.to_string()))
+                .ok();
+        });
+
+        Self {
+            shards: devices
+                .iter()
+                .map(|d| (d.instance.clone(), ShardProgress::Pending))
+                .collect(),
+            devices,
+            started_at: Instant::now(),
+            polled_at: None,
+            rx,
+            health_rx: None,
+        }
+    }
+
+    /// Poll the health of the shards (periodically) and collect what has arrived,
+    /// returning the result of the load once it has finished.
+    pub fn poll(&mut self) -> Option<Result<LoadModelResponse, String>> {
+        let should_poll = self
+            .polled_at
+            .is_none_or(|at| at.elapsed() >= PROGRESS_POLL_INTERVAL);
+        if should_poll && self.health_rx.is_none() {
+            let (tx, rx) = mpsc::unbounded_channel();
+            for device in &self.devices {
+                let tx = tx.clone();
+                let device = device.clone();
+                tokio::spawn(async move {
+                    let health = ShardView::fetch(&device.local_ip, device.server_port).await;
+                    tx.send((device.instance, health)).ok();
+                });
+            }
+            self.health_rx = Some(rx);
+            self.polled_at = Some(Instant::now());
+        }
+
+        if let Some(health_rx) = &mut self.health_rx {
+            loop {
+                match health_rx.try_recv() {
+                    Ok((instance, health)) => {
+                        // unreachable shards are still pending, e.g. while restarting
+                        if let (Ok(health), Some((_, progress))) =
+                            (health, self.shards.iter_mut().find(|(i, _)| *i == instance))
+                        {
+                            *progress = ShardProgress::from_health(&health);
+                        }
+                    }
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => {
+                        self.health_rx = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let result = self.rx.try_recv().ok()?;
+        if let Ok(response) = &result {
+            self.apply_response(response);
+        }
+        Some(result)
+    }
+
+    /// Key of the durations of this load, see [`crate::Config::load_key`].
+    pub fn duration_key(&self, model: &str) -> String {
+        crate::Config::load_key(model, self.devices.iter().map(|d| d.instance.as_str()))
+    }
+
+    /// Set the final progress of the shards from the response of the load.
+    pub fn apply_response(&mut self, response: &LoadModelResponse) {
+        for status in &response.shard_statuses {
+            let progress = if status.success {
+                ShardProgress::Done
+            } else {
+                ShardProgress::Failed(status.message.clone().unwrap_or_default())
+            };
+            match self.shards.iter_mut().find(|(i, _)| *i == status.instance) {
+                Some((_, p)) => *p = progress,
+                None => self.shards.push((status.instance.clone(), progress)),
+            }
+        }
+    }
+}
+
+impl crate::App {
+    /// Draw the progress of each shard of the model being loaded.
+    pub(super) fn draw_load_progress(&self, frame: &mut Frame, area: Rect, model: &str) {
+        let Some(load) = &self.state.model.load else {
+            frame.render_widget(
+                Paragraph::new(format!("Loading model {}...", model))
+                    .block(Block::bordered())
+                    .centered(),
+                area,
+            );
+            return;
+        };
+
+        let num_done = load
+            .shards
+            .iter()
+            .filter(|(_, p)| *p == ShardProgress::Done)
+            .count();
+        let estimate = self
+            .config
+            .estimated_load_duration(&load.duration_key(model));
+        let mut lines = vec![
+            Line::from(format!("Loading model {}...", model)).bold(),
+            Line::from(format!(
+                "{} / {} shards done, {}",
+                num_done,
+                load.shards.len(),
+                format_elapsed(load.started_at.elapsed(), estimate)
+            ))
+            .dark_gray(),
+            Line::from(""),
+        ];
+        if load.shards.is_empty() {
+            lines.push(Line::from("No shards in the topology to track.").dark_gray());
+        }
+        for (instance, progress) in &load.shards {
+            let (symbol, color, label) = progress.display();
+            lines.push(Line::from(vec![
+                format!("  {} ", symbol).fg(color),
+                format!("{:<24}", self.config.device_name(instance)).into(),
+                label.fg(color),
+            ]));
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Progress")),
+            area,
+        );
+    }
+}
+
+/// Elapsed time of a load, along with the time left of the estimate (if any).
+fn format_elapsed(elapsed: Duration, estimate: Option<Duration>) -> String {
+    let elapsed_secs = elapsed.as_secs();
+    match estimate.map(|e| e.as_secs()) {
+        None => format!("{}s elapsed", elapsed_secs),
+        Some(estimate) if elapsed_secs <= estimate => format!(
+            "{}s elapsed, ~{}s left (usually ~{}s)",
+            elapsed_secs,
+            estimate - elapsed_secs,
+            estimate
+        ),
+        Some(estimate) => format!(
+            "{}s elapsed, taking longer than usual (~{}s)",
+            elapsed_secs, estimate
+        ),
+    }
+}
+
+/// Label of a download, e.g. `downloading 1.2 GiB / 4.0 GiB (30%)`.
+fn format_download(download: &DownloadProgress) -> String {
+    let downloaded = format_bytes(download.downloaded_bytes);
+    match (download.total_bytes, download.ratio()) {
+        (Some(total), Some(ratio)) => format!(
+            "downloading {} / {} ({:.0}%)",
+            downloaded,
+            format_bytes(total),
+            ratio * 100.0
+        ),
+        _ => format!("downloading {}", downloaded),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{ShardLoadStatus, ShardResources};
+
+    #[test]
+    fn test_shard_progress() {
+        let mut health = ShardHealth {
+            status: "ok".to_string(),
+            running: true,
+            model_loaded: false,
+            model_path: None,
+            assigned_layers: vec![0, 1],
+            queue_size: 0,
+            grpc_port: 58080,
+            http_port: 8080,
+            instance: "shard-a".to_string(),
+            resources: ShardResources::default(),
+            download: None,
+        };
+        assert_eq!(ShardProgress::from_health(&health), ShardProgress::Loading);
+        health.status = "Downloading weights".to_string();
+        assert_eq!(
+            ShardProgress::from_health(&health),
+            ShardProgress::Downloading(None)
+        );
+
+        let download = DownloadProgress {
+            downloaded_bytes: 300,
+            total_bytes: Some(1000),
+        };
+        health.download = Some(download);
+        assert_eq!(
+            ShardProgress::from_health(&health),
+            ShardProgress::Downloading(Some(download))
+        );
+        assert!(format_download(&download).ends_with("(30%)"));
+
+        // a finished download is loading, unless the status says otherwise
+        health.status = "ok".to_string();
+        health.download = Some(DownloadProgress {
+            downloaded_bytes: 1000,
+            ..download
+        });
+        assert_eq!(ShardProgress::from_health(&health), ShardProgress::Loading);
+        health.model_loaded = true;
+        assert_eq!(ShardProgress::from_health(&health), ShardProgress::Done);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        let secs = Duration::from_secs;
+        assert_eq!(format_elapsed(secs(12), None), "12s elapsed");
+        assert_eq!(
+            format_elapsed(secs(12), Some(secs(40))),
+            "12s elapsed, ~28s left (usually ~40s)"
+        );
+        assert_eq!(
+            format_elapsed(secs(52), Some(secs(40))),
+            "52s elapsed, taking longer than usual (~40s)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_response() {
+        let mut load = ModelLoad::start(ApiClient::new("127.0.0.1", 1), "model", None);
+        load.shards
+            .push(("shard-a".to_string(), ShardProgress::Loading));
+
+        let status = |instance: &str, success: bool| ShardLoadStatus {
+            instance: instance.to_string(),
+            success,
+            layers_loaded: None,
+            message: (!success).then(|| "out of memory".to_string()),
+        };
+        load.apply_response(&LoadModelResponse {
+            model: "model".to_string(),
+            success: false,
+            shard_statuses: vec![status("shard-a", true), status("shard-b", false)],
+            message: None,
+        });
+        assert_eq!(
+            load.shards,
+            vec![
+                ("shard-a".to_string(), ShardProgress::Done),
+                (
+                    "shard-b".to_string(),
+                    ShardProgress::Failed("out of memory".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use super::LoadModelView;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+
+/// Lines of the steps of a model switch, marking those before the current one as done.
+///
+/// Returns `None` if the view is not a step of the switch.
+pub fn switch_steps(from: &str, view: &LoadModelView) -> Option<Vec<Line<'static>>> {
+    let (current, to) = match view {
+        LoadModelView::UnloadingModel(to) => (0, to),
+        LoadModelView::PreparingTopology(to) => (1, to),
+        LoadModelView::LoadingModel(to) => (2, to),
+        _ => return None,
+    };
+
+    let steps = [
+        format!("Unload {}", from),
+        format!("Prepare the topology for {}", to),
+        format!("Load {}", to),
+    ];
+    let lines = steps
+        .into_iter()
+        .enumerate()
+        .map(|(i, step)| match i.cmp(&current) {
+            std::cmp::Ordering::Less => Line::from(format!("  ✓ {}", step)).green(),
+            std::cmp::Ordering::Equal => Line::from(format!("  … {}", step)).bold().yellow(),
+            std::cmp::Ordering::Greater => Line::from(format!("    {}", step)).dark_gray(),
+        })
+        .collect();
+    Some(lines)
+}
+
+impl crate::App {
+    /// Draw the steps of the model switch, if one is in progress.
+    pub(super) fn draw_switch_steps(&self, frame: &mut Frame, area: Rect, view: &LoadModelView) {
+        let Some(from) = &self.state.model.switch_from else {
+            return;
+        };
+        if let Some(lines) = switch_steps(from, view) {
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title("Switching Model")),
+                area,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_steps() {
+        let view = LoadModelView::PreparingTopology("b".to_string());
+        let lines = switch_steps("a", &view)
+            .unwrap()
+            .iter()
+            .map(Line::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                "  ✓ Unload a",
+                "  … Prepare the topology for b",
+                "    Load b",
+            ]
+        );
+        assert!(switch_steps("a", &LoadModelView::SelectingModel).is_none());
+    }
+}
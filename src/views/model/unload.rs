@@ -1,15 +1,22 @@
+use crate::utils::centered_rect;
+use crate::views::developer::utils::format_layers;
+use crate::widgets::ConfirmDialog;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout},
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::Line,
-    widgets::{Block, Paragraph},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum UnloadModelView {
-    Unloading,
+    /// Picking which model to unload, when several are loaded.
+    SelectingModel,
+    /// Asking to confirm the unload, showing the model and its shards.
+    Confirming(String /* model name */),
+    Unloading(String /* model name */),
     Error(String),
     Success,
 }
@@ -31,9 +38,13 @@ impl crate::App {
 
         // Content
         match state {
-            UnloadModelView::Unloading => {
+            UnloadModelView::SelectingModel => self.draw_unload_selection(frame, content_area),
+            UnloadModelView::Confirming(model) => {
+                self.draw_unload_confirmation(frame, content_area, model)
+            }
+            UnloadModelView::Unloading(model) => {
                 frame.render_widget(
-                    Paragraph::new("Unloading model...")
+                    Paragraph::new(format!("Unloading {}...", model))
                         .block(Block::bordered())
                         .centered(),
                     content_area,
@@ -61,29 +72,125 @@ impl crate::App {
 
         // Footer
         let footer_text = match state {
+            UnloadModelView::SelectingModel => {
+                "↑/↓ to select  |  Enter to unload  |  Esc to go back"
+            }
+            UnloadModelView::Confirming(_) => "y/Enter to unload  |  n/Esc to go back",
             UnloadModelView::Error(_) | UnloadModelView::Success => "Press Esc to go back",
-            UnloadModelView::Unloading => "Please wait...",
+            UnloadModelView::Unloading(_) => "Please wait...",
         };
         frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
     }
 
-    pub(super) fn handle_unload_model_input(&mut self, key: KeyEvent, _state: &UnloadModelView) {
-        // only allow ESC to go back
-        if key.code == KeyCode::Esc {
-            self.view = crate::AppView::Menu;
+    /// Draw the list of the loaded models to pick the one to unload.
+    fn draw_unload_selection(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let models = self.loaded_models();
+        let items = models
+            .iter()
+            .map(|model| {
+                let num_shards = self
+                    .model_topology(model)
+                    .map_or(0, |t| t.assignments.len());
+                ListItem::new(Line::from(vec![
+                    format!("  {:<48}", model).bold(),
+                    format!(" {} shards", num_shards).dark_gray(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title("Loaded Models"))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let selected = self
+            .state
+            .model
+            .unload_selected
+            .min(models.len().saturating_sub(1));
+        let mut state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Draw the confirmation of the unload, with the model and the shards that hold it.
+    fn draw_unload_confirmation(
+        &self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        model: &str,
+    ) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Unload {}?", model)).bold(),
+            Line::from(""),
+            Line::from("Any ongoing generation will fail. Affected shards:"),
+        ];
+        let assignments = self
+            .model_topology(model)
+            .into_iter()
+            .flat_map(|t| &t.assignments);
+        for assignment in assignments {
+            lines.push(Line::from(format!(
+                "  • {}  {}",
+                self.config.device_name(&assignment.instance),
+                format_layers(&assignment.layers.concat())
+            )));
+        }
+
+        frame.render_widget(
+            ConfirmDialog::new("Unload Model", lines),
+            centered_rect(60, 60, area),
+        );
+    }
+
+    pub(super) fn handle_unload_model_input(&mut self, key: KeyEvent, state: &UnloadModelView) {
+        let models = self.loaded_models();
+        let selected = &mut self.state.model.unload_selected;
+        match (state, key.code) {
+            (UnloadModelView::SelectingModel, KeyCode::Up) => {
+                *selected = selected.saturating_sub(1)
+            }
+            (UnloadModelView::SelectingModel, KeyCode::Down) => {
+                *selected = (*selected + 1).min(models.len().saturating_sub(1))
+            }
+            (UnloadModelView::SelectingModel, KeyCode::Enter) => {
+                if let Some(model) = models.get(*selected).cloned() {
+                    self.view = crate::AppView::Model(super::ModelView::Unload(
+                        UnloadModelView::Confirming(model),
+                    ));
+                }
+            }
+            (UnloadModelView::Confirming(model), KeyCode::Char('y') | KeyCode::Enter) => {
+                self.view = crate::AppView::Model(super::ModelView::Unload(
+                    UnloadModelView::Unloading(model.clone()),
+                ));
+            }
+            (UnloadModelView::Confirming(_), KeyCode::Char('n')) | (_, KeyCode::Esc) => {
+                self.view = crate::AppView::Menu;
+            }
+            _ => {}
         }
     }
 
     /// Handle async operations for unload model state (called during tick).
     pub(super) async fn tick_unload_model(&mut self, view: &UnloadModelView) {
-        if matches!(view, UnloadModelView::Unloading) {
-            match self.api.unload_model().await {
+        if let UnloadModelView::Unloading(model) = view {
+            let started_at = std::time::Instant::now();
+            let result = self.api.unload_model(Some(model)).await;
+            let record = super::LoadRecord::unload(
+                model,
+                started_at.elapsed(),
+                result.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+            );
+            self.record_load(record);
+
+            match result {
                 Ok(_) => {
                     self.view =
                         crate::AppView::Model(super::ModelView::Unload(UnloadModelView::Success));
-                    if let Some(topology) = &mut self.topology {
-                        topology.model = None;
-                    };
+                    self.forget_loaded_model(model);
                 }
                 Err(err) => {
                     self.view = crate::AppView::Model(super::ModelView::Unload(
@@ -0,0 +1,37 @@
+use crate::common::{WarmUp, warm_up};
+use tokio::sync::mpsc;
+
+/// Warm-up request sent after a successful load, see [`crate::config::Config::warm_up`].
+#[derive(Debug, Default)]
+pub struct WarmUpState {
+    /// Receiver of the running warm-up, if any.
+    rx: Option<mpsc::UnboundedReceiver<Result<WarmUp, String>>>,
+    /// Result of the last warm-up.
+    pub result: Option<Result<WarmUp, String>>,
+}
+
+impl WarmUpState {
+    /// Send the warm-up request to the model in the background, dropping any previous result.
+    pub fn start(&mut self, api_url: String, model: String) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            tx.send(warm_up(api_url, model).await).ok();
+        });
+        self.rx = Some(rx);
+        self.result = None;
+    }
+
+    /// Whether the warm-up request is running.
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Collect the result of the warm-up, if it has finished.
+    pub fn poll(&mut self) {
+        let Some(result) = self.rx.as_mut().and_then(|rx| rx.try_recv().ok()) else {
+            return;
+        };
+        self.rx = None;
+        self.result = Some(result);
+    }
+}
@@ -7,7 +7,7 @@ use ratatui::{
     layout::{Constraint, Layout},
     style::{Color, Modifier, Style, Stylize},
     text::Line,
-    widgets::Paragraph,
+    widgets::{List, ListState, Paragraph},
 };
 
 #[derive(Default, Debug)]
@@ -20,6 +20,8 @@ pub struct SettingsState {
     pub is_editing: bool,
     /// Temporary config for editing stuff.
     pub temp_config: Config,
+    /// Scroll state of the fields, following the selection.
+    pub list_state: ListState,
 }
 
 /// Possible settings fields.
@@ -36,12 +38,31 @@ pub enum SettingsField {
     Temperature,
     /// Devices refresh interval in seconds.
     DevicesRefreshInterval,
+    /// Topology ring refresh interval in seconds, 0 to disable.
+    TopologyRefreshInterval,
+    /// Shard health refresh interval in seconds, 0 to disable.
+    ShardRefreshInterval,
     /// Quantization level.
     KVBits,
     /// Sequence length to optimize for.
     SeqLen,
     /// Max batch size as power of 2 exponent.
     MaxBatchExp,
+    /// Chat completion smoke test after loading a model.
+    WarmUp,
+    /// File format for exported chats.
+    ExportFormat,
+    /// Default system prompt for chat sessions.
+    SystemPrompt,
+    TimestampFormat,
+    AutosaveInterval,
+    /// Token budget of the chat history, 0 for automatic.
+    ContextBudget,
+    /// Notification for finished chat responses.
+    Notification,
+    UserColor,
+    AssistantColor,
+    ThinkingColor,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -71,15 +92,27 @@ impl SettingsStatus {
 }
 
 impl SettingsField {
-    pub const ALL: [SettingsField; 8] = [
+    pub const ALL: [SettingsField; 20] = [
         SettingsField::Host,
         SettingsField::Port,
         SettingsField::MaxTokens,
         SettingsField::Temperature,
         SettingsField::DevicesRefreshInterval,
+        SettingsField::TopologyRefreshInterval,
+        SettingsField::ShardRefreshInterval,
         SettingsField::KVBits,
         SettingsField::MaxBatchExp,
         SettingsField::SeqLen,
+        SettingsField::WarmUp,
+        SettingsField::ExportFormat,
+        SettingsField::SystemPrompt,
+        SettingsField::TimestampFormat,
+        SettingsField::AutosaveInterval,
+        SettingsField::ContextBudget,
+        SettingsField::Notification,
+        SettingsField::UserColor,
+        SettingsField::AssistantColor,
+        SettingsField::ThinkingColor,
     ];
 
     pub fn label(&self) -> &'static str {
@@ -89,9 +122,21 @@ impl SettingsField {
             SettingsField::MaxTokens => "Max Tokens",
             SettingsField::Temperature => "Temperature",
             SettingsField::DevicesRefreshInterval => "Device Refresh (s)",
+            SettingsField::TopologyRefreshInterval => "Topology Refresh (s)",
+            SettingsField::ShardRefreshInterval => "Shard Refresh (s)",
             SettingsField::KVBits => "KV Bits",
             SettingsField::MaxBatchExp => "Max Batch Exponent",
             SettingsField::SeqLen => "Sequence Length",
+            SettingsField::WarmUp => "Post-load Warm-up",
+            SettingsField::ExportFormat => "Chat Export Format",
+            SettingsField::SystemPrompt => "System Prompt",
+            SettingsField::TimestampFormat => "Timestamp Format",
+            SettingsField::AutosaveInterval => "Chat Autosave Interval",
+            SettingsField::ContextBudget => "Chat Context Budget",
+            SettingsField::Notification => "Chat Notification",
+            SettingsField::UserColor => "User Color",
+            SettingsField::AssistantColor => "Assistant Color",
+            SettingsField::ThinkingColor => "Thinking Color",
         }
     }
 
@@ -149,15 +194,16 @@ impl App {
         let vertical = Layout::vertical([
             Constraint::Length(3), // Title
             Constraint::Min(0),    // Settings fields
+            Constraint::Length(2), // Config location & status
             Constraint::Length(2), // Footer
         ]);
-        let [title_area, settings_area, footer_area] = vertical.areas(area);
+        let [title_area, settings_area, info_area, footer_area] = vertical.areas(area);
 
         // Title
         let title = Line::from("Settings").bold().cyan().centered();
         frame.render_widget(Paragraph::new(title), title_area);
 
-        // Body
+        // Body, scrolled to keep the selected field in view
         let settings_lines = SettingsField::ALL
             .iter()
             .map(|s| {
@@ -170,28 +216,25 @@ impl App {
             })
             .collect::<Vec<_>>();
 
-        // start with one empty line
-        let mut body_lines = vec![];
-        body_lines.extend_from_slice(&settings_lines);
-        body_lines.push(
-            vec![
-                "  Current config:  ".dim(),
-                Config::current_location().dim(),
-            ]
-            .into(),
+        let selected = SettingsField::ALL
+            .iter()
+            .position(|s| *s == self.state.settings.selection);
+        self.state.settings.list_state.select(selected);
+        frame.render_stateful_widget(
+            List::new(settings_lines),
+            settings_area,
+            &mut self.state.settings.list_state,
         );
 
+        let mut info_lines = vec![Line::from(vec![
+            "  Current config:  ".dim(),
+            Config::current_location().dim(),
+        ])];
         // if there is a status message, add that as well
         if !self.state.settings.status.is_empty() {
-            body_lines.push(Line::from(self.state.settings.status.to_span()));
-        }
-
-        // add an empty line in between every element (better readability)
-        for i in 1..body_lines.len() {
-            body_lines.insert(i * 2 - 1, Line::from(" "));
+            info_lines.push(Line::from(self.state.settings.status.to_span()));
         }
-
-        frame.render_widget(Paragraph::new(body_lines), settings_area);
+        frame.render_widget(Paragraph::new(info_lines), info_area);
 
         // Footer
         let footer_text = "Press Esc to go back  |  Enter to edit field  |  s to save";
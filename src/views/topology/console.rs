@@ -0,0 +1,358 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tui_input::backend::crossterm::EventHandler;
+
+/// Timeout of a console request, so that a wedged shard does not hang the console.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConsoleMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+impl fmt::Display for ConsoleMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsoleMethod::Get => write!(f, "GET"),
+            ConsoleMethod::Post => write!(f, "POST"),
+        }
+    }
+}
+
+/// Field of a request console that has the focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConsoleField {
+    Method,
+    #[default]
+    Path,
+    Body,
+    /// Requests sent so far, in the consoles that keep them.
+    History,
+}
+
+impl ConsoleField {
+    /// The field after (or before) this one among `fields`, wrapping around.
+    pub fn next(self, fields: &[ConsoleField], forward: bool) -> Self {
+        let i = fields.iter().position(|f| *f == self).unwrap_or_default();
+        let i = if forward { i + 1 } else { i + fields.len() - 1 };
+        fields[i % fields.len()]
+    }
+}
+
+/// Fields of the shard console, which keeps no history.
+const FIELDS: [ConsoleField; 3] = [ConsoleField::Method, ConsoleField::Path, ConsoleField::Body];
+
+/// Response to a console request, or why it failed.
+#[derive(Debug, Clone)]
+pub struct ConsoleResponse {
+    /// HTTP status, `None` if the request failed altogether.
+    pub status: Option<reqwest::StatusCode>,
+    /// Pretty-printed body, or the error.
+    pub body: String,
+    pub elapsed: Duration,
+}
+
+/// Request console of the shard view, to send arbitrary requests to the shard.
+#[derive(Debug)]
+pub struct ShardConsole {
+    pub method: ConsoleMethod,
+    /// Request path, e.g. `/health`.
+    pub path: tui_input::Input,
+    /// JSON body of POST requests, may be empty.
+    pub body: tui_input::Input,
+    pub focus: ConsoleField,
+    /// Response to the last request.
+    pub response: Option<ConsoleResponse>,
+    /// Scroll offset of the response pane.
+    pub scroll: u16,
+    /// Receiver of the response of the request in flight, if any.
+    rx: Option<mpsc::UnboundedReceiver<ConsoleResponse>>,
+}
+
+impl Default for ShardConsole {
+    fn default() -> Self {
+        Self {
+            method: ConsoleMethod::default(),
+            path: tui_input::Input::new("/health".to_string()),
+            body: tui_input::Input::default(),
+            focus: ConsoleField::default(),
+            response: None,
+            scroll: 0,
+            rx: None,
+        }
+    }
+}
+
+impl ShardConsole {
+    /// Whether a request is in flight.
+    pub fn is_pending(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Send the request in the form to the shard at `base_url` in the background.
+    ///
+    /// The body is validated as JSON beforehand, an invalid one is reported as the response.
+    pub fn send(&mut self, base_url: &str) {
+        let body = match self.method {
+            ConsoleMethod::Get => String::new(),
+            ConsoleMethod::Post => self.body.value().trim().to_string(),
+        };
+        let invalid = match body.as_str() {
+            "" => None,
+            body => serde_json::from_str::<serde_json::Value>(body).err(),
+        };
+        if let Some(err) = invalid {
+            self.response = Some(ConsoleResponse {
+                status: None,
+                body: format!("Invalid JSON body: {}", err),
+                elapsed: Duration::ZERO,
+            });
+            return;
+        }
+
+        let path = self.path.value().trim();
+        let url = format!("{}/{}", base_url, path.trim_start_matches('/'));
+        let method = self.method;
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            tx.send(request(method, &url, body).await).ok();
+        });
+        self.rx = Some(rx);
+    }
+
+    /// Collect the response of the request in flight, if it has arrived.
+    pub fn poll(&mut self) {
+        let Some(rx) = &mut self.rx else {
+            return;
+        };
+        if let Ok(response) = rx.try_recv() {
+            self.response = Some(response);
+            self.scroll = 0;
+            self.rx = None;
+        }
+    }
+}
+
+/// Send a request with the given JSON body, which is omitted if empty.
+async fn request(method: ConsoleMethod, url: &str, body: String) -> ConsoleResponse {
+    let start = Instant::now();
+    let failed = |err: String| ConsoleResponse {
+        status: None,
+        body: err,
+        elapsed: start.elapsed(),
+    };
+
+    let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => return failed(err.to_string()),
+    };
+    let mut request = match method {
+        ConsoleMethod::Get => client.get(url),
+        ConsoleMethod::Post => client.post(url),
+    };
+    if !body.is_empty() {
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(err) => return failed(format!("Request failed: {}", err)),
+    };
+    let status = response.status();
+    match response.text().await {
+        Ok(text) => ConsoleResponse {
+            status: Some(status),
+            body: pretty_body(&text),
+            elapsed: start.elapsed(),
+        },
+        Err(err) => failed(format!("Failed to read response: {}", err)),
+    }
+}
+
+/// Pretty-print the body if it is JSON, otherwise return it as is.
+pub fn pretty_body(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| body.to_string())
+}
+
+impl crate::App {
+    /// Draw the request console in place of the shard health.
+    pub(super) fn draw_shard_console(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(console) = &mut self.state.topology.console else {
+            return;
+        };
+
+        let [form_area, body_area, response_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [method_area, path_area] =
+            Layout::horizontal([Constraint::Length(10), Constraint::Min(0)]).areas(form_area);
+
+        let field_block = |field: ConsoleField, title: &'static str| {
+            let block = Block::bordered().title(title);
+            if console.focus == field {
+                block.border_style(Style::default().fg(Color::Cyan))
+            } else {
+                block
+            }
+        };
+
+        frame.render_widget(
+            Paragraph::new(console.method.to_string().bold())
+                .centered()
+                .block(field_block(ConsoleField::Method, "Method")),
+            method_area,
+        );
+
+        let body_title = if console.method == ConsoleMethod::Post {
+            "JSON Body"
+        } else {
+            "JSON Body (POST only)"
+        };
+        for (field, input, title, input_area) in [
+            (ConsoleField::Path, &console.path, "Path", path_area),
+            (ConsoleField::Body, &console.body, body_title, body_area),
+        ] {
+            // keep 2 for borders and 1 for cursor
+            let width = input_area.width.max(3) - 3;
+            let scroll = input.visual_scroll(width as usize);
+            frame.render_widget(
+                Paragraph::new(input.value())
+                    .scroll((0, scroll as u16))
+                    .block(field_block(field, title)),
+                input_area,
+            );
+            if console.focus == field {
+                let x = input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+            }
+        }
+
+        // Response
+        let (title, lines) = match &console.response {
+            _ if console.is_pending() => (
+                Line::from("Response"),
+                vec![Line::from("Sending request...").dark_gray()],
+            ),
+            None => (
+                Line::from("Response"),
+                vec![Line::from("Press Enter to send the request.").dark_gray()],
+            ),
+            Some(response) => {
+                let status = match response.status {
+                    Some(status) if status.is_success() => status.to_string().green(),
+                    Some(status) => status.to_string().red(),
+                    None => "Error".red(),
+                };
+                let title = Line::from(vec![
+                    "Response: ".into(),
+                    status.bold(),
+                    format!(" ({} ms)", response.elapsed.as_millis()).into(),
+                ]);
+                let lines = response.body.lines().map(Line::from).collect();
+                (title, lines)
+            }
+        };
+
+        let num_lines = lines.len();
+        let max_scroll = num_lines.saturating_sub(response_area.height.saturating_sub(2) as usize);
+        console.scroll = console.scroll.min(max_scroll as u16);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .scroll((console.scroll, 0))
+                .block(Block::bordered().title(title)),
+            response_area,
+        );
+
+        let mut scroll_bar = ScrollbarState::new(max_scroll).position(console.scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            response_area,
+            &mut scroll_bar,
+        );
+    }
+
+    /// Handle input while the request console is open, `device` is the shard of the view.
+    pub(super) fn handle_shard_console_input(&mut self, key: KeyEvent, device: &str) {
+        let base_url = self
+            .topology
+            .as_ref()
+            .and_then(|t| t.devices.iter().find(|d| d.instance == device))
+            .map(|d| format!("http://{}:{}", d.local_ip, d.server_port));
+        let Some(console) = &mut self.state.topology.console else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.state.topology.console = None,
+            KeyCode::Tab => console.focus = console.focus.next(&FIELDS, true),
+            KeyCode::BackTab => console.focus = console.focus.next(&FIELDS, false),
+            KeyCode::Enter if !console.is_pending() => match base_url {
+                Some(base_url) => console.send(&base_url),
+                None => {
+                    self.status_message =
+                        format!("Error: device '{}' not found in topology", device)
+                }
+            },
+            KeyCode::Up => console.scroll = console.scroll.saturating_sub(1),
+            KeyCode::Down => console.scroll = console.scroll.saturating_add(1),
+            KeyCode::PageUp => console.scroll = console.scroll.saturating_sub(10),
+            KeyCode::PageDown => console.scroll = console.scroll.saturating_add(10),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
+                if console.focus == ConsoleField::Method =>
+            {
+                console.method = match console.method {
+                    ConsoleMethod::Get => ConsoleMethod::Post,
+                    ConsoleMethod::Post => ConsoleMethod::Get,
+                };
+            }
+            _ => {
+                let event = crossterm::event::Event::Key(key);
+                match console.focus {
+                    ConsoleField::Path => {
+                        console.path.handle_event(&event);
+                    }
+                    ConsoleField::Body => {
+                        console.body.handle_event(&event);
+                    }
+                    ConsoleField::Method | ConsoleField::History => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_body() {
+        assert_eq!(
+            pretty_body(r#"{"layers":[0,1]}"#),
+            "{\n  \"layers\": [\n    0,\n    1\n  ]\n}"
+        );
+        assert_eq!(pretty_body("Not Found"), "Not Found");
+        assert_eq!(pretty_body(""), "");
+    }
+}
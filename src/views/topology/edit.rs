@@ -0,0 +1,405 @@
+use super::TopologyRingView;
+use crate::AppView;
+use crate::common::{AssignmentInfo, TopologyInfo};
+use crate::utils::centered_rect;
+use crate::views::developer::utils::{
+    determine_next_instances, find_missing_layers, format_layers, format_rounds,
+    parse_rounds_input, validate_ring,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::collections::{HashMap, HashSet};
+use tui_input::backend::crossterm::EventHandler;
+
+/// Field of the assignment editor that has the focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EditorField {
+    #[default]
+    Layers,
+    Window,
+}
+
+/// Inline editor of the layer assignment of a device in the ring.
+#[derive(Debug)]
+pub struct AssignmentEditor {
+    /// Instance of the edited device.
+    pub instance: String,
+    /// Layers of each round, e.g. `0-5,10` or `0-3; 8-11`.
+    pub layers: tui_input::Input,
+    /// Window size, in layers.
+    pub window: tui_input::Input,
+    pub focus: EditorField,
+}
+
+impl AssignmentEditor {
+    /// Editor of the given device, initialized to its current assignment.
+    pub fn new(topology: &TopologyInfo, instance: &str) -> Self {
+        let assignment = topology.assignment_of(instance);
+        let layers = assignment
+            .map(|a| format_rounds(&a.layers))
+            .filter(|layers| layers != "[]")
+            .unwrap_or_default();
+        let window = assignment
+            .map(|a| a.window_size.to_string())
+            .unwrap_or_default();
+
+        Self {
+            instance: instance.to_string(),
+            layers: tui_input::Input::new(layers),
+            window: tui_input::Input::new(window),
+            focus: EditorField::default(),
+        }
+    }
+}
+
+/// Assignments of the topology with the rounds of `instance` replaced by `rounds`
+/// and its window by `window`, to be resubmitted as a manual topology.
+///
+/// Layers of other devices that are taken are removed from their rounds, and every
+/// layer of the model must remain assigned. The residency sizes are kept as long as
+/// they fit, and the ring order (i.e. the next instances) is recomputed from the layers.
+pub fn edit_assignments(
+    topology: &TopologyInfo,
+    instance: &str,
+    rounds: Vec<Vec<u32>>,
+    window: u32,
+) -> Result<Vec<AssignmentInfo>, String> {
+    let largest_round = rounds.iter().map(Vec::len).max().unwrap_or_default() as u32;
+    if window == 0 || window > largest_round {
+        return Err(format!(
+            "Window must be between 1 and {}, the layers of the largest round",
+            largest_round
+        ));
+    }
+    let layers = rounds.concat();
+
+    let mut assignments = topology.assignments.clone();
+    for other in assignments.iter_mut().filter(|a| !a.is_for(instance)) {
+        for round in &mut other.layers {
+            round.retain(|layer| !layers.contains(layer));
+        }
+        other.layers.retain(|round| !round.is_empty());
+        if other.layers.is_empty() {
+            return Err(format!("No layers would be left to {}", other.instance));
+        }
+        let largest_round = other.layers.iter().map(Vec::len).max().unwrap_or_default() as u32;
+        let num_layers = other.layers.iter().map(Vec::len).sum::<usize>() as u32;
+        other.window_size = other.window_size.min(largest_round);
+        other.residency_size = other.residency_size.min(num_layers);
+    }
+
+    match assignments.iter_mut().find(|a| a.is_for(instance)) {
+        Some(assignment) => {
+            if assignment.residency_size > layers.len() as u32 {
+                assignment.residency_size = window;
+            }
+            assignment.layers = rounds;
+            assignment.window_size = window;
+        }
+        None => assignments.push(AssignmentInfo {
+            instance: instance.to_string(),
+            layers: rounds,
+            next_instance: instance.to_string(),
+            window_size: window,
+            residency_size: window,
+        }),
+    }
+
+    let layer_map: HashMap<String, Vec<Vec<u32>>> = assignments
+        .iter()
+        .map(|a| (a.instance.clone(), a.layers.clone()))
+        .collect();
+    let assigned = layer_map
+        .values()
+        .flatten()
+        .flatten()
+        .copied()
+        .collect::<HashSet<_>>();
+    let missing = find_missing_layers(&assigned, topology.num_layers);
+    if !missing.is_empty() {
+        return Err(format!(
+            "Layers {} would be unassigned",
+            format_layers(&missing)
+        ));
+    }
+
+    // keep the ring as it is (e.g. a custom order) unless it is broken, such as when
+    // the device is new to the topology
+    let next_instances = assignments
+        .iter()
+        .map(|a| (a.instance.clone(), a.next_instance.clone()))
+        .collect::<HashMap<_, _>>();
+    if validate_ring(&next_instances).is_ok() {
+        return Ok(assignments);
+    }
+    let next_instances = determine_next_instances(&layer_map);
+    for assignment in &mut assignments {
+        if let Some(next) = next_instances.get(&assignment.instance) {
+            assignment.next_instance = next.clone();
+        }
+    }
+
+    Ok(assignments)
+}
+
+impl crate::App {
+    /// Open the assignment editor for the selected device.
+    pub(super) fn open_assignment_editor(&mut self) {
+        let Some(topology) = &self.topology else {
+            return;
+        };
+        if let Some(device) = topology.devices.get(self.state.topology.selected_device) {
+            self.state.topology.editor = Some(AssignmentEditor::new(topology, &device.instance));
+        }
+    }
+
+    /// Draw the assignment editor (if open) on top of the ring.
+    pub(super) fn draw_assignment_editor(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(editor) = &self.state.topology.editor else {
+            return;
+        };
+        let num_layers = self.topology.as_ref().map_or(0, |t| t.num_layers);
+
+        let popup_area = centered_rect(60, 40, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Edit Assignment ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [help_area, layers_area, window_area, hint_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let help = vec![
+            Line::from(vec![
+                "Assignment of ".into(),
+                self.config
+                    .device_name(&editor.instance)
+                    .to_string()
+                    .bold()
+                    .cyan(),
+            ]),
+            Line::from(
+                format!(
+                    "The model has {} layers, e.g. 0-5, or 0-3; 8-11 for several rounds",
+                    num_layers
+                )
+                .dark_gray(),
+            ),
+            Line::from("Layers taken from other devices are removed from them.".dark_gray()),
+            Line::from("The model must be reloaded for the changes to take effect.".dark_gray()),
+        ];
+        frame.render_widget(Paragraph::new(help), help_area);
+
+        for (field, input, title, input_area) in [
+            (EditorField::Layers, &editor.layers, "Layers", layers_area),
+            (
+                EditorField::Window,
+                &editor.window,
+                "Window Size",
+                window_area,
+            ),
+        ] {
+            let block = if editor.focus == field {
+                Block::bordered()
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Cyan))
+            } else {
+                Block::bordered().title(title)
+            };
+
+            // keep 2 for borders and 1 for cursor
+            let width = input_area.width.max(3) - 3;
+            let scroll = input.visual_scroll(width as usize);
+            frame.render_widget(
+                Paragraph::new(input.value())
+                    .scroll((0, scroll as u16))
+                    .block(block),
+                input_area,
+            );
+            if editor.focus == field {
+                let x = input.visual_cursor().max(scroll) - scroll + 1;
+                frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new("Enter: Submit | Tab: Next field | Esc: Cancel")
+                .centered()
+                .gray(),
+            hint_area,
+        );
+    }
+
+    /// Handle input while the assignment editor is open.
+    pub(super) fn handle_assignment_editor_input(&mut self, key: KeyEvent) {
+        let Some(editor) = &mut self.state.topology.editor else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.topology.editor = None;
+                self.status_message.clear();
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                editor.focus = match editor.focus {
+                    EditorField::Layers => EditorField::Window,
+                    EditorField::Window => EditorField::Layers,
+                };
+            }
+            KeyCode::Enter => {
+                let Some(topology) = &self.topology else {
+                    return;
+                };
+                let window = editor.window.value().trim().parse::<u32>();
+                let assignments = match (
+                    parse_rounds_input(editor.layers.value(), topology.num_layers),
+                    window,
+                ) {
+                    (None, _) => Err(format!(
+                        "Invalid layers, expected layers below {}",
+                        topology.num_layers
+                    )),
+                    (_, Err(_)) => Err("Invalid window size".to_string()),
+                    (Some(rounds), Ok(window)) => {
+                        edit_assignments(topology, &editor.instance, rounds, window)
+                    }
+                };
+
+                match assignments {
+                    Ok(assignments) => {
+                        self.state.topology.editor = None;
+                        self.status_message.clear();
+                        self.view = AppView::Topology(super::TopologyView::Ring(
+                            TopologyRingView::Submitting(assignments),
+                        ));
+                    }
+                    // keep the editor open so that the input can be fixed
+                    Err(err) => self.status_message = format!("Error: {}", err),
+                }
+            }
+            _ => {
+                let event = crossterm::event::Event::Key(key);
+                match editor.focus {
+                    EditorField::Layers => editor.layers.handle_event(&event),
+                    EditorField::Window => editor.window.handle_event(&event),
+                };
+            }
+        }
+    }
+
+    /// Resubmit the topology with the edited assignments, then reload it.
+    pub(super) async fn submit_edited_topology(&mut self, assignments: &[AssignmentInfo]) {
+        let result = match &self.topology {
+            Some(TopologyInfo {
+                model: Some(model),
+                num_layers,
+                devices,
+                ..
+            }) => self
+                .api
+                .prepare_topology_manual(
+                    &self.config,
+                    model,
+                    *num_layers,
+                    devices.clone(),
+                    assignments.to_vec(),
+                )
+                .await
+                .map_err(|e| e.to_string()),
+            _ => Err("No model loaded in the topology".to_string()),
+        };
+
+        self.status_message = match result {
+            Ok(_) => "Topology updated, reload the model to apply it".to_string(),
+            Err(err) => format!("Error: {}", err),
+        };
+        self.view = AppView::Topology(super::TopologyView::Ring(TopologyRingView::Loading));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KVBits;
+
+    fn assignment(instance: &str, layers: Vec<u32>, next_instance: &str) -> AssignmentInfo {
+        AssignmentInfo {
+            instance: instance.to_string(),
+            window_size: layers.len() as u32,
+            residency_size: layers.len() as u32,
+            layers: vec![layers],
+            next_instance: next_instance.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_edit_assignments() {
+        let topology = TopologyInfo {
+            model: Some("model".to_string()),
+            num_layers: 8,
+            devices: vec![],
+            assignments: vec![
+                AssignmentInfo {
+                    instance: "a".to_string(),
+                    window_size: 2,
+                    residency_size: 1,
+                    layers: vec![vec![0, 1], vec![4, 5]],
+                    next_instance: "b".to_string(),
+                },
+                assignment("b", vec![2, 3, 6, 7], "a"),
+            ],
+            solution: None,
+            kv_bits: KVBits::default(),
+        };
+
+        // changing the window keeps the rounds and the residency
+        let edited = edit_assignments(&topology, "a", vec![vec![0, 1], vec![4, 5]], 1).unwrap();
+        assert_eq!(edited[0].layers, vec![vec![0, 1], vec![4, 5]]);
+        assert_eq!(edited[0].window_size, 1);
+        assert_eq!(edited[0].residency_size, 1);
+        assert_eq!(edited[0].next_instance, "b");
+        assert_eq!(edited[1].next_instance, "a");
+
+        // the window must fit within the largest round
+        assert!(edit_assignments(&topology, "a", vec![vec![0, 1], vec![4, 5]], 3).is_err());
+        assert!(edit_assignments(&topology, "a", vec![vec![0, 1], vec![4, 5]], 0).is_err());
+
+        // shrinking a would leave its layers unassigned
+        let edited = edit_assignments(&topology, "a", vec![vec![0, 1]], 2);
+        assert_eq!(edited, Err("Layers 4-5 would be unassigned".to_string()));
+
+        // growing b takes the layers from a, whose window shrinks to its round
+        let edited = edit_assignments(&topology, "b", vec![vec![1, 2, 3], vec![6, 7]], 3).unwrap();
+        assert_eq!(edited[0].layers, vec![vec![0], vec![4, 5]]);
+        assert_eq!(edited[0].window_size, 2);
+        assert_eq!(edited[1].layers, vec![vec![1, 2, 3], vec![6, 7]]);
+        assert_eq!(edited[1].residency_size, 4);
+
+        // a new device joins the ring after the layers before its first round
+        let edited = edit_assignments(&topology, "c", vec![vec![7]], 1).unwrap();
+        assert_eq!(edited[1].layers, vec![vec![2, 3, 6]]);
+        assert_eq!(edited[2].instance, "c");
+        assert_eq!(edited[1].next_instance, "c");
+        assert_eq!(edited[2].next_instance, "a");
+
+        // taking all layers of a leaves it without any
+        let edited = edit_assignments(&topology, "b", vec![(0..8).collect()], 2);
+        assert_eq!(edited, Err("No layers would be left to a".to_string()));
+    }
+}
@@ -0,0 +1,263 @@
+use super::QueueHistory;
+use crate::common::{DeviceProperties, ShardResources};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Timeout of a single latency probe, slower shards are reported as unreachable.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Round-trip times at or above this are shown as a slow hop.
+const SLOW_RTT: Duration = Duration::from_millis(100);
+
+/// Round-trip times at or above this are shown as a lagging hop.
+const LAGGING_RTT: Duration = Duration::from_millis(20);
+
+/// Round-trip time to a shard's health endpoint, or why it could not be measured.
+pub type ShardLatency = Result<Duration, String>;
+
+/// Latency probes of the shards in the topology, keyed by instance.
+#[derive(Debug, Default)]
+pub struct LatencyProbe {
+    /// Latest result of each shard.
+    pub latencies: HashMap<String, ShardLatency>,
+    /// Resource usage of each shard, as reported in its health (if it does).
+    pub resources: HashMap<String, ShardResources>,
+    /// Queue sizes of each shard over time, also sampled by the shard view.
+    pub queue_sizes: QueueHistory,
+    /// Receiver of the results of the running probes.
+    rx: Option<mpsc::UnboundedReceiver<(String, ProbeResult)>>,
+    /// Number of probes whose results have not arrived yet.
+    num_pending: usize,
+}
+
+/// Aggregate of the latencies of the shards, see [`LatencyProbe::stats`].
+#[derive(Debug, Default, PartialEq)]
+pub struct LatencyStats {
+    /// Shards that responded faster than a lagging hop.
+    pub num_ok: usize,
+    /// Shards that responded, but slowly.
+    pub num_slow: usize,
+    /// Shards that did not respond (in time).
+    pub num_unreachable: usize,
+    pub min: Option<Duration>,
+    pub avg: Option<Duration>,
+    pub max: Option<Duration>,
+}
+
+/// Result of probing a shard: its latency, and its health if it could be read.
+type ProbeResult = (ShardLatency, Option<ProbedHealth>);
+
+/// Parts of the health response that are kept from a probe.
+#[derive(Debug, Deserialize)]
+struct ProbedHealth {
+    #[serde(flatten)]
+    resources: ShardResources,
+    #[serde(default)]
+    queue_size: Option<u32>,
+}
+
+impl LatencyProbe {
+    /// Ping the health endpoint of each shard in the background, keeping
+    /// the previous results on screen until the new ones arrive.
+    pub fn start<'a>(&mut self, devices: impl IntoIterator<Item = &'a DeviceProperties>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.num_pending = 0;
+        for device in devices {
+            let tx = tx.clone();
+            let instance = device.instance.clone();
+            let url = format!("http://{}:{}/health", device.local_ip, device.server_port);
+            tokio::spawn(async move {
+                tx.send((instance, probe(&url).await)).ok();
+            });
+            self.num_pending += 1;
+        }
+        self.rx = Some(rx);
+    }
+
+    /// Whether some probes are still running.
+    pub fn is_pending(&self) -> bool {
+        self.num_pending > 0
+    }
+
+    /// Collect the results that have arrived so far.
+    pub fn poll(&mut self) {
+        let Some(rx) = &mut self.rx else {
+            return;
+        };
+        while let Ok((instance, (latency, health))) = rx.try_recv() {
+            if let Some(queue_size) = health.as_ref().and_then(|h| h.queue_size) {
+                self.queue_sizes.record(&instance, queue_size);
+            }
+            match health.map(|h| h.resources).filter(|r| !r.is_empty()) {
+                Some(resources) => self.resources.insert(instance.clone(), resources),
+                None => self.resources.remove(&instance),
+            };
+            self.latencies.insert(instance, latency);
+            self.num_pending = self.num_pending.saturating_sub(1);
+        }
+    }
+
+    /// Aggregate of the latest latencies of the given shards, those not probed yet are skipped.
+    pub fn stats<'a>(&self, instances: impl IntoIterator<Item = &'a str>) -> LatencyStats {
+        let mut stats = LatencyStats::default();
+        let mut rtts = Vec::new();
+        for latency in instances.into_iter().filter_map(|i| self.get(i)) {
+            match latency {
+                Ok(rtt) if *rtt >= LAGGING_RTT => stats.num_slow += 1,
+                Ok(_) => stats.num_ok += 1,
+                Err(_) => stats.num_unreachable += 1,
+            }
+            if let Ok(rtt) = latency {
+                rtts.push(*rtt);
+            }
+        }
+
+        stats.min = rtts.iter().min().copied();
+        stats.max = rtts.iter().max().copied();
+        stats.avg = (!rtts.is_empty()).then(|| rtts.iter().sum::<Duration>() / rtts.len() as u32);
+        stats
+    }
+
+    /// Latency of the given shard, if it was probed.
+    pub fn get(&self, instance: &str) -> Option<&ShardLatency> {
+        self.latencies.get(instance)
+    }
+
+    /// Resource usage of the given shard, if it reported any when probed.
+    pub fn get_resources(&self, instance: &str) -> Option<&ShardResources> {
+        self.resources.get(instance)
+    }
+}
+
+/// Measure the round-trip time of a GET request to the given health URL,
+/// reading the resource usage and the queue size from the response as well.
+async fn probe(url: &str) -> ProbeResult {
+    let client = match reqwest::Client::builder().timeout(PROBE_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => return (Err(err.to_string()), None),
+    };
+
+    let start = Instant::now();
+    let response = match client.get(url).send().await {
+        Ok(response) => response,
+        Err(err) if err.is_timeout() => return (Err("timeout".to_string()), None),
+        Err(_) => return (Err("unreachable".to_string()), None),
+    };
+    if !response.status().is_success() {
+        return (Err(format!("HTTP {}", response.status().as_u16())), None);
+    }
+
+    // the body is read after the round-trip is measured
+    let rtt = start.elapsed();
+    (Ok(rtt), response.json().await.ok())
+}
+
+/// Short form of the latency, e.g. `12 ms`.
+pub fn format_latency(latency: &ShardLatency) -> String {
+    match latency {
+        Ok(rtt) => format!("{} ms", rtt.as_millis()),
+        Err(err) => err.clone(),
+    }
+}
+
+/// Color of the latency, from green for fast hops to red for slow (or failed) ones.
+pub fn latency_color(latency: &ShardLatency) -> Color {
+    match latency {
+        Ok(rtt) if *rtt >= SLOW_RTT => Color::Red,
+        Ok(rtt) if *rtt >= LAGGING_RTT => Color::Yellow,
+        Ok(_) => Color::Green,
+        Err(_) => Color::Red,
+    }
+}
+
+impl crate::App {
+    /// Draw the health of all shards as a row of cells colored by their latency
+    /// (gray if unreachable), followed by the aggregate stats.
+    pub(super) fn draw_latency_heatmap(&self, frame: &mut Frame, area: Rect) {
+        let Some(topology) = &self.topology else {
+            return;
+        };
+        let probe = &self.state.topology.latency;
+
+        let mut spans = vec!["Health: ".into()];
+        for device in &topology.devices {
+            spans.push(match probe.get(&device.instance) {
+                Some(Err(_)) => "■".dark_gray(),
+                Some(latency) => "■".fg(latency_color(latency)),
+                None => "□".dark_gray(),
+            });
+        }
+
+        let stats = probe.stats(topology.devices.iter().map(|d| d.instance.as_str()));
+        let millis =
+            |rtt: Option<Duration>| rtt.map_or("-".to_string(), |d| d.as_millis().to_string());
+        spans.push(Span::from(format!(
+            "  {} ok, {} slow, {} unreachable  |  RTT min/avg/max: {}/{}/{} ms",
+            stats.num_ok,
+            stats.num_slow,
+            stats.num_unreachable,
+            millis(stats.min),
+            millis(stats.avg),
+            millis(stats.max),
+        )));
+        if probe.is_pending() {
+            spans.push("  (pinging...)".dark_gray());
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).centered(), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_format_and_color() {
+        let fast: ShardLatency = Ok(Duration::from_millis(5));
+        let slow: ShardLatency = Ok(Duration::from_millis(150));
+        let failed: ShardLatency = Err("timeout".to_string());
+
+        assert_eq!(format_latency(&fast), "5 ms");
+        assert_eq!(format_latency(&failed), "timeout");
+        assert_eq!(latency_color(&fast), Color::Green);
+        assert_eq!(latency_color(&Ok(Duration::from_millis(50))), Color::Yellow);
+        assert_eq!(latency_color(&slow), Color::Red);
+        assert_eq!(latency_color(&failed), Color::Red);
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let mut probe = LatencyProbe::default();
+        probe
+            .latencies
+            .insert("a".into(), Ok(Duration::from_millis(5)));
+        probe
+            .latencies
+            .insert("b".into(), Ok(Duration::from_millis(55)));
+        probe.latencies.insert("c".into(), Err("timeout".into()));
+
+        let stats = probe.stats(["a", "b", "c", "not-probed"]);
+        assert_eq!(
+            stats,
+            LatencyStats {
+                num_ok: 1,
+                num_slow: 1,
+                num_unreachable: 1,
+                min: Some(Duration::from_millis(5)),
+                avg: Some(Duration::from_millis(30)),
+                max: Some(Duration::from_millis(55)),
+            }
+        );
+        assert_eq!(probe.stats([]), LatencyStats::default());
+    }
+}
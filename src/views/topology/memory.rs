@@ -0,0 +1,157 @@
+use crate::common::ShardResources;
+use crate::config::KVBits;
+use crate::utils::{ModelConfig, format_bytes};
+use tokio::sync::mpsc;
+
+/// Estimated KV cache size of a model (e.g. the one in the topology), per layer,
+/// along with the size of the weights of a layer.
+///
+/// The model config is fetched in the background, and the estimate is
+/// recomputed when the model, the sequence length or the KV bits change.
+#[derive(Debug, Default)]
+pub struct KvCacheEstimate {
+    /// What the estimate is for.
+    key: Option<(String, u32, KVBits)>,
+    /// KV cache of a single layer in bytes, `None` until (or unless) it is known.
+    per_layer: Option<u64>,
+    /// Weights of a single layer in bytes, `None` until (or unless) it is known.
+    weights_per_layer: Option<u64>,
+    /// Receiver of the estimates being computed.
+    rx: Option<mpsc::UnboundedReceiver<(Option<u64>, Option<u64>)>>,
+}
+
+impl KvCacheEstimate {
+    /// Estimate the KV cache of the given model, unless it is already estimated.
+    pub fn update(&mut self, model: &str, seq_len: u32, kv_bits: KVBits) {
+        let key = (model.to_string(), seq_len, kv_bits);
+        if self.key.as_ref() == Some(&key) {
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let repo_id = model.to_string();
+        tokio::spawn(async move {
+            let estimates = match ModelConfig::get_model_config(&repo_id).await {
+                Ok(config) => (
+                    config.kv_cache_bytes_per_layer(seq_len, kv_bits),
+                    config.layer_weight_bytes(),
+                ),
+                Err(_) => (None, None),
+            };
+            tx.send(estimates).ok();
+        });
+        self.key = Some(key);
+        self.per_layer = None;
+        self.weights_per_layer = None;
+        self.rx = Some(rx);
+    }
+
+    /// Collect the estimate, if it has been computed.
+    pub fn poll(&mut self) {
+        let Some(rx) = &mut self.rx else {
+            return;
+        };
+        if let Ok((per_layer, weights_per_layer)) = rx.try_recv() {
+            self.per_layer = per_layer;
+            self.weights_per_layer = weights_per_layer;
+            self.rx = None;
+        }
+    }
+
+    /// Whether the estimates are being computed.
+    pub fn is_pending(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Estimated memory of a single layer, i.e. its weights and its KV cache, in bytes.
+    pub fn layer_bytes(&self) -> Option<u64> {
+        Some(self.weights_per_layer? + self.per_layer?)
+    }
+
+    /// Estimated KV cache of the given number of layers, in bytes.
+    pub fn for_layers(&self, num_layers: usize) -> Option<u64> {
+        self.per_layer.map(|bytes| bytes * num_layers as u64)
+    }
+}
+
+/// Memory budget label of a device, e.g. `KV: 1.0 GiB | Mem: 16.0 GiB`, along with
+/// whether the device is over its memory, i.e. the KV cache and the weights (if reported)
+/// do not fit into the reported total memory.
+pub fn memory_budget(
+    kv_cache: Option<u64>,
+    resources: Option<&ShardResources>,
+) -> Option<(String, bool)> {
+    let total = resources.and_then(|r| r.memory_total);
+    match (kv_cache, total) {
+        (Some(kv_cache), Some(total)) => {
+            let weights = resources.and_then(|r| r.weights_bytes).unwrap_or_default();
+            let label = format!(
+                "KV: {} | Mem: {}",
+                format_bytes(kv_cache),
+                format_bytes(total)
+            );
+            Some((label, kv_cache + weights > total))
+        }
+        (Some(kv_cache), None) => Some((format!("KV: {}", format_bytes(kv_cache)), false)),
+        (None, Some(total)) => Some((format!("Mem: {}", format_bytes(total)), false)),
+        (None, None) => None,
+    }
+}
+
+/// Number of layers (of the given size) that fit into the free memory of a device,
+/// `None` if the device does not report its memory.
+///
+/// The free memory is what the device has left beside the shard's current usage,
+/// or beside its loaded weights if the usage is not reported.
+pub fn layers_that_fit(layer_bytes: u64, resources: &ShardResources) -> Option<u64> {
+    let total = resources.memory_total?;
+    let used = resources
+        .memory_used
+        .or(resources.weights_bytes)
+        .unwrap_or_default();
+    Some(total.saturating_sub(used) / layer_bytes.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_budget() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let resources = ShardResources {
+            memory_total: Some(16 * GIB),
+            weights_bytes: Some(12 * GIB),
+            ..Default::default()
+        };
+
+        assert_eq!(memory_budget(None, None), None);
+        assert_eq!(
+            memory_budget(Some(GIB), None),
+            Some(("KV: 1.0 GiB".to_string(), false))
+        );
+        assert_eq!(
+            memory_budget(Some(2 * GIB), Some(&resources)),
+            Some(("KV: 2.0 GiB | Mem: 16.0 GiB".to_string(), false))
+        );
+        // the weights leave no room for the KV cache
+        assert!(memory_budget(Some(5 * GIB), Some(&resources)).unwrap().1);
+    }
+
+    #[test]
+    fn test_layers_that_fit() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let mut resources = ShardResources {
+            memory_total: Some(16 * GIB),
+            weights_bytes: Some(12 * GIB),
+            ..Default::default()
+        };
+        assert_eq!(layers_that_fit(GIB, &resources), Some(4));
+
+        // the usage is preferred over the weights
+        resources.memory_used = Some(15 * GIB + GIB / 2);
+        assert_eq!(layers_that_fit(GIB, &resources), Some(0));
+
+        assert_eq!(layers_that_fit(GIB, &ShardResources::default()), None);
+    }
+}
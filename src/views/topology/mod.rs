@@ -1,21 +1,109 @@
 /// Shard-viewer.
 mod shard;
-pub use shard::ShardView;
+pub use shard::{CachedModel, ShardView};
+
+/// Request console of the shard-viewer.
+mod console;
+pub use console::{ConsoleField, REQUEST_TIMEOUT, ShardConsole, pretty_body};
+
+/// Inline editor of the assignments in the ring.
+mod edit;
+pub use edit::AssignmentEditor;
 
 /// Ring topology viewer.
 mod ring;
 pub use ring::*;
 
+/// Table layout of the ring topology viewer.
+mod table;
+
+/// Solver details panel of the ring topology viewer.
+mod solver;
+pub use solver::SolverDetails;
+
+/// KV cache estimates of the shards in the ring.
+mod memory;
+pub use memory::{KvCacheEstimate, layers_that_fit, memory_budget};
+
+/// Queue size history of the shards.
+mod queue;
+pub use queue::QueueHistory;
+
+/// Latency probing of the shards in the ring.
+mod latency;
+pub use latency::*;
+
+use std::collections::HashSet;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TopologyView {
     Shard(String, ShardView),
     Ring(TopologyRingView),
 }
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct TopologyState {
     /// Selected device index in topology view.
     pub selected_device: usize,
+    /// Last time we refreshed the topology.
+    pub refreshed_at: Instant,
+    /// Zoom & pan of the ring canvas.
+    pub ring_viewport: RingViewport,
+    /// Changes at the last refresh that changed the topology.
+    pub diff: Option<TopologyDiff>,
+    /// Whether the topology is shown as a table instead of the ring.
+    pub table_layout: bool,
+    /// Whether the solver details panel is shown next to the topology.
+    pub show_solver: bool,
+    /// Round-trip times to the shards, probed at each refresh.
+    pub latency: LatencyProbe,
+    /// Whether the shard restart confirmation dialog is open.
+    pub confirm_restart: bool,
+    /// Last time we fetched the health of the viewed shard.
+    pub shard_refreshed_at: Instant,
+    /// Request console of the shard view, if open.
+    pub console: Option<ShardConsole>,
+    /// Assignment editor of the ring view, if open.
+    pub editor: Option<AssignmentEditor>,
+    /// All active topologies, one per loaded model, the shown one is `App::topology`.
+    pub topologies: Vec<crate::common::TopologyInfo>,
+    /// Whether the API supports several loaded models, i.e. reports a list of topologies.
+    pub is_multi_model: bool,
+    /// KV cache estimate of the shown topology's model.
+    pub kv_cache: KvCacheEstimate,
+    /// Instances of the busy devices, polled more often than the topology,
+    /// `None` until the first poll so that the topology's busy states are used.
+    pub busy: Option<HashSet<String>>,
+    /// Last time we polled the busy states.
+    pub busy_refreshed_at: Instant,
+    /// Receiver of the busy states being polled, if any.
+    pub busy_rx: Option<mpsc::UnboundedReceiver<HashSet<String>>>,
+}
+
+impl Default for TopologyState {
+    fn default() -> Self {
+        Self {
+            selected_device: 0,
+            refreshed_at: Instant::now(),
+            ring_viewport: RingViewport::default(),
+            diff: None,
+            table_layout: false,
+            show_solver: false,
+            latency: LatencyProbe::default(),
+            confirm_restart: false,
+            shard_refreshed_at: Instant::now(),
+            console: None,
+            editor: None,
+            topologies: Vec::new(),
+            is_multi_model: false,
+            kv_cache: KvCacheEstimate::default(),
+            busy: None,
+            busy_refreshed_at: Instant::now(),
+            busy_rx: None,
+        }
+    }
 }
 
 impl crate::App {
@@ -0,0 +1,51 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Number of queue size samples that are kept per shard.
+pub const MAX_QUEUE_SAMPLES: usize = 120;
+
+/// Queue sizes of the shards over time, keyed by instance, to spot backpressure.
+///
+/// Samples are taken whenever a shard's health is fetched, i.e. by the latency
+/// probes of the ring view and by the refreshes of the shard view.
+#[derive(Debug, Default)]
+pub struct QueueHistory {
+    samples: HashMap<String, VecDeque<u64>>,
+}
+
+impl QueueHistory {
+    /// Record a sample of the shard's queue size, dropping the oldest beyond the limit.
+    pub fn record(&mut self, instance: &str, queue_size: u32) {
+        let samples = self.samples.entry(instance.to_string()).or_default();
+        if samples.len() == MAX_QUEUE_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(queue_size as u64);
+    }
+
+    /// Samples of the shard from the oldest to the latest, empty if never sampled.
+    pub fn get(&self, instance: &str) -> impl Iterator<Item = u64> + '_ {
+        self.samples.get(instance).into_iter().flatten().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_history() {
+        let mut history = QueueHistory::default();
+        assert_eq!(history.get("shard-1").count(), 0);
+
+        for queue_size in 0..MAX_QUEUE_SAMPLES as u32 + 2 {
+            history.record("shard-1", queue_size);
+        }
+        history.record("shard-2", 7);
+
+        let samples = history.get("shard-1").collect::<Vec<_>>();
+        assert_eq!(samples.len(), MAX_QUEUE_SAMPLES);
+        assert_eq!(samples.first(), Some(&2));
+        assert_eq!(samples.last(), Some(&(MAX_QUEUE_SAMPLES as u64 + 1)));
+        assert_eq!(history.get("shard-2").collect::<Vec<_>>(), vec![7]);
+    }
+}
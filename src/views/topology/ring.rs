@@ -1,25 +1,333 @@
-use crate::common::TopologyInfo;
+use super::{ShardLatency, format_latency, latency_color, memory_budget};
+use crate::common::{AssignmentInfo, Topologies, TopologyInfo, is_instance_of};
+use crate::widgets::Legend;
 use crate::{app::AppView, utils::get_sliding_text};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Margin, Rect},
     style::{Color, Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{
-        Block, Paragraph,
+        Block, Paragraph, Tabs,
         canvas::{Canvas, Circle, Line as CanvasLine, Points},
     },
 };
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Color of the selected device in the ring.
+const SELECTED_COLOR: Color = Color::Yellow;
+
+/// Color of the devices that are idle, i.e. none of the other states apply.
+const IDLE_COLOR: Color = Color::Green;
+
+/// Color of the devices that are busy, i.e. computing, which is orange.
+const BUSY_COLOR: Color = Color::Indexed(208);
+
+/// Color of the manager (API) device.
+const MANAGER_COLOR: Color = Color::Blue;
+
+/// Color of the devices that did not respond to the latency probe.
+const UNREACHABLE_COLOR: Color = Color::DarkGray;
+
+/// Color of the devices that have no layers assigned, which are dimmed.
+const UNASSIGNED_COLOR: Color = Color::Gray;
+
+/// Radius of the circle around the devices at the ends of the pipeline.
+const PIPELINE_END_RADIUS: f64 = 2.5;
+
+/// Color of the thunderbolt links between devices in the ring.
+const THUNDERBOLT_COLOR: Color = Color::Magenta;
+
+/// Color of the marker that travels the ring while a chat response is generated.
+const TOKEN_MARKER_COLOR: Color = Color::White;
+
+/// Seconds that the token marker takes to travel from one device to the next.
+const TOKEN_HOP_DURATION: f64 = 0.5;
+
+/// Color of the devices that were removed since the last refresh.
+const REMOVED_COLOR: Color = Color::Red;
+
+/// How long the changes of a topology refresh stay highlighted.
+const CHANGE_HIGHLIGHT_DURATION: Duration = Duration::from_secs(5);
+
+/// A change of a device between two topology refreshes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeviceChange {
+    /// The device has joined the topology.
+    Added,
+    /// The layers or the window size of the device have changed.
+    Reassigned,
+}
+
+impl DeviceChange {
+    pub fn color(&self) -> Color {
+        match self {
+            DeviceChange::Added => Color::LightGreen,
+            DeviceChange::Reassigned => Color::LightBlue,
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceChange::Added => write!(f, "new"),
+            DeviceChange::Reassigned => write!(f, "moved"),
+        }
+    }
+}
+
+/// A device at an end of the pipeline, which shows the direction of the ring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PipelineEnd {
+    /// The device holding the first layer, i.e. the embeddings.
+    Entry,
+    /// The device holding the last layer, i.e. the output head.
+    Exit,
+}
+
+impl PipelineEnd {
+    pub fn color(&self) -> Color {
+        match self {
+            PipelineEnd::Entry => Color::LightCyan,
+            PipelineEnd::Exit => Color::LightMagenta,
+        }
+    }
+}
+
+impl std::fmt::Display for PipelineEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineEnd::Entry => write!(f, "input"),
+            PipelineEnd::Exit => write!(f, "output"),
+        }
+    }
+}
+
+/// Changes between two topologies, highlighted in the ring for a few seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopologyDiff {
+    /// Changed devices, by instance.
+    pub changed: HashMap<String, DeviceChange>,
+    /// Instances of the removed devices.
+    pub removed: Vec<String>,
+    /// When the changes were detected.
+    pub detected_at: Instant,
+}
+
+impl TopologyDiff {
+    /// Changes from the old topology to the new one, `None` if nothing has changed.
+    pub fn between(old: &TopologyInfo, new: &TopologyInfo) -> Option<Self> {
+        let assignment = |topology: &TopologyInfo, instance: &str| {
+            topology
+                .assignment_of(instance)
+                .map(|a| (a.layers.clone(), a.window_size))
+        };
+
+        let mut changed = HashMap::new();
+        for device in &new.devices {
+            if !old.devices.iter().any(|d| d.instance == device.instance) {
+                changed.insert(device.instance.clone(), DeviceChange::Added);
+            } else if assignment(old, &device.instance) != assignment(new, &device.instance) {
+                changed.insert(device.instance.clone(), DeviceChange::Reassigned);
+            }
+        }
+        let removed = old
+            .devices
+            .iter()
+            .filter(|d| !new.devices.iter().any(|n| n.instance == d.instance))
+            .map(|d| d.instance.clone())
+            .collect::<Vec<_>>();
+
+        (!changed.is_empty() || !removed.is_empty()).then(|| Self {
+            changed,
+            removed,
+            detected_at: Instant::now(),
+        })
+    }
+
+    /// Whether the changes are recent enough to be highlighted.
+    pub fn is_recent(&self) -> bool {
+        self.detected_at.elapsed() < CHANGE_HIGHLIGHT_DURATION
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TopologyRingView {
     Loading,
     Loaded,
+    /// Resubmitting the topology with the edited assignments.
+    Submitting(Vec<AssignmentInfo>),
     Error(String),
 }
 
+/// Width of a terminal cell relative to its height, so that the ring stays round.
+const CELL_ASPECT: f64 = 0.5;
+
+/// Size of the labels next to the devices in cells, i.e. the longest line (with its
+/// offset from the device) and the number of lines, which the ring leaves room for.
+const LABEL_SIZE: (f64, f64) = (32.0, 7.0);
+
+/// Smallest radius of the ring, below which the labels are allowed to clip.
+const MIN_RING_RADIUS: f64 = 10.0;
+
+/// Zoom & pan of the ring canvas, so that large clusters stay readable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RingViewport {
+    /// Zoom factor, larger values show a smaller part of the canvas.
+    pub zoom: f64,
+    /// Center of the view in canvas coordinates.
+    pub center: (f64, f64),
+}
+
+impl Default for RingViewport {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            center: (0.0, 0.0),
+        }
+    }
+}
+
+impl RingViewport {
+    /// Half of the width (and height) of the canvas when not zoomed.
+    const HALF_SIZE: f64 = 60.0;
+    const MIN_ZOOM: f64 = 0.5;
+    const MAX_ZOOM: f64 = 8.0;
+    /// Zoom factor applied at each step.
+    const ZOOM_STEP: f64 = 1.25;
+    /// Pan distance at each step, as a fraction of the visible size.
+    const PAN_STEP: f64 = 0.1;
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * Self::ZOOM_STEP).min(Self::MAX_ZOOM);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / Self::ZOOM_STEP).max(Self::MIN_ZOOM);
+    }
+
+    /// Pan by the given number of steps in each direction, steps shrink as we zoom in.
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let step = 2.0 * Self::HALF_SIZE / self.zoom * Self::PAN_STEP;
+        self.center.0 += dx * step;
+        self.center.1 += dy * step;
+    }
+
+    /// The `x` and `y` bounds of the canvas drawn within the given area, where the
+    /// width follows the aspect ratio of the area so that the ring is not squashed.
+    pub fn bounds(&self, area: Rect) -> ([f64; 2], [f64; 2]) {
+        let half_y = Self::HALF_SIZE / self.zoom;
+        let half_x = half_y * Self::aspect_ratio(area);
+        let (x, y) = self.center;
+        ([x - half_x, x + half_x], [y - half_y, y + half_y])
+    }
+
+    /// Width and height of a terminal cell in canvas units, within the given area.
+    pub fn cell_size(&self, area: Rect) -> (f64, f64) {
+        let ([x0, x1], [y0, y1]) = self.bounds(area);
+        (
+            (x1 - x0) / area.width.max(1) as f64,
+            (y1 - y0) / area.height.max(1) as f64,
+        )
+    }
+
+    /// Radius of the ring that leaves room for the labels within the given area, when
+    /// not zoomed. Labels are printed rightwards, so they take room on the right only.
+    pub fn ring_radius(area: Rect) -> f64 {
+        let viewport = Self::default();
+        let ([_, half_x], [_, half_y]) = viewport.bounds(area);
+        let (cell_width, cell_height) = viewport.cell_size(area);
+        let (label_width, label_height) = LABEL_SIZE;
+
+        (half_x - label_width * cell_width)
+            .min(half_y - label_height * cell_height)
+            .max(MIN_RING_RADIUS)
+    }
+
+    /// Ratio of the width of the area to its height, in terms of length on screen.
+    fn aspect_ratio(area: Rect) -> f64 {
+        area.width as f64 * CELL_ASPECT / area.height.max(1) as f64
+    }
+}
+
 impl TopologyInfo {
+    /// How the topology was prepared, i.e. `Optimized` by the solver or `Manual`.
+    pub fn solver(&self) -> &'static str {
+        if self.solution.is_some() {
+            "Optimized"
+        } else {
+            "Manual"
+        }
+    }
+
+    /// Whether the device is at an end of the pipeline, i.e. holds the first or
+    /// the last assigned layer. A device holding both is reported as the entry.
+    pub fn pipeline_end(&self, instance: &str) -> Option<PipelineEnd> {
+        let layers = |a: &AssignmentInfo| a.layers.iter().flatten().copied().collect::<Vec<_>>();
+        let last_layer = self.assignments.iter().flat_map(layers).max()?;
+        let layers = self.assignment_of(instance).map(layers)?;
+
+        if layers.contains(&0) {
+            Some(PipelineEnd::Entry)
+        } else if layers.contains(&last_layer) {
+            Some(PipelineEnd::Exit)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the device after (or before) the given one along the ring, i.e.
+    /// following the `next_instance` of the assignments rather than the device order.
+    pub fn ring_neighbor(&self, index: usize, forward: bool) -> Option<usize> {
+        let instance = &self.devices.get(index)?.instance;
+        let neighbor = if forward {
+            &self.assignment_of(instance)?.next_instance
+        } else {
+            self.assignments
+                .iter()
+                .find(|a| is_instance_of(&a.next_instance, instance))
+                .map(|a| &a.instance)?
+        };
+        self.devices
+            .iter()
+            .position(|d| is_instance_of(neighbor, &d.instance))
+    }
+
+    /// Indices of the devices in the order of the ring, from the input shard (or the
+    /// first assigned device) along the `next_instance` of the assignments, until the
+    /// ring closes or breaks.
+    pub fn ring_order(&self) -> Vec<usize> {
+        let assigned = (0..self.devices.len())
+            .filter(|i| self.assignment_of(&self.devices[*i].instance).is_some())
+            .collect::<Vec<_>>();
+        let mut current = assigned
+            .iter()
+            .find(|i| self.pipeline_end(&self.devices[**i].instance) == Some(PipelineEnd::Entry))
+            .or(assigned.first())
+            .copied();
+
+        let mut order = Vec::new();
+        while let Some(i) = current.filter(|i| !order.contains(i)) {
+            order.push(i);
+            current = self.ring_neighbor(i, true);
+        }
+        order
+    }
+
+    /// Number of distinct layers that are assigned to a device.
+    pub fn num_assigned_layers(&self) -> usize {
+        self.assignments
+            .iter()
+            .flat_map(|a| a.layers.iter().flatten())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
     /// Format layer assignments compactly (e.g., [0..11, 12..23, 24..35])
     pub fn format_layers(layers: &[Vec<u32>]) -> String {
         let ranges: Vec<String> = layers
@@ -36,6 +344,76 @@ impl TopologyInfo {
             .collect();
         format!("[{}]", ranges.join(", "))
     }
+
+    /// Thunderbolt links between the devices, as pairs of device indices.
+    ///
+    /// A device is linked to another if one of its thunderbolt instances is connected
+    /// to an instance of the other, matched by the domain UUID. Each link is listed once.
+    pub fn thunderbolt_links(&self) -> Vec<(usize, usize)> {
+        crate::common::thunderbolt_links(&self.devices)
+    }
+
+    /// Render the topology as a Graphviz DOT digraph, with an edge from each
+    /// instance to its next instance in the ring.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('"', "\\\""));
+
+        let mut dot = String::from("digraph topology {\n");
+        dot.push_str(&format!(
+            "  label={};\n  node [shape=box];\n",
+            quote(&format!(
+                "{} ({} layers)",
+                self.model.as_deref().unwrap_or("<not loaded>"),
+                self.num_layers
+            ))
+        ));
+        for device in &self.devices {
+            let mut label = format!(
+                "{}\\n{}:{} ({})",
+                device.instance, device.local_ip, device.shard_port, device.server_port
+            );
+            if let Some(assignment) = self.assignment_of(&device.instance) {
+                label.push_str(&format!(
+                    "\\nlayers {}\\nwindow {}",
+                    Self::format_layers(&assignment.layers),
+                    assignment.window_size
+                ));
+            }
+            dot.push_str(&format!(
+                "  {} [label={}];\n",
+                quote(&device.instance),
+                quote(&label)
+            ));
+        }
+        for assignment in &self.assignments {
+            dot.push_str(&format!(
+                "  {} -> {};\n",
+                quote(&assignment.instance),
+                quote(&assignment.next_instance)
+            ));
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Write the topology to a timestamped file in the current directory,
+    /// as DOT or pretty JSON, returning the path of the written file.
+    pub fn export(&self, as_dot: bool) -> color_eyre::Result<PathBuf> {
+        let (content, extension) = if as_dot {
+            (self.to_dot(), "dot")
+        } else {
+            (serde_json::to_string_pretty(self)?, "json")
+        };
+
+        let path = PathBuf::from(format!(
+            "dnet-topology-{}.{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            extension
+        ));
+        std::fs::write(&path, content)?;
+
+        Ok(path)
+    }
 }
 impl crate::App {
     pub(super) fn draw_topology_ring_view(&mut self, frame: &mut Frame, state: &TopologyRingView) {
@@ -44,21 +422,53 @@ impl crate::App {
         let vertical = Layout::vertical([
             Constraint::Length(3), // Title
             Constraint::Min(0),    // Content
+            Constraint::Length(1), // Health heatmap
             Constraint::Length(2), // Footer
         ]);
-        let [title_area, content_area, footer_area] = vertical.areas(area);
+        let [title_area, content_area, heatmap_area, footer_area] = vertical.areas(area);
 
-        // Title
-        let title = Line::from("Topology Ring View").bold().blue().centered();
+        // Title, with a summary of the topology below it and the models to switch between
+        let [title_area, tabs_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Length(1)]).areas(title_area);
+        if self.state.topology.topologies.len() > 1 {
+            let models = self.state.topology.topologies.iter().map(|t| {
+                t.model
+                    .clone()
+                    .unwrap_or_else(|| "<not loaded>".to_string())
+            });
+            frame.render_widget(
+                Tabs::new(models)
+                    .select(self.active_topology())
+                    .highlight_style(Style::default().fg(Color::Yellow).bold()),
+                tabs_area,
+            );
+        }
+        let mut title = vec![Line::from("Topology Ring View").bold().blue().centered()];
+        if let (TopologyRingView::Loaded, Some(topology)) = (state, &self.topology) {
+            title.push(
+                Line::from(format!(
+                    "Devices: {}  |  Layers: {}/{} assigned  |  Solver: {}",
+                    topology.devices.len(),
+                    topology.num_assigned_layers(),
+                    topology.num_layers,
+                    topology.solver()
+                ))
+                .gray()
+                .centered(),
+            );
+        }
         frame.render_widget(Paragraph::new(title), title_area);
 
         // Content
         match state {
-            TopologyRingView::Loading => {
+            TopologyRingView::Loading | TopologyRingView::Submitting(_) => {
+                let message = if matches!(state, TopologyRingView::Submitting(_)) {
+                    "Submitting topology..."
+                } else {
+                    "Loading topology..."
+                };
                 frame.render_widget(
-                    Paragraph::new("Loading topology...")
-                        .block(Block::bordered())
-                        .centered(),
+                    Paragraph::new(message).block(Block::bordered()).centered(),
                     content_area,
                 );
             }
@@ -125,7 +535,22 @@ impl crate::App {
             }
             TopologyRingView::Loaded => {
                 if self.topology.is_some() {
-                    self.draw_topology_ring(frame, content_area);
+                    let content_area = if self.state.topology.show_solver {
+                        let [content_area, solver_area] =
+                            Layout::horizontal([Constraint::Min(0), Constraint::Length(40)])
+                                .areas(content_area);
+                        self.draw_solver_panel(frame, solver_area);
+                        content_area
+                    } else {
+                        content_area
+                    };
+                    if self.state.topology.table_layout {
+                        self.draw_topology_table(frame, content_area);
+                    } else {
+                        self.draw_topology_ring(frame, content_area);
+                    }
+                    self.draw_assignment_editor(frame, content_area);
+                    self.draw_latency_heatmap(frame, heatmap_area);
                 } else {
                     frame.render_widget(
                         Paragraph::new("No topology data available")
@@ -137,14 +562,27 @@ impl crate::App {
             }
         }
 
-        // Footer
+        // Footer, with the status (e.g. of an export) below the hints
         let footer_text = match state {
             TopologyRingView::Loaded => {
-                "Use ↑↓ to select device  |  Enter to interact  |  Esc to go back"
+                let hints = "Use ↑↓ or 1-9 to select device  |  ←→ to follow the ring  |  Enter to interact  |  a to edit assignment  |  t to toggle table  |  s to toggle solver  |  p to ping all  |  +/- to zoom  |  hjkl to pan  |  0 to reset  |  e/E to export DOT/JSON  |  Esc to go back";
+                if self.state.topology.topologies.len() > 1 {
+                    format!("Tab to switch model  |  {}", hints)
+                } else {
+                    hints.to_string()
+                }
             }
-            _ => "Press Esc to go back",
+            _ => "Press Esc to go back".to_string(),
         };
-        frame.render_widget(Paragraph::new(footer_text).centered().gray(), footer_area);
+        let status = if self.status_message.starts_with("Error") {
+            Line::from(self.status_message.as_str()).red()
+        } else {
+            Line::from(self.status_message.as_str()).green()
+        };
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(footer_text).gray(), status]).centered(),
+            footer_area,
+        );
     }
 
     pub fn draw_topology_ring(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
@@ -170,47 +608,83 @@ impl crate::App {
             return;
         }
 
-        // Calculate circle parameters for canvas
-        let radius = 35.0;
+        // Calculate circle parameters for canvas, w.r.t. the area within the borders
+        let canvas_area = area.inner(Margin::new(1, 1));
+        let viewport = self.state.topology.ring_viewport;
+        let radius = RingViewport::ring_radius(canvas_area);
+        let (cell_width, cell_height) = viewport.cell_size(canvas_area);
         let center_x = 0.0;
         let center_y = 0.0;
 
         // Prepare device data for drawing
         #[derive(Clone)]
         struct DeviceInfo {
+            index: usize,
             x: f64,
             y: f64,
             instance: String,
             ip: String,
             layers: String,
             is_selected: bool,
+            /// Whether the device has layers assigned, i.e. is part of the ring.
+            is_assigned: bool,
             num_rounds: u32,
             window_size: u32,
+            change: Option<DeviceChange>,
+            latency: Option<ShardLatency>,
+            is_busy: bool,
+            is_manager: bool,
+            pipeline_end: Option<PipelineEnd>,
+            memory: Option<(String, bool)>,
         }
 
         let mut devices_info = Vec::new();
+        let recent_diff = self
+            .state
+            .topology
+            .diff
+            .as_ref()
+            .filter(|diff| diff.is_recent());
+
+        // positions of all devices, including the unassigned ones
+        let positions = (0..num_devices)
+            .map(|i| {
+                let angle = 2.0 * PI * (i as f64) / (num_devices as f64) - PI / 2.0;
+                (
+                    center_x + radius * angle.cos(),
+                    center_y + radius * angle.sin(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // data-plane traffic often flows over thunderbolt rather than the logical ring
+        let thunderbolt_lines = topology
+            .thunderbolt_links()
+            .into_iter()
+            .map(|(i, j)| CanvasLine {
+                x1: positions[i].0,
+                y1: positions[i].1,
+                x2: positions[j].0,
+                y2: positions[j].1,
+                color: THUNDERBOLT_COLOR,
+            })
+            .collect::<Vec<_>>();
 
         for (i, device) in topology.devices.iter().enumerate() {
-            let angle = 2.0 * PI * (i as f64) / (num_devices as f64) - PI / 2.0;
-            let x = center_x + radius * angle.cos();
-            let y = center_y + radius * angle.sin();
+            let (x, y) = positions[i];
 
-            // assignment info - match by checking if service contains the device instance
-            let Some(assignment) = topology
-                .assignments
-                .iter()
-                // TODO: could be done with equals perhaps
-                .find(|a| a.instance.contains(&device.instance))
-            else {
-                continue;
-            };
+            // assignment info, devices without one (e.g. the manager) are still shown, dimmed
+            let assignment = topology.assignment_of(&device.instance);
 
-            // Get device name without "shard-" prefix
-            let instance = device
-                .instance
-                .strip_prefix("shard-")
-                .unwrap_or(&device.instance)
-                .to_string();
+            // Get device alias, or its name without "shard-" prefix
+            let instance = match self.config.device_aliases.get(&device.instance) {
+                Some(alias) => alias.clone(),
+                None => device
+                    .instance
+                    .strip_prefix("shard-")
+                    .unwrap_or(&device.instance)
+                    .to_string(),
+            };
 
             // Get IP and GRPC port
             let ip = format!(
@@ -219,51 +693,109 @@ impl crate::App {
             );
 
             // Get layer assignments
-            let layers = TopologyInfo::format_layers(&assignment.layers);
+            let layers = match assignment {
+                Some(assignment) => TopologyInfo::format_layers(&assignment.layers),
+                None => "No layers assigned".to_string(),
+            };
 
             let is_selected = i == self.state.topology.selected_device;
+            let change = recent_diff.and_then(|diff| diff.changed.get(&device.instance).copied());
 
             devices_info.push(DeviceInfo {
+                index: i,
                 x,
                 y,
                 instance: get_sliding_text(self.animation_start.elapsed(), &instance, 30),
                 ip,
                 layers,
                 is_selected,
-                num_rounds: assignment.layers.len() as u32,
-                window_size: assignment.window_size,
+                is_assigned: assignment.is_some(),
+                num_rounds: assignment.map_or(0, |a| a.layers.len() as u32),
+                window_size: assignment.map_or(0, |a| a.window_size),
+                change,
+                latency: self.state.topology.latency.get(&device.instance).cloned(),
+                is_busy: match &self.state.topology.busy {
+                    Some(busy) => busy.contains(&device.instance),
+                    None => device.is_busy,
+                },
+                is_manager: device.is_manager,
+                pipeline_end: topology.pipeline_end(&device.instance),
+                memory: assignment.and_then(|a| {
+                    memory_budget(
+                        self.state
+                            .topology
+                            .kv_cache
+                            .for_layers(a.layers.iter().map(Vec::len).sum()),
+                        self.state.topology.latency.get_resources(&device.instance),
+                    )
+                }),
             });
         }
 
-        // Clone for use in canvas closure
-        let devices_clone = devices_info
-            .iter()
-            .map(|d| {
-                (
-                    // TODO: clone with `.clone()`
-                    d.x,
-                    d.y,
-                    d.instance.clone(),
-                    d.ip.clone(),
-                    d.layers.clone(),
-                    d.is_selected,
-                    d.num_rounds,
-                    d.window_size,
-                )
-            })
-            .collect::<Vec<_>>();
+        let mut legend = Legend::default()
+            .entry("●", "Selected", SELECTED_COLOR)
+            .entry("●", "Idle", IDLE_COLOR)
+            .entry("●", "Busy", BUSY_COLOR)
+            .entry("●", "Manager", MANAGER_COLOR)
+            .entry("●", "Unreachable", UNREACHABLE_COLOR)
+            .entry("●", "Unassigned", UNASSIGNED_COLOR)
+            .entry("●", "New", DeviceChange::Added.color())
+            .entry("●", "Moved", DeviceChange::Reassigned.color())
+            .entry("○", "Input shard", PipelineEnd::Entry.color())
+            .entry("○", "Output shard", PipelineEnd::Exit.color());
+        if !thunderbolt_lines.is_empty() {
+            legend = legend.entry("─", "Thunderbolt", THUNDERBOLT_COLOR);
+        }
 
         let model_info = format!(
-            "Model: {}  |  Layers: {}",
-            topology.model.clone().unwrap_or("<not loaded>".into()),
-            topology.num_layers
+            "Model: {}",
+            topology.model.clone().unwrap_or("<not loaded>".into())
         );
 
+        // only the assigned devices are connected, in the order of the ring
+        let ring_order = topology.ring_order();
+        let is_ring_closed = ring_order
+            .last()
+            .and_then(|last| topology.ring_neighbor(*last, true))
+            == ring_order.first().copied();
+        let ring = ring_order
+            .into_iter()
+            .map(|i| &devices_info[i])
+            .map(|d| (d.x, d.y, d.latency.clone()))
+            .collect::<Vec<_>>();
+
+        // while a chat response is being generated, a marker travels the ring to visualize the pipeline
+        let is_generating = self.state.chat.sessions.iter().any(|s| s.is_generating);
+        let token_marker = (is_generating && !ring.is_empty()).then(|| {
+            let hops = self.animation_start.elapsed().as_secs_f64() / TOKEN_HOP_DURATION;
+            let (from_x, from_y, _) = &ring[hops as usize % ring.len()];
+            let (to_x, to_y, _) = &ring[(hops as usize + 1) % ring.len()];
+            let t = hops.fract();
+            (from_x + (to_x - from_x) * t, from_y + (to_y - from_y) * t)
+        });
+
+        // removed devices have no place in the ring, so they are listed below it
+        let mut block = Block::bordered().title(model_info);
+        if let Some(diff) = recent_diff.filter(|diff| !diff.removed.is_empty()) {
+            block = block.title_bottom(
+                Line::from(format!(
+                    " Removed: {} ",
+                    diff.removed
+                        .iter()
+                        .map(|instance| self.config.device_name(instance))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+                .fg(REMOVED_COLOR),
+            );
+        }
+
         // draw canvas with ring
+        let (x_bounds, y_bounds) = viewport.bounds(canvas_area);
         let canvas = Canvas::default()
-            .block(Block::bordered().title(model_info))
-            .x_bounds([-60.0, 60.0])
-            .y_bounds([-60.0, 60.0])
+            .block(block)
+            .x_bounds(x_bounds)
+            .y_bounds(y_bounds)
             .paint(move |ctx| {
                 // draw the circle
                 ctx.draw(&Circle {
@@ -274,90 +806,239 @@ impl crate::App {
                 });
 
                 // draw connection lines between devices
-                for i in 0..devices_clone.len() {
-                    let (x1, y1, _, _, _, _, _, _) = devices_clone[i];
-                    let next_i = (i + 1) % devices_clone.len();
-                    let (x2, y2, _, _, _, _, _, _) = devices_clone[next_i];
-
+                // colored by the latency of the hop's destination, to spot slow hops
+                // a broken ring is not closed from its last device back to the first
+                for (i, (x, y, _)) in ring.iter().enumerate() {
+                    if i + 1 == ring.len() && !is_ring_closed {
+                        break;
+                    }
+                    let (next_x, next_y, next_latency) = &ring[(i + 1) % ring.len()];
                     ctx.draw(&CanvasLine {
-                        x1,
-                        y1,
-                        x2,
-                        y2,
-                        color: Color::DarkGray,
+                        x1: *x,
+                        y1: *y,
+                        x2: *next_x,
+                        y2: *next_y,
+                        color: next_latency.as_ref().map_or(Color::DarkGray, latency_color),
+                    });
+                }
+
+                // thunderbolt links go on top of the ring connections
+                for line in &thunderbolt_lines {
+                    ctx.draw(line);
+                }
+
+                if let Some((x, y)) = token_marker {
+                    ctx.draw(&Circle {
+                        x,
+                        y,
+                        radius: 1.5,
+                        color: TOKEN_MARKER_COLOR,
                     });
                 }
 
                 // Draw devices with their info
-                for (x, y, name, ip, layers, is_selected, num_rounds, window_size) in
-                    devices_clone.iter()
-                {
-                    // Draw device point with larger size if selected
-                    let color = if *is_selected {
-                        Color::Yellow
-                    } else {
-                        Color::Green
+                for device in &devices_info {
+                    let (x, y) = (device.x, device.y);
+
+                    // Draw device point with larger size if selected, recently changed
+                    // devices are marked with the color of their change, see the legend
+                    let color = match (device.is_selected, device.change) {
+                        (true, _) => SELECTED_COLOR,
+                        (false, Some(change)) => change.color(),
+                        _ if device.latency.as_ref().is_some_and(|l| l.is_err()) => {
+                            UNREACHABLE_COLOR
+                        }
+                        _ if device.is_busy => BUSY_COLOR,
+                        _ if device.is_manager => MANAGER_COLOR,
+                        _ if !device.is_assigned => UNASSIGNED_COLOR,
+                        _ => IDLE_COLOR,
                     };
 
                     // Draw a larger point for better visibility
                     ctx.draw(&Points {
-                        coords: &[(*x, *y)],
+                        coords: &[(x, y)],
                         color,
                     });
 
-                    // If selected, draw additional points around it to make it stand out
-                    if *is_selected {
+                    // If selected or changed, draw additional points around it to make it stand out
+                    if device.is_selected || device.change.is_some() {
                         ctx.draw(&Points {
                             #[rustfmt::skip]
                             coords: &[
-                                (*x + 0.5, *y      ),
-                                (*x - 0.5, *y      ),
-                                (*x      , *y + 0.5),
-                                (*x      , *y - 0.5)
+                                (x + 0.5, y      ),
+                                (x - 0.5, y      ),
+                                (x      , y + 0.5),
+                                (x      , y - 0.5)
                             ],
-                            color: Color::Yellow,
+                            color,
                         });
                     }
 
-                    // Calculate text offset based on position to avoid overlap with circle
-                    let text_offset = 5.0;
-                    let angle = y.atan2(*x);
-                    let text_x = x + text_offset * angle.cos();
-                    let text_y = y + text_offset * angle.sin();
+                    // the ends of the pipeline are circled, to show the direction of the ring
+                    if let Some(end) = device.pipeline_end {
+                        ctx.draw(&Circle {
+                            x,
+                            y,
+                            radius: PIPELINE_END_RADIUS,
+                            color: end.color(),
+                        });
+                    }
+
+                    // Calculate text offset based on position to avoid overlap with circle,
+                    // in terms of cells so that lines do not overlap at any size
+                    let angle = y.atan2(x);
+                    let text_x = x + 2.0 * cell_width * angle.cos();
+                    let text_y = y + 2.0 * cell_height * angle.sin();
+                    let line_y = |line: f64| text_y + (1.5 - line) * cell_height;
 
                     // Draw device info: name, IP, layers, rounds/window (each on a separate line)
-                    // Highlight text in yellow if selected
-                    let rounds_window_text =
-                        format!("Rounds: {}, Window: {}", num_rounds, window_size);
-                    if *is_selected {
-                        ctx.print(text_x, text_y + 4.5, name.clone().yellow());
-                        ctx.print(text_x, text_y + 1.2, ip.clone().yellow());
-                        ctx.print(text_x, text_y - 1.2, layers.clone().yellow());
-                        ctx.print(text_x, text_y - 4.5, rounds_window_text.yellow());
+                    // Highlight text in yellow if selected, or with the color of the change
+                    // the first devices are numbered, to jump to them with number keys
+                    let mut name = match device.index {
+                        0..9 => format!("{}. {}", device.index + 1, device.instance),
+                        _ => device.instance.clone(),
+                    };
+                    if let Some(change) = device.change {
+                        name.push_str(&format!(" [{}]", change));
+                    }
+                    if let Some(end) = device.pipeline_end {
+                        name.push_str(&format!(" ({})", end));
+                    }
+                    let rounds_window_text = format!(
+                        "Rounds: {}, Window: {}",
+                        device.num_rounds, device.window_size
+                    );
+                    if !device.is_assigned {
+                        // unassigned devices are not in the ring, so they are dimmed
+                        let style = if device.is_selected {
+                            Style::default().fg(color)
+                        } else {
+                            Style::default().fg(UNASSIGNED_COLOR).dim()
+                        };
+                        ctx.print(text_x, line_y(0.0), Span::styled(name, style));
+                        ctx.print(text_x, line_y(1.0), Span::styled(device.ip.clone(), style));
+                        ctx.print(
+                            text_x,
+                            line_y(2.0),
+                            Span::styled(device.layers.clone(), style),
+                        );
+                    } else if device.is_selected || device.change.is_some() {
+                        ctx.print(text_x, line_y(0.0), name.fg(color));
+                        ctx.print(text_x, line_y(1.0), device.ip.clone().fg(color));
+                        ctx.print(text_x, line_y(2.0), device.layers.clone().fg(color));
+                        ctx.print(text_x, line_y(3.0), rounds_window_text.fg(color));
                     } else {
-                        ctx.print(text_x, text_y + 4.5, name.clone());
-                        ctx.print(text_x, text_y + 1.2, ip.clone());
-                        ctx.print(text_x, text_y - 1.2, layers.clone());
-                        ctx.print(text_x, text_y - 4.5, rounds_window_text);
+                        ctx.print(text_x, line_y(0.0), name);
+                        ctx.print(text_x, line_y(1.0), device.ip.clone());
+                        ctx.print(text_x, line_y(2.0), device.layers.clone());
+                        ctx.print(text_x, line_y(3.0), rounds_window_text);
+                    }
+                    let mut extra_y = line_y(4.0);
+                    if let Some(latency) = &device.latency {
+                        ctx.print(
+                            text_x,
+                            extra_y,
+                            format!("RTT: {}", format_latency(latency)).fg(latency_color(latency)),
+                        );
+                        extra_y -= cell_height;
+                    }
+                    // devices that can not fit their KV cache are flagged
+                    match &device.memory {
+                        Some((label, true)) => {
+                            ctx.print(text_x, extra_y, format!("{} (over)", label).red())
+                        }
+                        Some((label, false)) => ctx.print(text_x, extra_y, label.clone().gray()),
+                        None => {}
                     }
                 }
             });
 
         frame.render_widget(canvas, area);
+        frame.render_widget(legend, canvas_area);
     }
 
     pub(super) fn handle_topology_ring_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
+        if self.state.topology.editor.is_some() {
+            self.handle_assignment_editor_input(key);
+            return;
+        }
+
+        let viewport = &mut self.state.topology.ring_viewport;
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => {
+                self.status_message.clear();
                 self.view = AppView::Menu;
             }
-            KeyCode::Up => self.topology_device_up(),
-            KeyCode::Down => self.topology_device_down(),
-            KeyCode::Enter => self.open_shard_interaction(),
+            (_, KeyCode::Char('t')) => {
+                self.state.topology.table_layout = !self.state.topology.table_layout
+            }
+            (_, KeyCode::Char('s')) => {
+                self.state.topology.show_solver = !self.state.topology.show_solver
+            }
+            (_, KeyCode::Char('p')) => {
+                if let Some(topology) = &self.topology {
+                    self.state.topology.latency.start(&topology.devices);
+                }
+            }
+            (_, KeyCode::Char('e')) => self.export_topology(true),
+            (_, KeyCode::Char('E')) => self.export_topology(false),
+            // arrows select devices, so panning takes shift (or hjkl)
+            (KeyModifiers::SHIFT, KeyCode::Up) | (_, KeyCode::Char('k')) => viewport.pan(0.0, 1.0),
+            (KeyModifiers::SHIFT, KeyCode::Down) | (_, KeyCode::Char('j')) => {
+                viewport.pan(0.0, -1.0)
+            }
+            (KeyModifiers::SHIFT, KeyCode::Left) | (_, KeyCode::Char('h')) => {
+                viewport.pan(-1.0, 0.0)
+            }
+            (KeyModifiers::SHIFT, KeyCode::Right) | (_, KeyCode::Char('l')) => {
+                viewport.pan(1.0, 0.0)
+            }
+            // left & right follow the ring, up & down follow the device order
+            (_, KeyCode::Left) => self.topology_device_along_ring(false),
+            (_, KeyCode::Right) => self.topology_device_along_ring(true),
+            (_, KeyCode::Char('+') | KeyCode::Char('=')) => viewport.zoom_in(),
+            (_, KeyCode::Char('-')) => viewport.zoom_out(),
+            (_, KeyCode::Char('0')) => *viewport = RingViewport::default(),
+            (_, KeyCode::Char(c @ '1'..='9')) => {
+                let index = c as usize - '1' as usize;
+                let num_devices = self.topology.as_ref().map_or(0, |t| t.devices.len());
+                if index < num_devices {
+                    self.state.topology.selected_device = index;
+                }
+            }
+            (_, KeyCode::Up) => self.topology_device_up(),
+            (_, KeyCode::Down) => self.topology_device_down(),
+            (_, KeyCode::Enter) => self.open_shard_interaction(),
+            (_, KeyCode::Char('a')) => self.open_assignment_editor(),
+            (_, KeyCode::Tab) => self.switch_topology(true),
+            (_, KeyCode::BackTab) => self.switch_topology(false),
             _ => {}
         }
     }
 
+    /// Export the topology as DOT, or JSON otherwise, reporting the result in the status message.
+    fn export_topology(&mut self, as_dot: bool) {
+        let Some(topology) = &self.topology else {
+            return;
+        };
+
+        self.status_message = match topology.export(as_dot) {
+            Ok(path) => format!("Exported to {}", path.display()),
+            Err(e) => format!("Error: Could not export topology: {}", e),
+        };
+    }
+
+    /// Select the next (or the previous) device along the ring, if the selected one is in it.
+    fn topology_device_along_ring(&mut self, forward: bool) {
+        let neighbor = self
+            .topology
+            .as_ref()
+            .and_then(|t| t.ring_neighbor(self.state.topology.selected_device, forward));
+        if let Some(index) = neighbor {
+            self.state.topology.selected_device = index;
+        }
+    }
+
     fn topology_device_up(&mut self) {
         if let AppView::Topology(super::TopologyView::Ring(TopologyRingView::Loaded)) = &self.view {
             if let Some(topology) = &self.topology {
@@ -387,6 +1068,40 @@ impl crate::App {
         }
     }
 
+    /// Index of the shown topology within all of the active ones.
+    fn active_topology(&self) -> usize {
+        self.topology
+            .as_ref()
+            .and_then(|topology| {
+                self.state
+                    .topology
+                    .topologies
+                    .iter()
+                    .position(|t| t.model == topology.model)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Show the ring of the next (or the previous) model, if there are several.
+    fn switch_topology(&mut self, forward: bool) {
+        let num_topologies = self.state.topology.topologies.len();
+        if num_topologies < 2 {
+            return;
+        }
+
+        let active = self.active_topology();
+        let next = if forward {
+            (active + 1) % num_topologies
+        } else {
+            (active + num_topologies - 1) % num_topologies
+        };
+        let topology = self.state.topology.topologies[next].clone();
+        self.state.topology.selected_device = 0;
+        self.state.topology.diff = None;
+        self.state.topology.latency.start(&topology.devices);
+        self.topology = Some(topology);
+    }
+
     fn open_shard_interaction(&mut self) {
         if let AppView::Topology(super::TopologyView::Ring(TopologyRingView::Loaded)) = &self.view {
             if let Some(topology) = &self.topology {
@@ -401,16 +1116,105 @@ impl crate::App {
     }
 
     /// Handle async operations for topology ring state (called during tick).
+    ///
+    /// A loaded topology is refreshed periodically, so that layer reassignments
+    /// and device changes show up without re-entering the view.
     pub(super) async fn tick_topology_ring(&mut self, state: &TopologyRingView) {
-        if matches!(state, TopologyRingView::Loading) {
+        let refresh_interval = Duration::from_secs(self.config.topology_refresh_interval);
+        let should_refresh = matches!(state, TopologyRingView::Loaded)
+            && !refresh_interval.is_zero()
+            && self.state.topology.refreshed_at.elapsed() >= refresh_interval;
+
+        if let TopologyRingView::Submitting(assignments) = state {
+            self.submit_edited_topology(assignments).await;
+        } else if matches!(state, TopologyRingView::Loading) || should_refresh {
             self.load_topology().await;
+            if let Some(topology) = &self.topology {
+                self.state.topology.latency.start(&topology.devices);
+            }
+        }
+        self.state.topology.latency.poll();
+
+        // busy states change during generation, so they are polled with the devices interval
+        let busy_interval = Duration::from_secs(self.config.devices_refresh_interval);
+        if matches!(state, TopologyRingView::Loaded)
+            && self.state.topology.busy_rx.is_none()
+            && self.state.topology.busy_refreshed_at.elapsed() >= busy_interval
+        {
+            // polled in the background, so that a slow API does not stall the view
+            let api = self.api.clone();
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                if let Ok(devices) = api.get_devices().await {
+                    let busy = devices
+                        .into_values()
+                        .filter(|device| device.is_busy)
+                        .map(|device| device.instance)
+                        .collect();
+                    tx.send(busy).ok();
+                }
+            });
+            self.state.topology.busy_rx = Some(rx);
+            self.state.topology.busy_refreshed_at = Instant::now();
+        }
+        if let Some(rx) = &mut self.state.topology.busy_rx {
+            match rx.try_recv() {
+                Ok(busy) => {
+                    self.state.topology.busy = Some(busy);
+                    self.state.topology.busy_rx = None;
+                }
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.state.topology.busy_rx = None;
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+            }
+        }
+
+        // the KV cache of each shard is estimated from the model config
+        let model = self
+            .topology
+            .as_ref()
+            .and_then(|t| Some((t.model.as_deref()?, t.kv_bits)));
+        if let Some((model, kv_bits)) = model {
+            let kv_cache = &mut self.state.topology.kv_cache;
+            kv_cache.update(model, self.config.seq_len, kv_bits);
         }
+        self.state.topology.kv_cache.poll();
     }
 
     /// Load topology asynchronously and update state.
     async fn load_topology(&mut self) {
-        match self.api.get_topology().await {
-            Ok(topology) => {
+        match self.api.get_topologies().await {
+            Ok(Topologies {
+                topologies,
+                is_multi_model,
+            }) => {
+                // keep showing the ring of the same model, as there may be several
+                let active = self
+                    .topology
+                    .as_ref()
+                    .and_then(|old| topologies.iter().position(|t| t.model == old.model))
+                    .unwrap_or(0);
+                let topology = topologies.get(active).cloned();
+                self.state.topology.topologies = topologies;
+                self.state.topology.is_multi_model = is_multi_model;
+
+                // keep the selection within bounds, in case devices have left
+                let num_devices = topology.as_ref().map_or(0, |t| t.devices.len());
+                self.state.topology.selected_device = self
+                    .state
+                    .topology
+                    .selected_device
+                    .min(num_devices.saturating_sub(1));
+
+                // keep the changes since the last refresh, until there are new ones
+                let diff = match (&self.topology, &topology) {
+                    (Some(old), Some(new)) => TopologyDiff::between(old, new),
+                    _ => None,
+                };
+                if diff.is_some() {
+                    self.state.topology.diff = diff;
+                }
                 self.topology = topology;
                 self.view = AppView::Topology(super::TopologyView::Ring(TopologyRingView::Loaded));
             }
@@ -441,6 +1245,8 @@ impl crate::App {
                 )));
             }
         }
+
+        self.state.topology.refreshed_at = Instant::now();
     }
 }
 
@@ -448,6 +1254,189 @@ impl crate::App {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_to_dot() {
+        let topology: TopologyInfo = serde_json::from_value(serde_json::json!({
+            "model": "Qwen/Qwen3-4B-MLX-4bit",
+            "num_layers": 36,
+            "devices": [{
+                "instance": "shard-1",
+                "server_port": 8081,
+                "shard_port": 58081,
+                "local_ip": "192.168.1.2",
+                "thunderbolt": null
+            }],
+            "assignments": [{
+                "instance": "shard-1",
+                "layers": [[0, 1, 2]],
+                "next_instance": "shard-1",
+                "window_size": 3,
+                "residency_size": 3
+            }],
+            "kv_bits": "8bit"
+        }))
+        .unwrap();
+
+        let expected = [
+            "digraph topology {",
+            "  label=\"Qwen/Qwen3-4B-MLX-4bit (36 layers)\";",
+            "  node [shape=box];",
+            "  \"shard-1\" [label=\"shard-1\\n192.168.1.2:58081 (8081)\\nlayers [0..2]\\nwindow 3\"];",
+            "  \"shard-1\" -> \"shard-1\";",
+            "}",
+        ]
+        .join("\n");
+        assert_eq!(topology.to_dot(), expected);
+    }
+
+    #[test]
+    fn test_thunderbolt_links() {
+        let device = |instance: &str, own: &str, connected: &[&str]| {
+            let tb = |uuid: &str| serde_json::json!({ "uuid": uuid, "name": "thunderboltusb4_bus_1", "device": "Mac15,12" });
+            serde_json::json!({
+                "instance": instance,
+                "server_port": 8081,
+                "shard_port": 58081,
+                "local_ip": "192.168.1.2",
+                "thunderbolt": {
+                    "ip_addr": "169.254.0.1",
+                    "instances": [[tb(own), connected.iter().map(|c| tb(c)).collect::<Vec<_>>()]]
+                }
+            })
+        };
+        let topology: TopologyInfo = serde_json::from_value(serde_json::json!({
+            "model": null,
+            "num_layers": 36,
+            "devices": [
+                device("shard-1", "a", &["b"]),
+                device("shard-2", "b", &["a", "c"]),
+                device("shard-3", "c", &["b"]),
+                device("shard-4", "d", &["unknown"]),
+            ],
+            "assignments": [],
+            "kv_bits": "8bit"
+        }))
+        .unwrap();
+
+        assert_eq!(topology.thunderbolt_links(), vec![(0, 1), (1, 2)]);
+    }
+
+    /// A topology of the given devices with their layers, in a single round each.
+    fn topology(devices: &[(&str, Vec<u32>)]) -> TopologyInfo {
+        serde_json::from_value(serde_json::json!({
+            "model": null,
+            "num_layers": 36,
+            "devices": devices.iter().map(|(instance, _)| serde_json::json!({
+                "instance": instance,
+                "server_port": 8081,
+                "shard_port": 58081,
+                "local_ip": "192.168.1.2",
+                "thunderbolt": null
+            })).collect::<Vec<_>>(),
+            "assignments": devices.iter().map(|(instance, layers)| serde_json::json!({
+                "instance": instance,
+                "layers": [layers],
+                "next_instance": instance,
+                "window_size": 1,
+                "residency_size": 1
+            })).collect::<Vec<_>>(),
+            "kv_bits": "8bit"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_topology_diff() {
+        let old = topology(&[("shard-1", vec![0, 1]), ("shard-2", vec![2, 3])]);
+        let new = topology(&[("shard-1", vec![0]), ("shard-3", vec![1, 2, 3])]);
+        assert!(TopologyDiff::between(&old, &old).is_none());
+
+        let diff = TopologyDiff::between(&old, &new).unwrap();
+        assert_eq!(diff.changed.len(), 2);
+        assert_eq!(diff.changed["shard-1"], DeviceChange::Reassigned);
+        assert_eq!(diff.changed["shard-3"], DeviceChange::Added);
+        assert_eq!(diff.removed, vec!["shard-2"]);
+    }
+
+    #[test]
+    fn test_ring_neighbor() {
+        // devices are listed in a different order than the ring
+        let mut topology = topology(&[
+            ("shard-1", vec![0, 1]),
+            ("shard-2", vec![4, 5]),
+            ("shard-3", vec![2, 3]),
+        ]);
+        for (instance, next) in [
+            ("shard-1", "shard-3"),
+            ("shard-3", "shard-2"),
+            ("shard-2", "shard-1"),
+        ] {
+            let assignment = topology
+                .assignments
+                .iter_mut()
+                .find(|a| a.instance == instance);
+            assignment.unwrap().next_instance = next.to_string();
+        }
+
+        assert_eq!(topology.ring_neighbor(0, true), Some(2));
+        assert_eq!(topology.ring_neighbor(2, true), Some(1));
+        assert_eq!(topology.ring_neighbor(0, false), Some(1));
+        assert_eq!(topology.ring_neighbor(3, true), None);
+        assert_eq!(topology.ring_order(), vec![0, 2, 1]);
+
+        // the instances of the services contain those of their devices
+        for assignment in &mut topology.assignments {
+            assignment.instance = format!("{}.local", assignment.instance);
+            assignment.next_instance = format!("{}.local", assignment.next_instance);
+        }
+        assert_eq!(topology.ring_neighbor(0, true), Some(2));
+        assert_eq!(topology.ring_neighbor(0, false), Some(1));
+    }
+
+    #[test]
+    fn test_pipeline_end() {
+        let topology = topology(&[
+            ("shard-1", vec![2, 3]),
+            ("shard-2", vec![0, 1]),
+            ("shard-3", vec![4, 5]),
+        ]);
+        assert_eq!(topology.pipeline_end("shard-2"), Some(PipelineEnd::Entry));
+        assert_eq!(topology.pipeline_end("shard-3"), Some(PipelineEnd::Exit));
+        assert_eq!(topology.pipeline_end("shard-1"), None);
+        assert_eq!(topology.pipeline_end("shard-4"), None);
+    }
+
+    #[test]
+    fn test_ring_viewport() {
+        // cells are twice as tall as they are wide, so this area is square on screen
+        let area = Rect::new(0, 0, 80, 40);
+        let mut viewport = RingViewport::default();
+        assert_eq!(viewport.bounds(area), ([-60.0, 60.0], [-60.0, 60.0]));
+        assert_eq!(viewport.cell_size(area), (1.5, 3.0));
+
+        // a wide area shows more horizontally
+        let wide = Rect::new(0, 0, 160, 40);
+        assert_eq!(viewport.bounds(wide), ([-120.0, 120.0], [-60.0, 60.0]));
+
+        // zooming in halves the visible area, and pans by smaller steps
+        viewport.zoom = 2.0;
+        viewport.pan(1.0, -1.0);
+        assert_eq!(viewport.bounds(area), ([-24.0, 36.0], [-36.0, 24.0]));
+
+        // the ring leaves room for the labels, unless the area is too small
+        assert_eq!(RingViewport::ring_radius(area), 12.0);
+        assert_eq!(
+            RingViewport::ring_radius(Rect::new(0, 0, 20, 10)),
+            MIN_RING_RADIUS
+        );
+
+        // zoom is limited
+        for _ in 0..20 {
+            viewport.zoom_out();
+        }
+        assert_eq!(viewport.zoom, RingViewport::MIN_ZOOM);
+    }
+
     #[test]
     fn test_format_layers() {
         let layers = vec![
@@ -1,4 +1,7 @@
-use crate::common::ShardHealth;
+use super::ShardConsole;
+use crate::common::{ShardHealth, ShardResources};
+use crate::utils::{centered_rect, format_bytes};
+use crate::widgets::ConfirmDialog;
 use crate::{App, app::AppView, views::topology::TopologyView};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
@@ -6,16 +9,42 @@ use ratatui::{
     layout::{Constraint, Layout},
     style::{Color, Style, Stylize},
     text::Line,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Sparkline},
 };
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ShardView {
     Loading,
-    Loaded(ShardHealth),
+    Loaded(Box<ShardHealth>),
     Error(String),
+    /// Restart has been confirmed, and is being requested.
+    Restarting,
 }
 
+/// Admin endpoint of the shard that restarts (i.e. resets) it.
+const RESTART_PATH: &str = "/restart";
+
+/// Endpoint of the shard that lists the models whose weights it has cached, see [`CachedModel`].
+const CACHED_MODELS_PATH: &str = "/models";
+
+/// Weights of a model that a shard has cached on its disk.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct CachedModel {
+    pub model: String,
+    /// Disk space taken by the weights, in bytes.
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+/// Admin endpoint of the shard that deletes the cached weights of a model,
+/// expecting `{"model": ...}` and responding with the freed bytes (if known).
+const DELETE_WEIGHTS_PATH: &str = "/delete_weights";
+
+/// Admin endpoint of the shard that sets its log level, expecting `{"level": ...}`
+/// with a level name of Python's `logging`, e.g. `DEBUG`.
+const LOG_LEVEL_PATH: &str = "/log_level";
+
 impl ShardView {
     /// Fetch shard health from the shard's HTTP endpoint
     pub async fn fetch(device_ip: &str, http_port: u16) -> Result<ShardHealth, String> {
@@ -28,13 +57,118 @@ impl ShardView {
             return Err(format!("Shard returned error: {}", response.status()));
         }
 
-        let health: ShardHealth = response
+        response
             .json()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
 
+    /// Fetch shard health along with its resources, which may be reported separately
+    /// by the `/stats` endpoint; only for views that show them, to spare the others
+    /// from a second request.
+    pub async fn fetch_with_resources(
+        device_ip: &str,
+        http_port: u16,
+    ) -> Result<ShardHealth, String> {
+        let mut health = Self::fetch(device_ip, http_port).await?;
+        if health.resources.is_empty() {
+            health.resources = Self::fetch_stats(device_ip, http_port)
+                .await
+                .unwrap_or_default();
+        }
         Ok(health)
     }
+
+    /// Fetch the resource usage from the shard's `/stats` endpoint, if it has one.
+    async fn fetch_stats(device_ip: &str, http_port: u16) -> Option<ShardResources> {
+        let url = format!("http://{}:{}/stats", device_ip, http_port);
+        let response = reqwest::get(&url).await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.json().await.ok()
+    }
+
+    /// Request the shard to restart, so that a wedged shard can be recovered.
+    pub async fn restart(device_ip: &str, http_port: u16) -> Result<(), String> {
+        let url = format!("http://{}:{}{}", device_ip, http_port, RESTART_PATH);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to shard: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Shard returned error: {}", response.status()));
+        }
+        Ok(())
+    }
+
+    /// Fetch the models whose weights the shard has cached, either listed as is
+    /// or under `models`.
+    pub async fn fetch_cached_models(
+        device_ip: &str,
+        http_port: u16,
+    ) -> Result<Vec<CachedModel>, String> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum CachedModels {
+            List(Vec<CachedModel>),
+            Object { models: Vec<CachedModel> },
+        }
+
+        let url = format!("http://{}:{}{}", device_ip, http_port, CACHED_MODELS_PATH);
+        let response = reqwest::get(&url)
+            .await
+            .map_err(|e| format!("Failed to connect to shard: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Shard returned error: {}", response.status()));
+        }
+
+        match response.json().await {
+            Ok(CachedModels::List(models) | CachedModels::Object { models }) => Ok(models),
+            Err(e) => Err(format!("Failed to parse models: {}", e)),
+        }
+    }
+
+    /// Request the shard to delete the cached weights of the model, to recover disk space.
+    ///
+    /// Returns the freed bytes, if the shard reports them as `freed_bytes`.
+    pub async fn delete_weights(
+        device_ip: &str,
+        http_port: u16,
+        model: &str,
+    ) -> Result<Option<u64>, String> {
+        let url = format!("http://{}:{}{}", device_ip, http_port, DELETE_WEIGHTS_PATH);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to shard: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Shard returned error: {}", response.status()));
+        }
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        Ok(body.get("freed_bytes").and_then(|b| b.as_u64()))
+    }
+
+    /// Request the shard to log at the given level, e.g. `DEBUG`, to debug it without SSH.
+    pub async fn set_log_level(device_ip: &str, http_port: u16, level: &str) -> Result<(), String> {
+        let url = format!("http://{}:{}{}", device_ip, http_port, LOG_LEVEL_PATH);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&serde_json::json!({ "level": level }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to shard: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Shard returned error: {}", response.status()));
+        }
+        Ok(())
+    }
 }
 
 impl App {
@@ -54,10 +188,13 @@ impl App {
         let [title_area, content_area, footer_area] = vertical.areas(area);
 
         // Title
-        let title = Line::from(format!("Shard: {}", device_instance))
-            .bold()
-            .cyan()
-            .centered();
+        let title = Line::from(format!(
+            "Shard: {}",
+            self.config.device_name(device_instance)
+        ))
+        .bold()
+        .cyan()
+        .centered();
         frame.render_widget(
             Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
             title_area,
@@ -65,12 +202,16 @@ impl App {
 
         // Content
         match state {
-            ShardView::Loading => {
-                let lines = vec![
-                    Line::from(""),
-                    Line::from("Loading shard health...").bold(),
-                    Line::from(""),
-                ];
+            _ if self.state.topology.console.is_some() => {
+                self.draw_shard_console(frame, content_area);
+            }
+            ShardView::Loading | ShardView::Restarting => {
+                let message = if matches!(state, ShardView::Restarting) {
+                    "Restarting shard..."
+                } else {
+                    "Loading shard health..."
+                };
+                let lines = vec![Line::from(""), Line::from(message).bold(), Line::from("")];
                 frame.render_widget(
                     Paragraph::new(lines).block(Block::bordered()).centered(),
                     content_area,
@@ -93,20 +234,48 @@ impl App {
                 );
             }
             ShardView::Loaded(health) => {
-                self.draw_shard_health(frame, content_area, health);
+                let [health_area, queue_area] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(6)])
+                        .areas(content_area);
+                self.draw_shard_health(frame, health_area, health);
+                self.draw_queue_sparkline(frame, queue_area, device_instance);
             }
         }
 
         // Footer
-        frame.render_widget(
-            Paragraph::new("Press Esc to go back to topology")
-                .centered()
-                .gray(),
-            footer_area,
-        );
+        let hint = if self.state.topology.console.is_some() {
+            "Tab: Next field | ←→: Method | Enter: Send | ↑↓/PgUp/PgDn: Scroll | Esc: Close console"
+        } else {
+            "c: Request console | r: Restart shard | y: Copy health | u: Copy URL | Esc: Back to topology"
+        };
+        let mut footer = vec![Line::from(hint).gray()];
+        if self.status_message.starts_with("Error") {
+            footer.push(Line::from(self.status_message.as_str()).red());
+        } else if !self.status_message.is_empty() {
+            footer.push(Line::from(self.status_message.as_str()).green());
+        }
+        frame.render_widget(Paragraph::new(footer).centered(), footer_area);
+
+        if self.state.topology.confirm_restart {
+            let lines = vec![
+                Line::from(""),
+                Line::from(format!(
+                    "Restart shard {}?",
+                    self.config.device_name(device_instance)
+                ))
+                .bold(),
+                Line::from(""),
+                Line::from("Any ongoing generation through this shard will fail."),
+            ];
+            frame.render_widget(
+                ConfirmDialog::new("Restart Shard", lines),
+                centered_rect(50, 30, area),
+            );
+        }
     }
 
-    fn draw_shard_health(
+    /// Draw the health of a shard, also used by the device details.
+    pub(crate) fn draw_shard_health(
         &mut self,
         frame: &mut Frame,
         area: ratatui::layout::Rect,
@@ -192,6 +361,45 @@ impl App {
         };
         let queue_display = format!("  Queue Size:     {queue_health} ({})", queue_status).into();
         lines.push(queue_display);
+        lines.push("".into());
+
+        // Resource usage, if reported
+        lines.push("━━━ Resources ━━━".bold().cyan().into());
+        let resources = &health.resources;
+        if resources.is_empty() {
+            lines.push("  Not reported by this shard".dark_gray().into());
+        }
+        match (resources.memory_used, resources.memory_total) {
+            (Some(used), Some(total)) if total > 0 => {
+                let ratio = used as f64 / total as f64;
+                let usage = format!(
+                    "{} / {} ({:.0}%)",
+                    format_bytes(used),
+                    format_bytes(total),
+                    ratio * 100.0
+                );
+                let usage = match ratio {
+                    r if r >= 0.9 => usage.bold().red(),
+                    r if r >= 0.75 => usage.bold().yellow(),
+                    _ => usage.bold().green(),
+                };
+                lines.push(Line::from(vec!["  Memory:         ".into(), usage]));
+            }
+            (Some(used), _) => {
+                lines.push(format!("  Memory:         {}", format_bytes(used)).into());
+            }
+            _ => {}
+        }
+        if let Some(weights) = resources.weights_bytes {
+            lines.push(format!("  Weights Loaded: {}", format_bytes(weights)).into());
+        }
+        if let Some(kv_cache) = resources.kv_cache_bytes {
+            lines.push(format!("  KV Cache:       {}", format_bytes(kv_cache)).into());
+        }
+        if let (Some(used), Some(total)) = (resources.disk_used, resources.disk_total) {
+            let disk = format!("{} / {}", format_bytes(used), format_bytes(total));
+            lines.push(format!("  Disk:           {}", disk).into());
+        }
 
         frame.render_widget(
             Paragraph::new(lines).block(Block::bordered().title("Health Status")),
@@ -199,30 +407,166 @@ impl App {
         );
     }
 
+    /// Draw the queue size of the shard over time, where a rising trend means backpressure.
+    fn draw_queue_sparkline(&self, frame: &mut Frame, area: ratatui::layout::Rect, instance: &str) {
+        let samples = self
+            .state
+            .topology
+            .latency
+            .queue_sizes
+            .get(instance)
+            .collect::<Vec<_>>();
+        // the latest samples are kept, as many as fit within the borders
+        let visible = &samples[samples
+            .len()
+            .saturating_sub(area.width.saturating_sub(2) as usize)..];
+        let max = visible.iter().copied().max().unwrap_or_default();
+
+        let title = format!(" Queue Size (max {} over {} samples) ", max, visible.len());
+        let color = match max {
+            0 => Color::Green,
+            1..=9 => Color::Yellow,
+            _ => Color::Red,
+        };
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::bordered().title(title))
+                .data(visible)
+                // at least 1 so that an idle queue is a flat line at the bottom
+                .max(max.max(1))
+                .style(Style::default().fg(color)),
+            area,
+        );
+    }
+
     pub(super) fn handle_shard_interaction_input(&mut self, key: KeyEvent) {
-        if key.code == KeyCode::Esc {
-            // go back to topology view
-            if let AppView::Topology(TopologyView::Shard(_, _)) = &self.view {
+        let AppView::Topology(TopologyView::Shard(device, state)) = &self.view else {
+            return;
+        };
+        let device = device.clone();
+
+        if self.state.topology.confirm_restart {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.state.topology.confirm_restart = false;
+                    self.status_message.clear();
+                    self.view =
+                        AppView::Topology(TopologyView::Shard(device, ShardView::Restarting));
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.state.topology.confirm_restart = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.state.topology.console.is_some() {
+            self.handle_shard_console_input(key, &device);
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => {
+                // go back to topology view
+                self.status_message.clear();
                 self.view =
                     AppView::Topology(super::TopologyView::Ring(super::TopologyRingView::Loaded));
             }
+            KeyCode::Char('r') if !matches!(state, ShardView::Loading | ShardView::Restarting) => {
+                self.state.topology.confirm_restart = true;
+            }
+            KeyCode::Char('c') => {
+                self.state.topology.console = Some(ShardConsole::default());
+            }
+            KeyCode::Char('y') => match state {
+                ShardView::Loaded(health) => match serde_json::to_string_pretty(health) {
+                    Ok(json) => self.copy_shard_info(json, "health"),
+                    Err(err) => self.status_message = format!("Error: {}", err),
+                },
+                _ => self.status_message = "Error: shard health is not loaded".to_string(),
+            },
+            KeyCode::Char('u') => {
+                let base_url = self
+                    .topology
+                    .as_ref()
+                    .and_then(|t| t.devices.iter().find(|d| d.instance == device))
+                    .map(|d| format!("http://{}:{}", d.local_ip, d.server_port));
+                match base_url {
+                    Some(base_url) => self.copy_shard_info(base_url, "base URL"),
+                    None => {
+                        self.status_message =
+                            format!("Error: device '{}' not found in topology", device)
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
+    /// Copy the shard's info to the system clipboard, e.g. for curl or bug reports.
+    ///
+    /// This uses the OSC 52 escape sequence, like copying a chat does.
+    fn copy_shard_info(&mut self, info: String, what: &str) {
+        self.status_message = match crossterm::execute!(
+            std::io::stdout(),
+            crossterm::clipboard::CopyToClipboard::to_clipboard_from(info)
+        ) {
+            Ok(()) => format!("Copied the shard {} to the clipboard", what),
+            Err(err) => format!("Error copying the shard {}: {}", what, err),
+        };
+    }
+
     /// Handle async operations for shard interaction state (called during tick).
+    ///
+    /// Loaded (or failed) health is re-fetched periodically, so that the queue
+    /// size and model status update live.
     pub(super) async fn tick_topology_shard(&mut self, device: &str, state: &ShardView) {
-        if matches!(state, ShardView::Loading) {
+        if let Some(console) = &mut self.state.topology.console {
+            console.poll();
+        }
+
+        let refresh_interval = Duration::from_secs(self.config.shard_refresh_interval);
+        let should_refresh = matches!(state, ShardView::Loaded(_) | ShardView::Error(_))
+            && !refresh_interval.is_zero()
+            && !self.state.topology.confirm_restart
+            && self.state.topology.console.is_none()
+            && self.state.topology.shard_refreshed_at.elapsed() >= refresh_interval;
+
+        if matches!(state, ShardView::Restarting) {
+            let dev = self
+                .topology
+                .as_ref()
+                .and_then(|t| t.devices.iter().find(|d| d.instance == device));
+            let result = match dev {
+                Some(dev) => ShardView::restart(&dev.local_ip, dev.server_port).await,
+                None => Err(format!("Device '{}' not found in topology", device)),
+            };
+            self.status_message = match result {
+                Ok(()) => format!("Restart requested for {}", device),
+                Err(err) => format!("Error restarting shard: {}", err),
+            };
+
+            // reload the health to see the shard coming back
+            self.view =
+                AppView::Topology(TopologyView::Shard(device.to_string(), ShardView::Loading));
+        } else if matches!(state, ShardView::Loading) || should_refresh {
             // Find the device in the topology to get its IP and port
             if let Some(topology) = &self.topology {
                 if let Some(dev) = topology.devices.iter().find(|d| d.instance == device) {
                     let device_ip = dev.local_ip.clone();
                     let http_port = dev.server_port;
 
-                    match ShardView::fetch(&device_ip, http_port).await {
+                    match ShardView::fetch_with_resources(&device_ip, http_port).await {
                         Ok(health) => {
+                            self.state
+                                .topology
+                                .latency
+                                .queue_sizes
+                                .record(device, health.queue_size);
                             self.view = AppView::Topology(TopologyView::Shard(
                                 device.to_string(),
-                                ShardView::Loaded(health),
+                                ShardView::Loaded(Box::new(health)),
                             ));
                         }
                         Err(err) => {
@@ -244,6 +588,7 @@ impl App {
                     ShardView::Error("No topology information available".to_string()),
                 ));
             }
+            self.state.topology.shard_refreshed_at = Instant::now();
         }
     }
 }
@@ -0,0 +1,166 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Paragraph, Wrap},
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Fields of an optimized solution of the solver, see [`crate::common::TopologyInfo::solution`].
+///
+/// Per-device values are in the order of the devices of the topology,
+/// and the sets hold device indices in the same order.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SolverDetails {
+    /// Number of layers per window of each device.
+    pub w: Vec<u32>,
+    /// Number of layers of each device that stay resident on the GPU.
+    pub n: Vec<u32>,
+    /// Number of rounds over the ring.
+    pub k: Option<u32>,
+    /// Value of the objective, i.e. the estimated latency.
+    pub obj_value: Option<f64>,
+    /// Device sets by name, e.g. `M1` to `M3`.
+    pub sets: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+impl SolverDetails {
+    /// Details of the solution, which may be wrapped as `{"Optimized": {...}}`.
+    ///
+    /// Returns `None` if the solution is not an object, e.g. for manual topologies.
+    pub fn from_solution(solution: &serde_json::Value) -> Option<Self> {
+        let solution = match solution.get("Optimized") {
+            Some(optimized) => optimized,
+            None => solution,
+        };
+        if !solution.is_object() {
+            return None;
+        }
+        serde_json::from_value(solution.clone()).ok()
+    }
+
+    /// Lines of the panel, with devices shown by name (see [`crate::config::Config::device_name`]).
+    pub fn lines<'a>(&self, device_name: impl Fn(usize) -> Option<&'a str>) -> Vec<Line<'static>> {
+        let or_unknown = |value: Option<String>| value.unwrap_or_else(|| "?".to_string());
+        let mut lines = vec![
+            Line::from(vec![
+                "Objective: ".into(),
+                or_unknown(self.obj_value.map(|v| format!("{:.4}", v))).bold(),
+            ]),
+            Line::from(vec![
+                "Rounds (k): ".into(),
+                or_unknown(self.k.map(|k| k.to_string())).bold(),
+            ]),
+            Line::from(""),
+            Line::from("Device: w / n".bold()),
+        ];
+
+        for i in 0..self.w.len().max(self.n.len()) {
+            let name = device_name(i).map_or_else(|| format!("#{}", i), str::to_string);
+            lines.push(Line::from(format!(
+                "  {}: {} / {}",
+                name,
+                or_unknown(self.w.get(i).map(u32::to_string)),
+                or_unknown(self.n.get(i).map(u32::to_string)),
+            )));
+        }
+
+        if !self.sets.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Sets".bold()));
+        }
+        for (set, members) in &self.sets {
+            let members = members
+                .iter()
+                .map(|member| match member {
+                    serde_json::Value::Number(i) => i
+                        .as_u64()
+                        .and_then(|i| device_name(i as usize))
+                        .map_or_else(|| i.to_string(), str::to_string),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>();
+            let members = if members.is_empty() {
+                "-".to_string()
+            } else {
+                members.join(", ")
+            };
+            lines.push(Line::from(format!("  {}: {}", set, members)));
+        }
+
+        lines
+    }
+}
+
+impl crate::App {
+    /// Draw the details of the solver's solution next to the ring, to compare
+    /// what the solver decided with the (possibly edited) assignments.
+    pub(super) fn draw_solver_panel(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" Solver ");
+        let lines = match self.topology.as_ref().and_then(|t| t.solution.as_ref()) {
+            None => vec![Line::from("Manual topology, there is no solution.").dark_gray()],
+            Some(solution) => match SolverDetails::from_solution(solution) {
+                Some(details) => {
+                    let devices = self.topology.as_ref().map(|t| &t.devices);
+                    details.lines(|i| {
+                        devices
+                            .and_then(|devices| devices.get(i))
+                            .map(|d| self.config.device_name(&d.instance))
+                    })
+                }
+                None => vec![Line::from(solution.to_string())],
+            },
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_solver_details() {
+        let solution = json!({
+            "Optimized": {
+                "w": [4, 2],
+                "n": [4, 1],
+                "k": 2,
+                "obj_value": 1.5,
+                "sets": {"M1": [0], "M2": [], "M3": [1]}
+            }
+        });
+        let details = SolverDetails::from_solution(&solution).unwrap();
+        assert_eq!(details.w, vec![4, 2]);
+        assert_eq!(details.k, Some(2));
+        assert_eq!(details.sets.len(), 3);
+
+        let names = ["mac-1", "mac-2"];
+        let lines = details
+            .lines(|i| names.get(i).copied())
+            .iter()
+            .map(Line::to_string)
+            .collect::<Vec<_>>();
+        assert!(lines.contains(&"  mac-2: 2 / 1".to_string()));
+        assert!(lines.contains(&"  M1: mac-1".to_string()));
+        assert!(lines.contains(&"  M2: -".to_string()));
+
+        // fields are optional, but the solution must be an object
+        assert_eq!(
+            SolverDetails::from_solution(&json!({})),
+            Some(SolverDetails::default())
+        );
+        assert_eq!(SolverDetails::from_solution(&json!("Manual")), None);
+    }
+}
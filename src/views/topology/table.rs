@@ -0,0 +1,128 @@
+use super::{format_latency, latency_color};
+use crate::common::TopologyInfo;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table},
+};
+
+impl crate::App {
+    /// Draw the topology as a table, which is more usable than the ring on
+    /// narrow terminals and easier to copy from.
+    pub(super) fn draw_topology_table(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(topology) = &self.topology else {
+            frame.render_widget(
+                Paragraph::new("No topology data available")
+                    .block(Block::bordered())
+                    .centered(),
+                area,
+            );
+            return;
+        };
+        let recent_diff = self
+            .state
+            .topology
+            .diff
+            .as_ref()
+            .filter(|diff| diff.is_recent());
+
+        let header = Row::new(
+            [
+                "Device",
+                "IP Address",
+                "HTTP Port",
+                "gRPC Port",
+                "Layers",
+                "Window",
+                "Next Hop",
+                "RTT",
+            ]
+            .map(|title| Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))),
+        )
+        .style(Style::default().fg(Color::Yellow))
+        .bottom_margin(1);
+
+        let rows = topology
+            .devices
+            .iter()
+            .enumerate()
+            .map(|(i, device)| {
+                let assignment = topology.assignment_of(&device.instance);
+                let change =
+                    recent_diff.and_then(|diff| diff.changed.get(&device.instance).copied());
+
+                let style = if i == self.state.topology.selected_device {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else if let Some(change) = change {
+                    Style::default().fg(change.color())
+                } else {
+                    Style::default().fg(Color::Green)
+                };
+
+                let (layers, window, next_hop) = match assignment {
+                    Some(assignment) => (
+                        TopologyInfo::format_layers(&assignment.layers),
+                        assignment.window_size.to_string(),
+                        self.config
+                            .device_name(&assignment.next_instance)
+                            .to_string(),
+                    ),
+                    None => ("-".to_string(), "-".to_string(), "-".to_string()),
+                };
+
+                Row::new(vec![
+                    match topology.pipeline_end(&device.instance) {
+                        Some(end) => Cell::from(Line::from(vec![
+                            self.config.device_name(&device.instance).to_string().into(),
+                            format!(" ({})", end).fg(end.color()),
+                        ])),
+                        None => Cell::from(self.config.device_name(&device.instance).to_string()),
+                    },
+                    Cell::from(device.local_ip.clone()),
+                    Cell::from(device.server_port.to_string()),
+                    Cell::from(device.shard_port.to_string()),
+                    Cell::from(layers),
+                    Cell::from(window),
+                    Cell::from(next_hop),
+                    match self.state.topology.latency.get(&device.instance) {
+                        Some(latency) => {
+                            Cell::from(format_latency(latency)).fg(latency_color(latency))
+                        }
+                        None => Cell::from("-"),
+                    },
+                ])
+                .style(style)
+            })
+            .collect::<Vec<_>>();
+
+        let widths = [
+            Constraint::Percentage(18), // Device
+            Constraint::Percentage(14), // IP Address
+            Constraint::Percentage(8),  // HTTP Port
+            Constraint::Percentage(8),  // gRPC Port
+            Constraint::Percentage(20), // Layers
+            Constraint::Percentage(6),  // Window
+            Constraint::Percentage(16), // Next Hop
+            Constraint::Percentage(10), // RTT
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(
+                Block::bordered()
+                    .title(format!(
+                        "Model: {}  |  Layers: {}",
+                        topology.model.as_deref().unwrap_or("<not loaded>"),
+                        topology.num_layers
+                    ))
+                    .title_style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .column_spacing(1);
+
+        frame.render_widget(table, area);
+    }
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+/// A modal asking to confirm a destructive action, rendered over the given area
+/// which is usually a centered rectangle of the screen.
+///
+/// The dialog only draws itself, the caller handles the keys shown in its hint.
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog<'a> {
+    title: String,
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> ConfirmDialog<'a> {
+    pub fn new(title: impl Into<String>, lines: Vec<Line<'a>>) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+        }
+    }
+}
+
+impl Widget for ConfirmDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [message_area, hint_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        Paragraph::new(self.lines)
+            .wrap(Wrap { trim: false })
+            .render(message_area, buf);
+        Paragraph::new("y/Enter: Confirm  |  n/Esc: Cancel")
+            .centered()
+            .gray()
+            .render(hint_area, buf);
+    }
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+/// A small bordered box explaining the colors of a chart, one entry per line.
+///
+/// It is rendered at the bottom-right corner of the given area, on top of its contents.
+#[derive(Debug, Clone, Default)]
+pub struct Legend<'a> {
+    entries: Vec<(&'a str, &'a str, Color)>,
+}
+
+impl<'a> Legend<'a> {
+    /// Add an entry, with the symbol (e.g. `●`) drawn in the color followed by its label.
+    pub fn entry(mut self, symbol: &'a str, label: &'a str, color: Color) -> Self {
+        self.entries.push((symbol, label, color));
+        self
+    }
+
+    /// Area that the legend takes within the given area.
+    fn area(&self, area: Rect) -> Rect {
+        let label_width = self
+            .entries
+            .iter()
+            .map(|(symbol, label, _)| symbol.chars().count() + 1 + label.chars().count())
+            .max()
+            .unwrap_or_default() as u16;
+        // borders on each side
+        let width = (label_width + 4).min(area.width);
+        let height = (self.entries.len() as u16 + 2).min(area.height);
+        Rect {
+            x: area.right() - width,
+            y: area.bottom() - height,
+            width,
+            height,
+        }
+    }
+}
+
+impl Widget for Legend<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = self.area(area);
+        let lines = self
+            .entries
+            .into_iter()
+            .map(|(symbol, label, color)| {
+                Line::from(vec![symbol.fg(color), " ".into(), label.into()])
+            })
+            .collect::<Vec<_>>();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Legend")
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .render(area, buf);
+    }
+}
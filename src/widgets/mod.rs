@@ -1,2 +1,8 @@
 mod model_selector;
 pub use model_selector::*;
+
+mod confirm_dialog;
+pub use confirm_dialog::*;
+
+mod legend;
+pub use legend::*;
@@ -1,6 +1,7 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{
         Block, List, ListItem, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
         Widget,
@@ -16,6 +17,19 @@ pub struct ModelSelectorState {
     offset: usize,
     /// Scrollbar state.
     scrollbar_state: ScrollbarState,
+    /// Typed filter, only the matching items are listed (see [`fuzzy_match`]).
+    filter: String,
+}
+
+/// Whether the characters of the pattern appear in the text in order,
+/// ignoring case, e.g. `q3b` matches `Qwen3-4B`.
+pub fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|p| chars.any(|c| c == p))
 }
 
 impl ModelSelectorState {
@@ -24,11 +38,56 @@ impl ModelSelectorState {
         Self::default()
     }
 
-    /// Get the currently selected index.
+    /// Get the currently selected index, among the items that match the filter.
     pub fn selected(&self) -> usize {
         self.selected
     }
 
+    /// Index of the selected item among all items, if any item matches the filter.
+    pub fn selected_item<S: AsRef<str>>(
+        &self,
+        items: impl IntoIterator<Item = S>,
+    ) -> Option<usize> {
+        self.matches(items).get(self.selected).copied()
+    }
+
+    /// Indices of the items that match the filter, in their order.
+    pub fn matches<S: AsRef<str>>(&self, items: impl IntoIterator<Item = S>) -> Vec<usize> {
+        items
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| fuzzy_match(&self.filter, item.as_ref()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The typed filter.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Type a character of the filter, selecting the first match.
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    /// Delete the last character of the filter.
+    pub fn pop_filter(&mut self) {
+        if self.filter.pop().is_some() {
+            self.selected = 0;
+            self.offset = 0;
+        }
+    }
+
+    /// Clear the filter.
+    pub fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.selected = 0;
+        self.offset = 0;
+    }
+
     /// Set the selected index.
     pub fn select(&mut self, index: usize) {
         self.selected = index;
@@ -56,11 +115,12 @@ impl ModelSelectorState {
         }
     }
 
-    /// Reset selection to 0.
+    /// Reset selection to 0, and clear the filter.
     pub fn reset(&mut self) {
         self.selected = 0;
         self.offset = 0;
         self.scrollbar_state = ScrollbarState::default();
+        self.filter.clear();
     }
 
     /// Update the scroll offset based on selected item and viewport height.
@@ -84,7 +144,8 @@ impl ModelSelectorState {
 /// A widget for selecting items from a list.
 ///
 /// This is a stateful widget that displays a list of items and allows
-/// the user to select one using up/down arrow keys.
+/// the user to select one using up/down arrow keys. Only the items that
+/// match the typed filter of the state are listed.
 ///
 /// ## Example
 ///
@@ -106,6 +167,8 @@ pub struct ModelSelector<'a> {
     block: Option<Block<'a>>,
     /// Prefix for each item (e.g., "  " for indentation).
     item_prefix: &'a str,
+    /// Badges shown after the items, which are not matched by the filter.
+    badges: Option<&'a [Span<'a>]>,
 }
 
 const SELECTED_STYLE: Style = Style::new()
@@ -122,6 +185,7 @@ impl<'a> ModelSelector<'a> {
             items,
             block: None,
             item_prefix: "  ",
+            badges: None,
         }
     }
 
@@ -131,6 +195,12 @@ impl<'a> ModelSelector<'a> {
         self
     }
 
+    /// Set the badges of the items, in the same order as the items.
+    pub fn badges(mut self, badges: &'a [Span<'a>]) -> Self {
+        self.badges = Some(badges);
+        self
+    }
+
     /// Set the prefix for each item.
     pub fn item_prefix(mut self, prefix: &'a str) -> Self {
         self.item_prefix = prefix;
@@ -149,40 +219,50 @@ impl<'a> StatefulWidget for ModelSelector<'a> {
             area.height as usize
         };
 
+        let matches = state.matches(self.items);
+        state.selected = state.selected.min(matches.len().saturating_sub(1));
+
         // Update scroll offset and scrollbar state
         state.update_offset(viewport_height);
-        state.update_scrollbar(self.items.len());
+        state.update_scrollbar(matches.len());
 
         // Calculate visible range
         let start = state.offset;
-        let end = (start + viewport_height).min(self.items.len());
+        let end = (start + viewport_height).min(matches.len());
 
         // Create list items only for visible items
-        let items: Vec<ListItem> = self
-            .items
+        let items: Vec<ListItem> = matches
             .iter()
             .enumerate()
             .skip(start)
             .take(end - start)
-            .map(|(i, item)| {
+            .map(|(i, &item)| {
                 let style = if i == state.selected {
                     SELECTED_STYLE
                 } else {
                     UNSELECTED_STYLE
                 };
-                ListItem::new(format!("{}{}", self.item_prefix, item)).style(style)
+                let mut line = Line::from(format!("{}{}", self.item_prefix, self.items[item]));
+                if let Some(badge) = self.badges.and_then(|badges| badges.get(item)) {
+                    line.push_span(badge.clone());
+                }
+                ListItem::new(line).style(style)
             })
             .collect();
 
         // Create and render the list
         let mut list = List::new(items);
-        if let Some(block) = self.block {
+        if let Some(mut block) = self.block {
+            if !state.filter.is_empty() {
+                let title = format!(" Filter: {} ({} matches) ", state.filter, matches.len());
+                block = block.title_bottom(title);
+            }
             list = list.block(block);
         }
         Widget::render(list, area, buf);
 
         // Render scrollbar if needed (only if there are more items than viewport height)
-        if self.items.len() > viewport_height {
+        if matches.len() > viewport_height {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓"));
@@ -191,3 +271,27 @@ impl<'a> StatefulWidget for ModelSelector<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("", "Qwen/Qwen3-4B-MLX-4bit"));
+        assert!(fuzzy_match("q3 4b", "Qwen/Qwen3-4B-MLX-4bit"));
+        assert!(fuzzy_match("LLAMA", "meta-llama/Llama-3.2-1B"));
+        assert!(!fuzzy_match("b4", "Qwen/Qwen3-4B"));
+        assert!(!fuzzy_match("gemma", "Qwen/Qwen3-4B"));
+
+        let mut state = ModelSelectorState::new();
+        let items = ["Qwen/Qwen3-4B", "meta-llama/Llama-3.2-1B", "Qwen/Qwen3-32B"];
+        "qwen".chars().for_each(|c| state.push_filter(c));
+        state.move_down(state.matches(items).len());
+        assert_eq!(state.selected_item(items), Some(2));
+        state.push_filter('x');
+        assert_eq!(state.selected_item(items), None);
+        state.clear_filter();
+        assert_eq!(state.matches(items), vec![0, 1, 2]);
+    }
+}
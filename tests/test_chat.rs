@@ -9,9 +9,10 @@ async fn test_chat_screen() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let terminal = ratatui::init();
     let mut chat = ChatState::default();
-    chat.messages
+    chat.session_mut()
+        .messages
         .push_back(ChatMessage::new_user("How do you prepare a Menemen?"));
-    chat.messages.push_back(ChatMessage::new_assistant(r#"
+    chat.session_mut().messages.push_back(ChatMessage::new_assistant(r#"
 Menemen - one of the classics of Turkish breakfasts - is a delicious, comforting dish made mainly with eggs, tomatoes, peppers, and olive oil (or butter).
 Here's a traditional way to prepare it, plus a few regional and personal variations.
 